@@ -15,7 +15,7 @@ pub async fn list_chunks_in_volume(
         .objects
         .iter()
         .map(|object| {
-            let identifier_segment = object.key.split('/').last();
+            let identifier_segment = object.key.split('/').next_back();
             let identifier = identifier_segment
                 .unwrap_or_else(|| object.key.as_ref())
                 .to_string();
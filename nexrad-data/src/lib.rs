@@ -16,3 +16,11 @@ pub mod aws;
 pub mod volume;
 
 pub mod result;
+
+#[cfg(all(
+    test,
+    feature = "decode",
+    feature = "nexrad-model",
+    any(feature = "bzip2", feature = "bzip2-rs")
+))]
+mod testing;
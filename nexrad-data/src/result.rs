@@ -13,7 +13,7 @@ pub enum Error {
     #[error("file deserialization error")]
     #[cfg(feature = "bincode")]
     DeserializationError(#[from] bincode::Error),
-    #[cfg(feature = "bzip2")]
+    #[cfg(any(feature = "bzip2", feature = "bzip2-rs"))]
     #[error("error decompressing uncompressed data")]
     UncompressedDataError,
     #[cfg(feature = "aws")]
@@ -31,6 +31,9 @@ pub enum Error {
     #[cfg(feature = "decode")]
     #[error("volume missing coverage pattern number")]
     MissingCoveragePattern,
+    #[cfg(feature = "decode")]
+    #[error("ldm record at offset {0} is truncated")]
+    TruncatedRecordError(usize),
     #[cfg(feature = "bzip2")]
     #[error("ldm record decompression error")]
     DecompressionError(#[from] bzip2::Error),
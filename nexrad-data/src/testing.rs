@@ -0,0 +1,141 @@
+//! Builders for the synthetic volume files, LDM records and messages used throughout this crate's
+//! tests. Records are compressed with the C-backed `bzip2` dev-dependency's encoder, so the same
+//! fixtures serve tests of either decompression backend.
+
+use crate::volume::{File, Header};
+use bzip2::write::BzEncoder;
+use bzip2::Compression;
+use std::io::Write;
+
+/// The volume coverage pattern declared by test digital radar data messages.
+pub(crate) const COVERAGE_PATTERN_NUMBER: u16 = 212;
+
+/// The size in bytes of the fixed-length frames which carry messages other than digital radar data.
+pub(crate) const FIXED_MESSAGE_SIZE: usize = 2432;
+
+/// A digital radar data message, including its 12-byte prefix, for the radial at the given azimuth
+/// number of a sweep with one degree radials. The message carries only a volume data block.
+pub(crate) fn radar_data_message(
+    elevation_number: u8,
+    azimuth_number: u16,
+    radial_status: u8,
+) -> Vec<u8> {
+    const HEADER_SIZE: usize = 32;
+    const POINTER_SIZE: usize = 4;
+    const VOLUME_BLOCK_SIZE: usize = 52;
+
+    let date = 19_000u16;
+    let time = elevation_number as u32 * 60_000 + azimuth_number as u32 * 100;
+    let radial_length = HEADER_SIZE + POINTER_SIZE + VOLUME_BLOCK_SIZE;
+
+    let mut message = vec![0; 12];
+    message.extend((((16 + radial_length) / 2) as u16).to_be_bytes());
+    message.extend([8, 31]);
+    message.extend(azimuth_number.to_be_bytes());
+    message.extend(date.to_be_bytes());
+    message.extend(time.to_be_bytes());
+    message.extend(1u16.to_be_bytes());
+    message.extend(1u16.to_be_bytes());
+
+    message.extend(b"KTLX");
+    message.extend(time.to_be_bytes());
+    message.extend(date.to_be_bytes());
+    message.extend(azimuth_number.to_be_bytes());
+    message.extend((azimuth_number as f32 - 0.5).to_be_bytes());
+    message.extend([0, 0]);
+    message.extend((radial_length as u16).to_be_bytes());
+    message.extend([2, radial_status, elevation_number, 1]);
+    message.extend((elevation_number as f32 * 0.5).to_be_bytes());
+    message.extend([0, 0]);
+    message.extend(1u16.to_be_bytes());
+    message.extend(((HEADER_SIZE + POINTER_SIZE) as u32).to_be_bytes());
+
+    message.extend(b"RVOL");
+    message.extend((VOLUME_BLOCK_SIZE as u16 - 8).to_be_bytes());
+    message.extend([2, 0]);
+    message.extend(35.33f32.to_be_bytes());
+    message.extend((-97.28f32).to_be_bytes());
+    message.extend(370i16.to_be_bytes());
+    message.extend(20u16.to_be_bytes());
+    message.extend([0; 20]);
+    message.extend(COVERAGE_PATTERN_NUMBER.to_be_bytes());
+    message.extend([0; 10]);
+
+    message
+}
+
+/// A digital radar data message, padded to fill a fixed-length frame, whose header gives no usable
+/// size and whose data block pointer lies beyond the end of the message.
+pub(crate) fn corrupt_radar_data_message() -> Vec<u8> {
+    let mut message = radar_data_message(1, 1, 1);
+    message[12..14].copy_from_slice(&0u16.to_be_bytes());
+    message[60..64].copy_from_slice(&u32::MAX.to_be_bytes());
+    message.resize(FIXED_MESSAGE_SIZE, 0);
+    message
+}
+
+/// A volume coverage pattern message, padded to fill a fixed-length frame, defining elevation cuts
+/// at the given angles in degrees with the given supplemental data flags.
+pub(crate) fn volume_coverage_pattern_message(elevation_cuts: &[(f32, u16)]) -> Vec<u8> {
+    const HEADER_SIZE: usize = 22;
+    const ELEVATION_CUT_SIZE: usize = 46;
+
+    let mut message = vec![0; 12];
+    message.extend((((FIXED_MESSAGE_SIZE - 12) / 2) as u16).to_be_bytes());
+    message.extend([8, 5]);
+    message.extend(1u16.to_be_bytes());
+    message.extend(19_000u16.to_be_bytes());
+    message.extend(0u32.to_be_bytes());
+    message.extend(1u16.to_be_bytes());
+    message.extend(1u16.to_be_bytes());
+
+    let size = HEADER_SIZE + elevation_cuts.len() * ELEVATION_CUT_SIZE;
+    message.extend(((size / 2) as u16).to_be_bytes());
+    message.extend(2u16.to_be_bytes());
+    message.extend(COVERAGE_PATTERN_NUMBER.to_be_bytes());
+    message.extend((elevation_cuts.len() as u16).to_be_bytes());
+    message.extend([0; HEADER_SIZE - 8]);
+
+    for (elevation_angle, supplemental_data) in elevation_cuts {
+        let mut elevation_cut = vec![0; ELEVATION_CUT_SIZE];
+        let coded_angle = (elevation_angle * 32768.0 / 180.0).round() as u16;
+        elevation_cut[0..2].copy_from_slice(&coded_angle.to_be_bytes());
+        elevation_cut[28..30].copy_from_slice(&supplemental_data.to_be_bytes());
+        message.extend(elevation_cut);
+    }
+
+    message.resize(FIXED_MESSAGE_SIZE, 0);
+    message
+}
+
+/// The digital radar data messages for the given azimuth numbers of a sweep, each marked as an
+/// intermediate radial.
+pub(crate) fn radar_data_messages(
+    elevation_number: u8,
+    azimuth_numbers: impl IntoIterator<Item = u16>,
+) -> Vec<u8> {
+    azimuth_numbers
+        .into_iter()
+        .flat_map(|azimuth_number| radar_data_message(elevation_number, azimuth_number, 1))
+        .collect()
+}
+
+/// An LDM record holding the given messages, bzip2-compressed and prefixed with its size.
+pub(crate) fn compressed_record(messages: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = BzEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(messages)?;
+    let compressed = encoder.finish()?;
+
+    let mut record = (compressed.len() as i32).to_be_bytes().to_vec();
+    record.extend(compressed);
+    Ok(record)
+}
+
+/// A volume file with an empty volume header followed by the given LDM records.
+pub(crate) fn volume_file(records: &[Vec<u8>]) -> File {
+    let mut data = vec![0; size_of::<Header>()];
+    for record in records {
+        data.extend(record);
+    }
+    File::new(data)
+}
@@ -23,3 +23,13 @@ mod record;
 pub use record::*;
 
 mod util;
+
+#[cfg(feature = "decode")]
+mod diagnostics;
+#[cfg(feature = "decode")]
+pub use diagnostics::*;
+
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+mod validation;
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+pub use validation::*;
@@ -0,0 +1,37 @@
+use nexrad_decode::messages::MessageType;
+
+/// A region of a volume file skipped while decoding leniently, either a whole LDM record or a
+/// single message within one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedRegion {
+    /// The index of the LDM record containing the skipped region among the file's records.
+    pub record_index: usize,
+
+    /// The byte offset of the LDM record from the start of the file.
+    pub record_offset: usize,
+
+    /// The byte offset of the skipped message from the start of the record's decompressed data,
+    /// or [None] if the whole record was skipped or the message's offset is unknown.
+    pub message_offset: Option<u64>,
+
+    /// The skipped message's type, or [None] if the whole record was skipped or the message's
+    /// header could not be decoded.
+    pub message_type: Option<MessageType>,
+
+    /// A description of the error which caused the region to be skipped.
+    pub error: String,
+}
+
+/// Diagnostics describing the regions of a volume file skipped while decoding leniently.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScanDiagnostics {
+    /// The skipped regions in file order.
+    pub skipped: Vec<SkippedRegion>,
+}
+
+impl ScanDiagnostics {
+    /// Whether no regions were skipped.
+    pub fn is_empty(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
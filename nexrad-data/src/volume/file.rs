@@ -24,45 +24,267 @@ impl File {
     }
 
     /// The file's LDM records.
-    pub fn records(&self) -> Vec<Record> {
-        split_compressed_records(&self.0[size_of::<Header>()..])
+    pub fn records(&self) -> Vec<Record<'_>> {
+        split_compressed_records(self.0.get(size_of::<Header>()..).unwrap_or_default())
     }
 
     /// Decodes this volume file into a common model scan containing sweeps and radials with moment
-    /// data.
+    /// data, failing on the first record or message that cannot be decoded. With the `parallel`
+    /// feature, records are decompressed and decoded concurrently and their radials reassembled in
+    /// record order. Decompressing records requires the `bzip2` or `bzip2-rs` feature, without
+    /// which a compressed record fails to decode.
     #[cfg(all(feature = "nexrad-model", feature = "decode"))]
     pub fn scan(&self) -> Result<nexrad_model::data::Scan> {
+        use nexrad_decode::messages::DecodeMode;
+        Ok(self.scan_with_mode(DecodeMode::Strict)?.0)
+    }
+
+    /// Decodes this volume file into a common model scan in the given mode. In strict mode,
+    /// decoding fails on the first record or message that cannot be decoded, as [File::scan] does.
+    /// In lenient mode, records that cannot be decompressed, messages that cannot be decoded and
+    /// any truncated record at the end of the file are skipped and described in the returned
+    /// diagnostics. The first volume coverage pattern message's definition is attached to the scan.
+    /// Either mode fails if no message declares the volume coverage pattern.
+    #[cfg(all(feature = "nexrad-model", feature = "decode"))]
+    pub fn scan_with_mode(
+        &self,
+        mode: nexrad_decode::messages::DecodeMode,
+    ) -> Result<(nexrad_model::data::Scan, crate::volume::ScanDiagnostics)> {
         use crate::result::Error;
-        use nexrad_decode::messages::Message;
+        use crate::volume::{ScanDiagnostics, SkippedRegion};
         use nexrad_model::data::{Scan, Sweep};
+        use nexrad_model::meta::VolumeCoveragePattern;
 
+        let (decoded_records, truncated) = self.decode_records(mode)?;
+
+        let mut diagnostics = ScanDiagnostics::default();
+        let record_count = decoded_records.len();
         let mut coverage_pattern_number = None;
+        let mut coverage_pattern = None;
         let mut radials = Vec::new();
-        for mut record in self.records() {
-            if record.compressed() {
-                record = record.decompress()?;
+        for decoded_record in decoded_records {
+            coverage_pattern_number =
+                coverage_pattern_number.or(decoded_record.coverage_pattern_number);
+            coverage_pattern = coverage_pattern.or(decoded_record.coverage_pattern);
+            radials.extend(decoded_record.radials);
+            diagnostics.skipped.extend(
+                decoded_record
+                    .skipped
+                    .into_iter()
+                    .map(|(_, skipped)| skipped),
+            );
+        }
+
+        if let Some(truncated) = truncated {
+            diagnostics.skipped.push(SkippedRegion {
+                record_index: record_count,
+                record_offset: truncated.offset,
+                message_offset: None,
+                message_type: None,
+                error: Error::TruncatedRecordError(truncated.offset).to_string(),
+            });
+        }
+
+        let coverage_pattern_number = coverage_pattern_number
+            .or(coverage_pattern
+                .as_ref()
+                .map(VolumeCoveragePattern::pattern_number))
+            .ok_or(Error::MissingCoveragePattern)?;
+
+        let mut scan = Scan::new(coverage_pattern_number, Sweep::from_radials(radials));
+        if let Some(coverage_pattern) = coverage_pattern {
+            scan = scan.with_coverage_pattern(coverage_pattern);
+        }
+        Ok((scan, diagnostics))
+    }
+
+    /// Splits this file's LDM records and decompresses and decodes each in the given mode, returning
+    /// the decoded records in order and any truncated record at the end of the file, with offsets
+    /// relative to the start of the file. In strict mode, a truncated record fails decoding. With
+    /// the `parallel` feature, records are decoded concurrently.
+    #[cfg(all(feature = "nexrad-model", feature = "decode"))]
+    pub(crate) fn decode_records(
+        &self,
+        mode: nexrad_decode::messages::DecodeMode,
+    ) -> Result<(Vec<DecodedRecord>, Option<crate::volume::TruncatedRecord>)> {
+        use crate::result::Error;
+        use crate::volume::split_records;
+        use nexrad_decode::messages::DecodeMode;
+
+        let header_size = size_of::<Header>();
+        let data = self.0.get(header_size..).unwrap_or_default();
+        let (records, truncated) = split_records(data);
+
+        let truncated = truncated.map(|mut truncated| {
+            truncated.offset += header_size;
+            truncated
+        });
+        if let Some(truncated) = truncated {
+            if mode == DecodeMode::Strict {
+                return Err(Error::TruncatedRecordError(truncated.offset));
             }
+        }
+
+        let decode = |(record_index, (offset, record)): (usize, (usize, Record<'_>))| {
+            decode_record_radials(record_index, header_size + offset, record, mode)
+        };
+
+        #[cfg(feature = "parallel")]
+        let decoded_records = {
+            use rayon::prelude::*;
+            records
+                .into_par_iter()
+                .enumerate()
+                .map(decode)
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let decoded_records = records
+            .into_iter()
+            .enumerate()
+            .map(decode)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((decoded_records, truncated))
+    }
+
+    /// Inspects this volume file for problems such as an undecodable header, truncated or
+    /// undecodable LDM records and messages, and problems with the decoded scan per
+    /// [nexrad_model::data::Scan::validate]. Unlike [File::scan], decoding continues past
+    /// problems so that all of them are reported.
+    #[cfg(all(feature = "nexrad-model", feature = "decode"))]
+    pub fn validate(&self) -> Vec<crate::volume::FileIssue> {
+        crate::volume::validation::validate_file(self)
+    }
+}
+
+/// Decodes many volume files into common model scans in parallel, returning each file's result in
+/// the order the files were provided.
+#[cfg(all(feature = "nexrad-model", feature = "decode", feature = "parallel"))]
+pub fn scan_files(files: &[File]) -> Vec<Result<nexrad_model::data::Scan>> {
+    use rayon::prelude::*;
+    files.par_iter().map(File::scan).collect()
+}
+
+/// The coverage pattern number and definition, radials and skipped regions decoded from a single
+/// LDM record.
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+pub(crate) struct DecodedRecord {
+    pub coverage_pattern_number: Option<u16>,
+    pub coverage_pattern: Option<nexrad_model::meta::VolumeCoveragePattern>,
+    pub radials: Vec<nexrad_model::data::Radial>,
+    pub skipped: Vec<(SkipCause, crate::volume::SkippedRegion)>,
+}
+
+/// The decoding step at which a region of an LDM record was skipped.
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SkipCause {
+    /// The record could not be decompressed.
+    Decompression,
+    /// The record's messages, or one of them, could not be decoded.
+    Messages,
+    /// A digital radar data message could not be converted into a radial.
+    Radial,
+}
+
+/// Decompresses and decodes a single LDM record at the given index and file offset, returning the
+/// first coverage pattern number and definition it declares, if any, and its radials in order. In lenient mode,
+/// a record that cannot be decompressed, including a compressed record when no decompression
+/// backend is enabled, and messages that cannot be decoded are skipped.
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+fn decode_record_radials(
+    record_index: usize,
+    record_offset: usize,
+    record: Record<'_>,
+    mode: nexrad_decode::messages::DecodeMode,
+) -> Result<DecodedRecord> {
+    use crate::volume::SkippedRegion;
+    use nexrad_decode::messages::{DecodeMode, Message, MessageType};
+
+    let mut decoded = DecodedRecord {
+        coverage_pattern_number: None,
+        coverage_pattern: None,
+        radials: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let skip = |cause, message_offset, message_type, error: &dyn std::fmt::Display| {
+        let skipped = SkippedRegion {
+            record_index,
+            record_offset,
+            message_offset,
+            message_type,
+            error: error.to_string(),
+        };
+        (cause, skipped)
+    };
+
+    let record = match record.into_decompressed() {
+        Ok(record) => record,
+        Err(error) if mode == DecodeMode::Lenient => {
+            decoded
+                .skipped
+                .push(skip(SkipCause::Decompression, None, None, &error));
+            return Ok(decoded);
+        }
+        Err(error) => return Err(error),
+    };
 
-            let messages = record.messages()?;
-            for message in messages {
-                if let Message::DigitalRadarData(radar_data_message) = message.message {
-                    if coverage_pattern_number.is_none() {
-                        if let Some(volume_block) = &radar_data_message.volume_data_block {
-                            coverage_pattern_number =
-                                Some(volume_block.volume_coverage_pattern_number);
-                        }
+    let messages = match record.messages_with_mode(mode) {
+        Ok((messages, diagnostics)) => {
+            decoded
+                .skipped
+                .extend(diagnostics.skipped.into_iter().map(|skipped| {
+                    skip(
+                        SkipCause::Messages,
+                        Some(skipped.offset),
+                        skipped.message_type,
+                        &skipped.error,
+                    )
+                }));
+            messages
+        }
+        Err(error) if mode == DecodeMode::Lenient => {
+            decoded
+                .skipped
+                .push(skip(SkipCause::Messages, None, None, &error));
+            return Ok(decoded);
+        }
+        Err(error) => return Err(error),
+    };
+
+    for message in messages {
+        match message.message {
+            Message::VolumeCoveragePattern(coverage_pattern_message)
+                if decoded.coverage_pattern.is_none() =>
+            {
+                decoded.coverage_pattern = Some(coverage_pattern_message.volume_coverage_pattern());
+            }
+            Message::DigitalRadarData(radar_data_message) => {
+                if decoded.coverage_pattern_number.is_none() {
+                    if let Some(volume_block) = &radar_data_message.volume_data_block {
+                        decoded.coverage_pattern_number =
+                            Some(volume_block.volume_coverage_pattern_number);
                     }
+                }
 
-                    radials.push(radar_data_message.into_radial()?);
+                match radar_data_message.into_radial() {
+                    Ok(radial) => decoded.radials.push(radial),
+                    Err(error) if mode == DecodeMode::Lenient => decoded.skipped.push(skip(
+                        SkipCause::Radial,
+                        None,
+                        Some(MessageType::RDADigitalRadarDataGenericFormat),
+                        &error,
+                    )),
+                    Err(error) => return Err(error.into()),
                 }
             }
+            _ => {}
         }
-
-        Ok(Scan::new(
-            coverage_pattern_number.ok_or(Error::MissingCoveragePattern)?,
-            Sweep::from_radials(radials),
-        ))
     }
+
+    Ok(decoded)
 }
 
 impl Debug for File {
@@ -79,3 +301,131 @@ impl Debug for File {
         debug.finish()
     }
 }
+
+#[cfg(all(
+    test,
+    feature = "decode",
+    feature = "nexrad-model",
+    any(feature = "bzip2", feature = "bzip2-rs")
+))]
+mod tests {
+    use super::*;
+    use crate::testing::{
+        compressed_record, corrupt_radar_data_message, radar_data_messages,
+        volume_coverage_pattern_message, volume_file, COVERAGE_PATTERN_NUMBER,
+    };
+    use nexrad_decode::messages::DecodeMode;
+
+    /// Each radial's elevation and azimuth numbers, in order, decoding each record in turn.
+    fn serial_radials(file: &File) -> Result<Vec<(u8, u16)>> {
+        let mut radials = Vec::new();
+        for record in file.records() {
+            for message in record.decompress()?.messages()? {
+                if let nexrad_decode::messages::Message::DigitalRadarData(message) = message.message
+                {
+                    radials.push((
+                        message.header.elevation_number,
+                        message.header.azimuth_number,
+                    ));
+                }
+            }
+        }
+        Ok(radials)
+    }
+
+    #[test]
+    fn test_scan_preserves_record_order() {
+        let records: Vec<Vec<u8>> = (0..8)
+            .map(|record_index| {
+                let elevation_number = record_index / 4 + 1;
+                let first_azimuth_number = (record_index % 4) as u16 * 90 + 1;
+                compressed_record(&radar_data_messages(
+                    elevation_number,
+                    first_azimuth_number..first_azimuth_number + 90,
+                ))
+                .unwrap()
+            })
+            .collect();
+        let file = volume_file(&records);
+
+        let scan = file.scan().unwrap();
+        assert_eq!(scan.coverage_pattern_number(), COVERAGE_PATTERN_NUMBER);
+
+        let radials: Vec<(u8, u16)> = scan
+            .sweeps()
+            .iter()
+            .flat_map(|sweep| sweep.radials())
+            .map(|radial| (radial.elevation_number(), radial.azimuth_number()))
+            .collect();
+        assert_eq!(radials.len(), 720);
+        assert_eq!(radials, serial_radials(&file).unwrap());
+    }
+
+    #[test]
+    fn test_scan_attaches_coverage_pattern() {
+        // The third cut is a SAILS cut, though its sweep's angle does not repeat an earlier one
+        let mut messages = volume_coverage_pattern_message(&[(0.5, 0), (1.0, 0), (0.5, 0b11)]);
+        for elevation_number in 1..=3 {
+            messages.extend(radar_data_messages(elevation_number, 1..=360));
+        }
+        let file = volume_file(&[compressed_record(&messages).unwrap()]);
+
+        let scan = file.scan().unwrap();
+        let coverage_pattern = scan.coverage_pattern().unwrap();
+        assert_eq!(coverage_pattern.pattern_number(), COVERAGE_PATTERN_NUMBER);
+
+        let elevation_cuts = coverage_pattern.elevation_cuts();
+        assert_eq!(elevation_cuts.len(), 3);
+        assert!((elevation_cuts[1].elevation_angle_degrees() - 1.0).abs() < 0.01);
+        assert_eq!(elevation_cuts[2].sails_sequence_number(), Some(1));
+        assert_eq!(elevation_cuts[2].mrle_sequence_number(), None);
+
+        assert_eq!(scan.supplemental_flags(), vec![false, false, true]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_scan_files_preserves_file_order() {
+        let files: Vec<File> = (1..=4)
+            .map(|elevation_number| {
+                volume_file(&[
+                    compressed_record(&radar_data_messages(elevation_number, 1..=360)).unwrap(),
+                ])
+            })
+            .collect();
+
+        let elevation_numbers: Vec<u8> = scan_files(&files)
+            .into_iter()
+            .map(|scan| scan.unwrap().sweeps()[0].elevation_number())
+            .collect();
+        assert_eq!(elevation_numbers, vec![1, 2, 3, 4]);
+    }
+
+    /// A volume file with one record holding a corrupt message followed by a full rotation.
+    fn corrupt_message_file() -> Result<File> {
+        let mut messages = corrupt_radar_data_message();
+        messages.extend(radar_data_messages(1, 1..=360));
+        Ok(volume_file(&[compressed_record(&messages)?]))
+    }
+
+    #[test]
+    fn test_scan_lenient_skips_corrupt_message() {
+        let file = corrupt_message_file().unwrap();
+
+        let (scan, diagnostics) = file.scan_with_mode(DecodeMode::Lenient).unwrap();
+        assert_eq!(scan.sweeps().len(), 1);
+        assert_eq!(scan.sweeps()[0].radials().len(), 360);
+
+        assert_eq!(diagnostics.skipped.len(), 1);
+        let skipped = &diagnostics.skipped[0];
+        assert_eq!(skipped.record_index, 0);
+        assert_eq!(skipped.message_offset, Some(0));
+    }
+
+    #[test]
+    fn test_scan_strict_fails_on_corrupt_message() {
+        let file = corrupt_message_file().unwrap();
+        assert!(file.scan_with_mode(DecodeMode::Strict).is_err());
+        assert!(file.scan().is_err());
+    }
+}
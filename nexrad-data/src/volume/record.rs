@@ -48,13 +48,18 @@ impl<'a> Record<'a> {
         self.data().len() >= 6 && self.data()[4..6].as_ref() == b"BZ"
     }
 
-    /// Decompresses this LDM record's data.
-    #[cfg(feature = "bzip2")]
+    /// Decompresses this LDM record's data. The C-backed `bzip2` backend is used when its feature
+    /// is enabled, otherwise the pure-Rust `bzip2-rs` backend is used.
+    #[cfg(any(feature = "bzip2", feature = "bzip2-rs"))]
     pub fn decompress<'b>(&self) -> crate::result::Result<Record<'b>> {
         use crate::result::Error;
-        use bzip2::read::BzDecoder;
         use std::io::Read;
 
+        #[cfg(feature = "bzip2")]
+        use bzip2::read::BzDecoder;
+        #[cfg(not(feature = "bzip2"))]
+        use bzip2_rs::DecoderReader as BzDecoder;
+
         if !self.compressed() {
             return Err(Error::UncompressedDataError);
         }
@@ -68,13 +73,43 @@ impl<'a> Record<'a> {
         Ok(Record::new(decompressed_data))
     }
 
+    /// This LDM record with its data decompressed, if it is compressed. Fails for a compressed
+    /// record when neither the `bzip2` nor the `bzip2-rs` feature is enabled.
+    #[cfg(all(feature = "nexrad-model", feature = "decode"))]
+    pub(crate) fn into_decompressed(self) -> crate::result::Result<Record<'a>> {
+        if !self.compressed() {
+            return Ok(self);
+        }
+
+        #[cfg(any(feature = "bzip2", feature = "bzip2-rs"))]
+        return self.decompress();
+
+        #[cfg(not(any(feature = "bzip2", feature = "bzip2-rs")))]
+        return Err(crate::result::Error::CompressedDataError);
+    }
+
     /// Decodes the NEXRAD level II messages contained in this LDM record.
     #[cfg(feature = "decode")]
     pub fn messages(
         &self,
     ) -> crate::result::Result<Vec<nexrad_decode::messages::MessageWithHeader>> {
+        use nexrad_decode::messages::DecodeMode;
+        Ok(self.messages_with_mode(DecodeMode::Strict)?.0)
+    }
+
+    /// Decodes the NEXRAD level II messages contained in this LDM record in the given mode. In
+    /// lenient mode, messages which cannot be decoded are skipped and described in the returned
+    /// diagnostics, with offsets relative to the start of this record's data.
+    #[cfg(feature = "decode")]
+    pub fn messages_with_mode(
+        &self,
+        mode: nexrad_decode::messages::DecodeMode,
+    ) -> crate::result::Result<(
+        Vec<nexrad_decode::messages::MessageWithHeader>,
+        nexrad_decode::messages::DecodeDiagnostics,
+    )> {
         use crate::result::Error;
-        use nexrad_decode::messages::decode_messages;
+        use nexrad_decode::messages::decode_messages_with_mode;
         use std::io::Cursor;
 
         if self.compressed() {
@@ -82,7 +117,7 @@ impl<'a> Record<'a> {
         }
 
         let mut reader = Cursor::new(self.data());
-        Ok(decode_messages(&mut reader)?)
+        Ok(decode_messages_with_mode(&mut reader, mode)?)
     }
 }
 
@@ -110,25 +145,215 @@ impl Debug for Record<'_> {
 }
 
 /// Splits compressed LDM record data into individual records. Will omit the record size prefix from
-/// each record.
-pub fn split_compressed_records(data: &[u8]) -> Vec<Record> {
+/// each record. A truncated record at the end of the data, whose size prefix declares more data than
+/// remains, is omitted.
+pub fn split_compressed_records(data: &[u8]) -> Vec<Record<'_>> {
+    split_records(data)
+        .0
+        .into_iter()
+        .map(|(_, record)| record)
+        .collect()
+}
+
+/// A record at the end of LDM record data whose size prefix declares more data than remains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TruncatedRecord {
+    /// The byte offset of the record in the data.
+    pub offset: usize,
+
+    /// The size in bytes the record declares, including its size prefix.
+    pub declared_size: usize,
+
+    /// The size in bytes remaining in the data from the record's offset.
+    pub available_size: usize,
+}
+
+/// Splits compressed LDM record data into individual records with their byte offsets in the data
+/// as [split_compressed_records] does, also returning the truncated record at the end of the data,
+/// if any. Records after a truncated record cannot be located.
+pub(crate) fn split_records(data: &[u8]) -> (Vec<(usize, Record<'_>)>, Option<TruncatedRecord>) {
     let mut records = Vec::new();
 
     let mut position = 0;
-    loop {
-        if position >= data.len() {
-            break;
+    while position < data.len() {
+        let available_size = data.len() - position;
+        let declared_size = match data.get(position..position + 4) {
+            Some(prefix) => {
+                let mut record_size = [0; 4];
+                record_size.copy_from_slice(prefix);
+                i32::from_be_bytes(record_size).unsigned_abs() as usize + 4
+            }
+            None => available_size + 4,
+        };
+
+        if declared_size > available_size {
+            let truncated = TruncatedRecord {
+                offset: position,
+                declared_size,
+                available_size,
+            };
+            return (records, Some(truncated));
         }
 
-        let mut record_size = [0; 4];
-        record_size.copy_from_slice(&data[position..position + 4]);
-        let record_size = i32::from_be_bytes(record_size).unsigned_abs() as usize;
+        records.push((
+            position,
+            Record::from_slice(&data[position..position + declared_size]),
+        ));
+        position += declared_size;
+    }
+
+    (records, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// "NEXRAD Archive II LDM record " repeated eight times, bzip2-compressed.
+    const COMPRESSED: [u8; 84] = [
+        66, 90, 104, 57, 49, 65, 89, 38, 83, 89, 15, 86, 213, 54, 0, 0, 59, 151, 128, 64, 0, 38,
+        39, 16, 64, 14, 96, 145, 0, 32, 0, 112, 83, 76, 140, 76, 76, 64, 170, 167, 168, 26, 122,
+        53, 6, 154, 138, 28, 10, 29, 11, 142, 69, 132, 139, 139, 13, 194, 68, 12, 12, 15, 133, 8,
+        25, 16, 60, 30, 136, 20, 50, 36, 100, 126, 46, 228, 138, 112, 161, 32, 30, 173, 170, 108,
+    ];
+
+    fn compressed_record() -> Vec<u8> {
+        let mut data = (COMPRESSED.len() as i32).to_be_bytes().to_vec();
+        data.extend_from_slice(&COMPRESSED);
+        data
+    }
+
+    #[test]
+    fn test_compressed() {
+        assert!(Record::new(compressed_record()).compressed());
+        assert!(!Record::from_slice(b"NEXRAD").compressed());
+    }
 
-        records.push(Record::from_slice(
-            &data[position..position + record_size + 4],
+    #[test]
+    fn test_split_compressed_records() {
+        let mut data = compressed_record();
+        data.extend(compressed_record());
+
+        let records = split_compressed_records(&data);
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|record| record.compressed()));
+    }
+
+    #[test]
+    fn test_split_compressed_records_truncated() {
+        let mut data = compressed_record();
+        let truncated_offset = data.len();
+        data.extend(&compressed_record()[..40]);
+
+        assert_eq!(split_compressed_records(&data).len(), 1);
+
+        let (records, truncated) = split_records(&data);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0, 0);
+        assert_eq!(
+            truncated,
+            Some(TruncatedRecord {
+                offset: truncated_offset,
+                declared_size: compressed_record().len(),
+                available_size: 40,
+            })
+        );
+    }
+
+    #[test]
+    fn test_split_compressed_records_partial_prefix() {
+        let mut data = compressed_record();
+        data.extend([0, 0]);
+
+        let (records, truncated) = split_records(&data);
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            truncated.map(|truncated| truncated.offset),
+            Some(compressed_record().len())
+        );
+    }
+
+    #[cfg(any(feature = "bzip2", feature = "bzip2-rs"))]
+    #[test]
+    fn test_decompress() {
+        let record = Record::new(compressed_record());
+        let decompressed = record.decompress().unwrap();
+
+        assert!(!decompressed.compressed());
+        assert_eq!(
+            decompressed.data(),
+            b"NEXRAD Archive II LDM record ".repeat(8).as_slice()
+        );
+    }
+
+    #[cfg(all(feature = "bzip2", feature = "bzip2-rs"))]
+    #[test]
+    fn test_decompress_backends_agree() {
+        use bzip2::write::BzEncoder;
+        use bzip2::Compression;
+        use std::io::{Read, Write};
+
+        // Enough varied data to span several 100 kB compression blocks
+        let mut state = 1u32;
+        let data: Vec<u8> = (0..350_000)
+            .map(|index| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                if index % 7 == 0 {
+                    (state >> 24) as u8
+                } else {
+                    (index % 64) as u8
+                }
+            })
+            .collect();
+
+        let mut encoder = BzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(&data).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut c_decompressed = Vec::new();
+        bzip2::read::BzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut c_decompressed)
+            .unwrap();
+
+        let mut rust_decompressed = Vec::new();
+        bzip2_rs::DecoderReader::new(compressed.as_slice())
+            .read_to_end(&mut rust_decompressed)
+            .unwrap();
+
+        assert_eq!(c_decompressed, data);
+        assert_eq!(rust_decompressed, c_decompressed);
+
+        let mut record = (compressed.len() as i32).to_be_bytes().to_vec();
+        record.extend(compressed);
+        assert_eq!(Record::new(record).decompress().unwrap().data(), data);
+    }
+
+    #[cfg(any(feature = "bzip2", feature = "bzip2-rs"))]
+    #[test]
+    fn test_decompress_uncompressed() {
+        let record = Record::from_slice(b"NEXRAD");
+        assert!(matches!(
+            record.decompress(),
+            Err(crate::result::Error::UncompressedDataError)
         ));
-        position += record_size + 4;
     }
 
-    records
+    #[cfg(all(
+        feature = "nexrad-model",
+        feature = "decode",
+        not(any(feature = "bzip2", feature = "bzip2-rs"))
+    ))]
+    #[test]
+    fn test_scan_compressed_without_backend() {
+        use crate::result::Error;
+        use crate::volume::{File, Header};
+
+        let mut data = vec![0; size_of::<Header>()];
+        data.extend(compressed_record());
+
+        assert!(matches!(
+            File::new(data).scan(),
+            Err(Error::CompressedDataError)
+        ));
+    }
 }
@@ -0,0 +1,279 @@
+use crate::volume::{File, SkipCause};
+use nexrad_decode::messages::DecodeMode;
+use nexrad_model::data::{Scan, ScanIssue, Severity, Sweep};
+use nexrad_model::meta::VolumeCoveragePattern;
+use std::fmt::Display;
+
+/// A problem found while validating an Archive II volume file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileIssue {
+    severity: Severity,
+    kind: FileIssueKind,
+}
+
+impl FileIssue {
+    /// Create a new file issue of the given kind, with its default severity.
+    pub fn new(kind: FileIssueKind) -> Self {
+        Self {
+            severity: kind.severity(),
+            kind,
+        }
+    }
+
+    /// How severe this issue is.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// What this issue is and where it was found.
+    pub fn kind(&self) -> &FileIssueKind {
+        &self.kind
+    }
+}
+
+impl Display for FileIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.kind)
+    }
+}
+
+/// The kinds of problems found while validating an Archive II volume file. Records are identified
+/// by their index among the file's LDM records.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FileIssueKind {
+    /// The file's records could not be decoded at all, so its contents could not be inspected.
+    UndecodableFile { reason: String },
+    /// The file's volume header could not be decoded.
+    UndecodableHeader { reason: String },
+    /// A record's size prefix declares more data than remains in the file.
+    TruncatedRecord {
+        record_index: usize,
+        declared_size: usize,
+        available_size: usize,
+    },
+    /// A compressed record could not be decompressed.
+    UndecompressibleRecord { record_index: usize, reason: String },
+    /// A record's messages could not be decoded.
+    UndecodableMessages { record_index: usize, reason: String },
+    /// A digital radar data message in a record could not be converted into a radial.
+    UndecodableRadial { record_index: usize, reason: String },
+    /// No digital radar data or volume coverage pattern message declares the volume coverage
+    /// pattern.
+    MissingCoveragePattern,
+    /// A problem with the scan decoded from the file.
+    Scan(ScanIssue),
+}
+
+impl FileIssueKind {
+    /// The default severity of this kind of issue.
+    pub fn severity(&self) -> Severity {
+        match self {
+            FileIssueKind::Scan(issue) => issue.severity(),
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Display for FileIssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileIssueKind::UndecodableFile { reason } => {
+                write!(f, "volume file could not be decoded: {reason}")
+            }
+            FileIssueKind::UndecodableHeader { reason } => {
+                write!(f, "volume header could not be decoded: {reason}")
+            }
+            FileIssueKind::TruncatedRecord {
+                record_index,
+                declared_size,
+                available_size,
+            } => write!(
+                f,
+                "record {record_index} declares {declared_size} bytes but only {available_size} remain"
+            ),
+            FileIssueKind::UndecompressibleRecord {
+                record_index,
+                reason,
+            } => write!(
+                f,
+                "record {record_index} could not be decompressed: {reason}"
+            ),
+            FileIssueKind::UndecodableMessages {
+                record_index,
+                reason,
+            } => write!(
+                f,
+                "record {record_index} messages could not be decoded: {reason}"
+            ),
+            FileIssueKind::UndecodableRadial {
+                record_index,
+                reason,
+            } => write!(
+                f,
+                "record {record_index} radial could not be decoded: {reason}"
+            ),
+            FileIssueKind::MissingCoveragePattern => {
+                write!(f, "volume missing coverage pattern number")
+            }
+            FileIssueKind::Scan(issue) => write!(f, "{}", issue.kind()),
+        }
+    }
+}
+
+/// Inspects a volume file for problems. See [File::validate].
+pub(crate) fn validate_file(file: &File) -> Vec<FileIssue> {
+    let mut issues = Vec::new();
+    if let Err(error) = file.header() {
+        issues.push(FileIssue::new(FileIssueKind::UndecodableHeader {
+            reason: error.to_string(),
+        }));
+    }
+
+    // Lenient decoding records problems as skipped regions rather than failing.
+    let (decoded_records, truncated) = match file.decode_records(DecodeMode::Lenient) {
+        Ok(decoded) => decoded,
+        Err(error) => {
+            issues.push(FileIssue::new(FileIssueKind::UndecodableFile {
+                reason: error.to_string(),
+            }));
+            return issues;
+        }
+    };
+
+    if let Some(truncated) = truncated {
+        issues.push(FileIssue::new(FileIssueKind::TruncatedRecord {
+            record_index: decoded_records.len(),
+            declared_size: truncated.declared_size,
+            available_size: truncated.available_size,
+        }));
+    }
+
+    let mut coverage_pattern_number = None;
+    let mut coverage_pattern = None;
+    let mut radials = Vec::new();
+    for decoded_record in decoded_records {
+        coverage_pattern_number =
+            coverage_pattern_number.or(decoded_record.coverage_pattern_number);
+        coverage_pattern = coverage_pattern.or(decoded_record.coverage_pattern);
+        radials.extend(decoded_record.radials);
+
+        issues.extend(decoded_record.skipped.into_iter().map(|(cause, skipped)| {
+            let record_index = skipped.record_index;
+            let reason = skipped.error;
+            FileIssue::new(match cause {
+                SkipCause::Decompression => FileIssueKind::UndecompressibleRecord {
+                    record_index,
+                    reason,
+                },
+                SkipCause::Messages => FileIssueKind::UndecodableMessages {
+                    record_index,
+                    reason,
+                },
+                SkipCause::Radial => FileIssueKind::UndecodableRadial {
+                    record_index,
+                    reason,
+                },
+            })
+        }));
+    }
+
+    let coverage_pattern_number = coverage_pattern_number.or(coverage_pattern
+        .as_ref()
+        .map(VolumeCoveragePattern::pattern_number));
+    if coverage_pattern_number.is_none() {
+        issues.push(FileIssue::new(FileIssueKind::MissingCoveragePattern));
+    }
+
+    let mut scan = Scan::new(
+        coverage_pattern_number.unwrap_or_default(),
+        Sweep::from_radials(radials),
+    );
+    if let Some(coverage_pattern) = coverage_pattern {
+        scan = scan.with_coverage_pattern(coverage_pattern);
+    }
+    issues.extend(
+        scan.validate()
+            .into_iter()
+            .map(|issue| FileIssue::new(FileIssueKind::Scan(issue))),
+    );
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::Header;
+    use nexrad_model::data::ScanIssueKind;
+
+    #[test]
+    fn test_validate_truncated_file() {
+        let mut data = vec![0; size_of::<Header>()];
+        data.extend(100i32.to_be_bytes());
+        data.extend(b"BZh9");
+
+        let issues = File::new(data).validate();
+        assert!(
+            issues.contains(&FileIssue::new(FileIssueKind::TruncatedRecord {
+                record_index: 0,
+                declared_size: 104,
+                available_size: 8,
+            }))
+        );
+        assert!(issues.contains(&FileIssue::new(FileIssueKind::MissingCoveragePattern)));
+        assert!(
+            issues.contains(&FileIssue::new(FileIssueKind::Scan(ScanIssue::new(
+                ScanIssueKind::NoSweeps
+            ))))
+        );
+        assert!(issues
+            .iter()
+            .all(|issue| issue.severity() == Severity::Error));
+    }
+
+    #[test]
+    fn test_validate_undecompressible_record() {
+        let mut data = vec![0; size_of::<Header>()];
+        data.extend(8i32.to_be_bytes());
+        data.extend(b"BZh9junk");
+
+        let issues = File::new(data).validate();
+        assert!(issues.iter().any(|issue| matches!(
+            issue.kind(),
+            FileIssueKind::UndecompressibleRecord {
+                record_index: 0,
+                ..
+            }
+        )));
+        assert!(!issues
+            .iter()
+            .any(|issue| matches!(issue.kind(), FileIssueKind::TruncatedRecord { .. })));
+    }
+
+    #[cfg(any(feature = "bzip2", feature = "bzip2-rs"))]
+    #[test]
+    fn test_validate_against_coverage_pattern() {
+        use crate::testing::{
+            compressed_record, radar_data_messages, volume_coverage_pattern_message, volume_file,
+        };
+
+        // The second of the coverage pattern's elevation cuts is never scanned
+        let mut messages = volume_coverage_pattern_message(&[(0.5, 0), (1.0, 0), (1.5, 0)]);
+        messages.extend(radar_data_messages(1, 1..=360));
+        messages.extend(radar_data_messages(3, 1..=360));
+
+        let issues = volume_file(&[compressed_record(&messages).unwrap()]).validate();
+        assert!(issues.iter().any(|issue| matches!(
+            issue.kind(),
+            FileIssueKind::Scan(scan_issue) if matches!(
+                scan_issue.kind(),
+                ScanIssueKind::MissingElevationCut {
+                    elevation_number: 2,
+                    ..
+                }
+            )
+        )));
+    }
+}
@@ -0,0 +1,75 @@
+//! Compares decoding a volume's worth of generic data moment blocks gate-by-gate with
+//! `decoded_values` against bulk decoding with `decoded_values_into`, for 8- and 16-bit words.
+//!
+//! Run with `cargo bench -p nexrad-decode`.
+
+use nexrad_decode::messages::digital_radar_data::{
+    DataBlockId, GenericDataBlock, GenericDataBlockHeader,
+};
+use std::hint::black_box;
+use std::time::Instant;
+
+const GATES: usize = 1840;
+const RADIALS: usize = 720;
+const ITERATIONS: usize = 10;
+
+fn block(data_word_size: u8) -> GenericDataBlock {
+    let word_size_bytes = data_word_size as usize / 8;
+    GenericDataBlock {
+        header: GenericDataBlockHeader {
+            data_block_id: DataBlockId {
+                data_block_type: b'D',
+                data_name: *b"REF",
+            },
+            reserved: 0,
+            number_of_data_moment_gates: GATES as u16,
+            data_moment_range: 2125,
+            data_moment_range_sample_interval: 250,
+            tover: 0,
+            snr_threshold: 0,
+            control_flags: 0,
+            data_word_size,
+            scale: 2.0,
+            offset: 66.0,
+        },
+        encoded_data: (0..GATES * word_size_bytes)
+            .map(|index| (index * 31 % 256) as u8)
+            .collect(),
+    }
+}
+
+fn measure(name: &str, mut decode: impl FnMut()) {
+    decode();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for _ in 0..RADIALS {
+            decode();
+        }
+    }
+
+    let elapsed = start.elapsed();
+    let gates = ITERATIONS * RADIALS * GATES;
+    println!(
+        "{name:<28} {:>8.2} ms/sweep {:>8.2} ns/gate",
+        (elapsed / ITERATIONS as u32).as_secs_f64() * 1000.0,
+        elapsed.as_nanos() as f64 / gates as f64,
+    );
+}
+
+fn main() {
+    for data_word_size in [8, 16] {
+        let block = block(data_word_size);
+        let mut values = vec![0.0; GATES];
+
+        measure(&format!("decoded_values ({data_word_size}-bit)"), || {
+            black_box(black_box(&block).decoded_values());
+        });
+        measure(
+            &format!("decoded_values_into ({data_word_size}-bit)"),
+            || {
+                black_box(black_box(&block).decoded_values_into(&mut values));
+            },
+        );
+    }
+}
@@ -2,6 +2,7 @@ pub mod clutter_filter_map;
 pub mod digital_radar_data;
 pub mod message_header;
 pub mod rda_status_data;
+pub mod volume_coverage_pattern;
 
 mod message_type;
 pub use message_type::MessageType;
@@ -9,39 +10,203 @@ pub use message_type::MessageType;
 mod message;
 pub use message::{Message, MessageWithHeader};
 
+mod diagnostics;
+pub use diagnostics::*;
+
 mod definitions;
 mod primitive_aliases;
 
 use crate::messages::digital_radar_data::decode_digital_radar_data;
 use crate::messages::message_header::MessageHeader;
 use crate::messages::rda_status_data::decode_rda_status_message;
-use crate::result::Result;
+use crate::messages::volume_coverage_pattern::decode_volume_coverage_pattern;
+use crate::result::{Error, Result};
 use crate::util::deserialize;
 use log::{debug, trace};
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
 
 /// Decode a NEXRAD Level II message from a reader.
 pub fn decode_message_header<R: Read>(reader: &mut R) -> Result<MessageHeader> {
     deserialize(reader)
 }
 
-/// Decode a series of NEXRAD Level II messages from a reader.
+/// The size in bytes of the fixed-length frames which carry all messages other than digital radar
+/// data messages.
+const FIXED_MESSAGE_SIZE: u64 = 2432;
+
+/// The size in bytes of the unused prefix preceding each message's header, which is not counted in
+/// the header's message size.
+const MESSAGE_PREFIX_SIZE: u64 = 12;
+
+/// Decode a series of NEXRAD Level II messages from a reader, failing on the first message that
+/// cannot be decoded.
 pub fn decode_messages<R: Read + Seek>(reader: &mut R) -> Result<Vec<MessageWithHeader>> {
-    debug!("Decoding messages");
+    Ok(decode_messages_with_mode(reader, DecodeMode::Strict)?.0)
+}
+
+/// Decode a series of NEXRAD Level II messages from a reader in the given mode. Each decoded header
+/// is checked for plausibility, with a known message type and redundant channel, consistent segment
+/// numbers and a size which fits its frame or the remaining data, and decoding stops at trailing
+/// zero padding. In strict mode, decoding fails on the first message that cannot be decoded or
+/// implausible header, and stops at the first header that cannot be decoded. In lenient mode, each
+/// message that cannot be decoded is skipped using the size given in its header, and decoding
+/// resynchronizes at the next fixed-length message frame after a header that cannot be decoded or
+/// is implausible. Skipped messages and headers are recorded in the returned diagnostics.
+pub fn decode_messages_with_mode<R: Read + Seek>(
+    reader: &mut R,
+    mode: DecodeMode,
+) -> Result<(Vec<MessageWithHeader>, DecodeDiagnostics)> {
+    debug!("Decoding messages in {:?} mode", mode);
+
+    let start = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(start))?;
 
     let mut messages = Vec::new();
-    while let Ok(header) = decode_message_header(reader) {
-        let message = decode_message(reader, header.message_type())?;
-        messages.push(MessageWithHeader { header, message });
+    let mut diagnostics = DecodeDiagnostics::default();
+    loop {
+        let offset = reader.stream_position()?;
+        let header = match decode_message_header(reader) {
+            Ok(header) => check_message_header(&header, offset, end).map(|_| header),
+            Err(_) if mode == DecodeMode::Strict => break,
+            Err(error) => Err(error),
+        };
+
+        let header = match header {
+            Ok(header) => header,
+            Err(error) => {
+                if is_padding(reader, offset)? {
+                    break;
+                }
+                if mode == DecodeMode::Strict {
+                    return Err(error);
+                }
+
+                debug!("Skipping unusable header at {}: {}", offset, error);
+                diagnostics.skipped.push(SkippedMessage {
+                    offset: offset - start,
+                    message_type: None,
+                    error: error.to_string(),
+                });
+
+                let next_offset = next_frame_offset(start, offset);
+                if next_offset > end {
+                    break;
+                }
+                reader.seek(SeekFrom::Start(next_offset))?;
+                continue;
+            }
+        };
+
+        let message_type = header.message_type();
+        match decode_message(reader, message_type) {
+            Ok(message) => messages.push(MessageWithHeader { header, message }),
+            Err(error) if mode == DecodeMode::Lenient => {
+                debug!(
+                    "Skipping {:?} message at {}: {}",
+                    message_type, offset, error
+                );
+                diagnostics.skipped.push(SkippedMessage {
+                    offset: offset - start,
+                    message_type: Some(message_type),
+                    error: error.to_string(),
+                });
+
+                let next_offset = if message_type == MessageType::RDADigitalRadarDataGenericFormat {
+                    offset + MESSAGE_PREFIX_SIZE + header.message_size_bytes() as u64
+                } else {
+                    offset + FIXED_MESSAGE_SIZE
+                };
+                if next_offset > end {
+                    break;
+                }
+                reader.seek(SeekFrom::Start(next_offset))?;
+            }
+            Err(error) => return Err(error),
+        }
     }
 
     debug!(
-        "Decoded {} messages ending at {:?}",
+        "Decoded {} messages and skipped {} ending at {:?}",
         messages.len(),
+        diagnostics.skipped.len(),
         reader.stream_position()
     );
 
-    Ok(messages)
+    Ok((messages, diagnostics))
+}
+
+/// The offset of the fixed-length message frame following the one containing the given offset,
+/// counting frames from the start of the data, at which decoding can resynchronize.
+fn next_frame_offset(start: u64, offset: u64) -> u64 {
+    start + ((offset - start) / FIXED_MESSAGE_SIZE + 1) * FIXED_MESSAGE_SIZE
+}
+
+/// Checks that a decoded header is plausible for a message at the given offset in data ending at
+/// the given offset, so that arbitrary data which happens to decode as a header is not trusted to
+/// locate its message or the next one.
+fn check_message_header(header: &MessageHeader, offset: u64, end: u64) -> Result<()> {
+    let implausible = |reason: String| {
+        Err(Error::DecodingError(format!(
+            "implausible message header: {}",
+            reason
+        )))
+    };
+
+    let message_type = header.message_type();
+    if message_type == MessageType::Unknown {
+        return implausible(format!("unknown message type {}", header.message_type));
+    }
+
+    if !matches!(header.redundant_channel, 0 | 1 | 2 | 8 | 9 | 10) {
+        return implausible(format!(
+            "unknown redundant channel {}",
+            header.redundant_channel
+        ));
+    }
+
+    if let (Some(segment_count), Some(segment_number)) =
+        (header.segment_count(), header.segment_number())
+    {
+        if segment_number == 0 || segment_number > segment_count {
+            return implausible(format!("segment {} of {}", segment_number, segment_count));
+        }
+    }
+
+    let size = MESSAGE_PREFIX_SIZE + header.message_size_bytes() as u64;
+    let max_size = if message_type == MessageType::RDADigitalRadarDataGenericFormat {
+        end - offset
+    } else {
+        FIXED_MESSAGE_SIZE
+    };
+    if size < size_of::<MessageHeader>() as u64 || size > max_size {
+        return implausible(format!(
+            "{:?} message size of {} bytes, outside {} to {} bytes",
+            message_type,
+            size,
+            size_of::<MessageHeader>(),
+            max_size
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether the data from the given offset to the end of the reader is empty or zero padding, reading
+/// it in chunks up to the first non-zero byte.
+fn is_padding<R: Read + Seek>(reader: &mut R, offset: u64) -> Result<bool> {
+    reader.seek(SeekFrom::Start(offset))?;
+
+    let mut chunk = [0; 4096];
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        if chunk[..read].iter().any(|byte| *byte != 0) {
+            return Ok(false);
+        }
+    }
 }
 
 /// Decode a NEXRAD Level II message of the specified type from a reader.
@@ -57,7 +222,7 @@ pub fn decode_message<R: Read + Seek>(
         return Ok(Message::DigitalRadarData(Box::new(decoded_message)));
     }
 
-    let mut message_buffer = [0; 2432 - size_of::<MessageHeader>()];
+    let mut message_buffer = [0; FIXED_MESSAGE_SIZE as usize - size_of::<MessageHeader>()];
     reader.read_exact(&mut message_buffer)?;
 
     let message_reader = &mut message_buffer.as_ref();
@@ -65,6 +230,9 @@ pub fn decode_message<R: Read + Seek>(
         MessageType::RDAStatusData => {
             Message::RDAStatusData(Box::new(decode_rda_status_message(message_reader)?))
         }
+        MessageType::RDAVolumeCoveragePattern => Message::VolumeCoveragePattern(Box::new(
+            decode_volume_coverage_pattern(message_reader)?,
+        )),
         // TODO: this message type is segmented which is not supported well currently
         // MessageType::RDAClutterFilterMap => {
         //     Message::ClutterFilterMap(Box::new(decode_clutter_filter_map(message_reader)?))
@@ -72,3 +240,91 @@ pub fn decode_message<R: Read + Seek>(
         _ => Message::Other,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// A fixed-length frame holding a message of the given type with an otherwise empty body.
+    fn frame(redundant_channel: u8, message_type: u8) -> Vec<u8> {
+        let mut frame = vec![0; MESSAGE_PREFIX_SIZE as usize];
+        frame.extend(((FIXED_MESSAGE_SIZE - MESSAGE_PREFIX_SIZE) as u16 / 2).to_be_bytes());
+        frame.extend([redundant_channel, message_type]);
+        frame.extend(1u16.to_be_bytes());
+        frame.extend(19_000u16.to_be_bytes());
+        frame.extend(0u32.to_be_bytes());
+        frame.extend(1u16.to_be_bytes());
+        frame.extend(1u16.to_be_bytes());
+        frame.resize(FIXED_MESSAGE_SIZE as usize, 0);
+        frame
+    }
+
+    fn decode(
+        data: Vec<u8>,
+        mode: DecodeMode,
+    ) -> Result<(Vec<MessageWithHeader>, DecodeDiagnostics)> {
+        decode_messages_with_mode(&mut Cursor::new(data), mode)
+    }
+
+    #[test]
+    fn test_decodes_frames_and_stops_at_padding() {
+        let mut data = frame(8, 4);
+        data.extend(frame(8, 6));
+        data.extend(vec![0; 10_000]);
+
+        for mode in [DecodeMode::Strict, DecodeMode::Lenient] {
+            let (messages, diagnostics) = decode(data.clone(), mode).unwrap();
+            assert_eq!(messages.len(), 2);
+            assert!(diagnostics.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_lenient_resynchronizes_after_implausible_header() {
+        let mut unknown_type = frame(8, 0);
+        unknown_type[20..].fill(0xFF);
+        let mut oversized = frame(8, 4);
+        oversized[12..14].copy_from_slice(&2000u16.to_be_bytes());
+        let mut bad_segment = frame(8, 4);
+        bad_segment[26..28].copy_from_slice(&2u16.to_be_bytes());
+
+        for (implausible, reason) in [
+            (unknown_type, "unknown message type"),
+            (frame(200, 4), "unknown redundant channel"),
+            (oversized, "message size"),
+            (bad_segment, "segment 2 of 1"),
+        ] {
+            let mut data = frame(8, 4);
+            data.extend(implausible);
+            data.extend(frame(8, 6));
+
+            let (messages, diagnostics) = decode(data.clone(), DecodeMode::Lenient).unwrap();
+            assert_eq!(messages.len(), 2);
+            assert_eq!(
+                messages[1].header.message_type(),
+                MessageType::RDAControlCommands
+            );
+
+            assert_eq!(diagnostics.skipped.len(), 1);
+            let skipped = &diagnostics.skipped[0];
+            assert_eq!(skipped.offset, FIXED_MESSAGE_SIZE);
+            assert_eq!(skipped.message_type, None);
+            assert!(skipped.error.contains(reason), "{}", skipped.error);
+
+            assert!(decode(data, DecodeMode::Strict).is_err());
+        }
+    }
+
+    #[test]
+    fn test_trailing_data_after_padding_is_not_padding() {
+        let mut data = frame(8, 4);
+        data.extend(vec![0; 10_000]);
+        data.push(1);
+
+        let (messages, diagnostics) = decode(data, DecodeMode::Lenient).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(!diagnostics.is_empty());
+        assert_eq!(diagnostics.skipped[0].offset, FIXED_MESSAGE_SIZE);
+    }
+}
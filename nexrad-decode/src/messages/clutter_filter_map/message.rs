@@ -21,4 +21,42 @@ impl Message {
             header,
         }
     }
+
+    /// Convert this clutter filter map into the common model's clutter filter map. Range zones with
+    /// an unrecognized operation code are kept with an unknown action.
+    #[cfg(feature = "nexrad-model")]
+    pub fn clutter_filter_map(&self) -> nexrad_model::meta::ClutterFilterMap {
+        use nexrad_model::meta::{ClutterFilterAction, ClutterFilterMap, ClutterFilterZone};
+
+        ClutterFilterMap::new(
+            self.elevation_segments
+                .iter()
+                .map(|elevation_segment| {
+                    elevation_segment
+                        .azimuth_segments
+                        .iter()
+                        .map(|azimuth_segment| {
+                            azimuth_segment
+                                .range_zones
+                                .iter()
+                                .map(|range_zone| {
+                                    let action = match range_zone.op_code {
+                                        0 => ClutterFilterAction::Bypass,
+                                        1 => ClutterFilterAction::BypassMapInControl,
+                                        2 => ClutterFilterAction::Force,
+                                        op_code => ClutterFilterAction::Unknown(op_code),
+                                    };
+
+                                    ClutterFilterZone::new(
+                                        range_zone.end_range as u32 * 1000,
+                                        action,
+                                    )
+                                })
+                                .collect()
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
 }
@@ -0,0 +1,39 @@
+use crate::messages::MessageType;
+
+/// How decoding handles malformed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DecodeMode {
+    /// Fail on the first message that cannot be decoded.
+    #[default]
+    Strict,
+    /// Skip messages that cannot be decoded and continue with the next message, recording each
+    /// skipped message in the diagnostics.
+    Lenient,
+}
+
+/// A message, or trailing data, skipped while decoding leniently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedMessage {
+    /// The byte offset of the skipped message's header from the start of the decoded data.
+    pub offset: u64,
+
+    /// The skipped message's type, or [None] if its header could not be decoded or was implausible.
+    pub message_type: Option<MessageType>,
+
+    /// A description of the error which caused the message to be skipped.
+    pub error: String,
+}
+
+/// Diagnostics describing the messages skipped while decoding leniently.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DecodeDiagnostics {
+    /// The skipped messages in the order they were encountered.
+    pub skipped: Vec<SkippedMessage>,
+}
+
+impl DecodeDiagnostics {
+    /// Whether no messages were skipped.
+    pub fn is_empty(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
@@ -41,28 +41,68 @@ impl GenericDataBlock {
     }
 
     /// Decodes raw moment values from `encoded_data` from their fixed-point representation into
-    /// their floating point representation. Additionally, identifies special values such as "below
-    /// threshold" and "range folded".
+    /// their floating point representation, honoring the header's 8- or 16-bit data word size.
+    /// Additionally, identifies special values such as "below threshold" and "range folded".
     pub fn decoded_values(&self) -> Vec<ScaledMomentValue> {
-        self.encoded_data
-            .iter()
-            .copied()
+        let scale = self.header.scale;
+        let offset = self.header.offset;
+        self.raw_words()
             .map(|raw_value| {
-                if self.header.scale == 0.0 {
+                if scale == 0.0 {
                     return ScaledMomentValue::Value(raw_value as f32);
                 }
 
                 match raw_value {
                     0 => ScaledMomentValue::BelowThreshold,
                     1 => ScaledMomentValue::RangeFolded,
-                    _ => ScaledMomentValue::Value(
-                        (raw_value as f32 - self.header.offset) / self.header.scale,
-                    ),
+                    _ => ScaledMomentValue::Value(decode_word(raw_value, scale, offset)),
                 }
             })
             .collect()
     }
 
+    /// Decodes raw moment values from `encoded_data` into the provided buffer, which should be
+    /// `number_of_data_moment_gates` long, honoring the header's 8- or 16-bit data word size.
+    /// Gates that are below threshold or range folded are NaN. 8-bit words are decoded through a
+    /// lookup table and 16-bit words are read in big-endian byte order, avoiding allocating and
+    /// matching per gate, so this is preferred over `decoded_values` for bulk processing. Returns
+    /// the number of values written.
+    pub fn decoded_values_into(&self, values: &mut [f32]) -> usize {
+        let scale = self.header.scale;
+        let offset = self.header.offset;
+
+        if self.header.data_word_size == 16 {
+            let words = self.encoded_data.chunks_exact(2);
+            let count = values.len().min(words.len());
+            for (value, word) in values[..count].iter_mut().zip(words) {
+                *value = decode_word(u16::from_be_bytes([word[0], word[1]]), scale, offset);
+            }
+            return count;
+        }
+
+        let table = decode_table(scale, offset);
+        let count = values.len().min(self.encoded_data.len());
+        for (value, raw_value) in values[..count].iter_mut().zip(&self.encoded_data) {
+            *value = table[*raw_value as usize];
+        }
+        count
+    }
+
+    /// The raw words in `encoded_data`, each one or two big-endian bytes per the header's data
+    /// word size.
+    fn raw_words(&self) -> impl Iterator<Item = u16> + '_ {
+        let word_size_bytes = if self.header.data_word_size == 16 {
+            2
+        } else {
+            1
+        };
+
+        self.encoded_data.chunks_exact(word_size_bytes).map(|word| {
+            word.iter()
+                .fold(0, |raw_value, part| raw_value << 8 | *part as u16)
+        })
+    }
+
     /// Get moment data from this generic data block. Note that this will clone the underlying data.
     #[cfg(feature = "nexrad-model")]
     pub fn moment_data(&self) -> nexrad_model::data::MomentData {
@@ -71,6 +111,11 @@ impl GenericDataBlock {
             self.header.offset,
             self.encoded_data.clone(),
         )
+        .with_gate_geometry(
+            self.header.data_moment_range,
+            self.header.data_moment_range_sample_interval,
+        )
+        .with_word_size(self.header.data_word_size)
     }
 
     /// Convert this generic data block into common model moment data, minimizing data copies.
@@ -81,6 +126,11 @@ impl GenericDataBlock {
             self.header.offset,
             self.encoded_data,
         )
+        .with_gate_geometry(
+            self.header.data_moment_range,
+            self.header.data_moment_range_sample_interval,
+        )
+        .with_word_size(self.header.data_word_size)
     }
 }
 
@@ -93,6 +143,32 @@ impl Debug for GenericDataBlock {
     }
 }
 
+/// The floating-point value of each 8-bit raw word for the given scale and offset, with NaN for
+/// the below threshold and range folded words.
+fn decode_table(scale: f32, offset: f32) -> [f32; 256] {
+    let mut table = [0.0; 256];
+    for (raw_value, value) in table.iter_mut().enumerate() {
+        *value = decode_word(raw_value as u16, scale, offset);
+    }
+    table
+}
+
+/// Decodes a single raw word, selecting NaN for special words rather than branching so that bulk
+/// loops over it can be vectorized. A scale of zero indicates the word is unscaled.
+#[inline(always)]
+fn decode_word(raw_value: u16, scale: f32, offset: f32) -> f32 {
+    if scale == 0.0 {
+        return raw_value as f32;
+    }
+
+    let value = (raw_value as f32 - offset) / scale;
+    if raw_value < 2 {
+        f32::NAN
+    } else {
+        value
+    }
+}
+
 /// A generic data moment block's decoded header.
 #[derive(Clone, PartialEq, Deserialize)]
 pub struct GenericDataBlockHeader {
@@ -219,3 +295,102 @@ impl Debug for GenericDataBlockHeader {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(
+        data_word_size: u8,
+        scale: f32,
+        offset: f32,
+        encoded_data: Vec<u8>,
+    ) -> GenericDataBlock {
+        let word_size_bytes = data_word_size as usize / 8;
+        GenericDataBlock {
+            header: GenericDataBlockHeader {
+                data_block_id: DataBlockId {
+                    data_block_type: b'D',
+                    data_name: *b"REF",
+                },
+                reserved: 0,
+                number_of_data_moment_gates: (encoded_data.len() / word_size_bytes) as u16,
+                data_moment_range: 2125,
+                data_moment_range_sample_interval: 250,
+                tover: 0,
+                snr_threshold: 0,
+                control_flags: 0,
+                data_word_size,
+                scale,
+                offset,
+            },
+            encoded_data,
+        }
+    }
+
+    fn assert_round_trip(block: &GenericDataBlock, expected: &[Option<f32>]) {
+        let decoded = block.decoded_values();
+        assert_eq!(decoded.len(), expected.len());
+        for (value, expected) in decoded.iter().zip(expected) {
+            match (value, expected) {
+                (ScaledMomentValue::Value(value), Some(expected)) => {
+                    assert!((value - expected).abs() < 1e-3, "{value} != {expected}")
+                }
+                (ScaledMomentValue::BelowThreshold | ScaledMomentValue::RangeFolded, None) => {}
+                _ => panic!("{value:?} != {expected:?}"),
+            }
+        }
+
+        let mut values = vec![0.0; expected.len()];
+        assert_eq!(block.decoded_values_into(&mut values), expected.len());
+        for (value, expected) in values.iter().zip(expected) {
+            match expected {
+                Some(expected) => assert!((value - expected).abs() < 1e-3),
+                None => assert!(value.is_nan()),
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_8_bit_words() {
+        let expected = [None, None, Some(-32.0), Some(0.0), Some(20.5), Some(94.5)];
+        let encoded = vec![0, 1, 2, 66, 107, 255];
+        let block = block(8, 2.0, 66.0, encoded);
+        assert_eq!(block.decoded_values()[0], ScaledMomentValue::BelowThreshold);
+        assert_eq!(block.decoded_values()[1], ScaledMomentValue::RangeFolded);
+        assert_round_trip(&block, &expected);
+    }
+
+    #[test]
+    fn decodes_16_bit_words() {
+        let physical = [0.0, 45.5, 180.0, 359.875];
+        let (scale, offset) = (8.0, 2.0);
+
+        let mut encoded = vec![0, 0, 0, 1];
+        for value in physical {
+            let raw_value = (value * scale + offset) as u16;
+            encoded.extend(raw_value.to_be_bytes());
+        }
+
+        let block = block(16, scale, offset, encoded);
+        assert_eq!(block.header.number_of_data_moment_gates, 6);
+
+        let mut expected = vec![None, None];
+        expected.extend(physical.map(Some));
+        assert_round_trip(&block, &expected);
+    }
+
+    #[test]
+    fn decodes_unscaled_words() {
+        let block = block(16, 0.0, 0.0, vec![0, 0, 0, 1, 1, 0]);
+        assert_round_trip(&block, &[Some(0.0), Some(1.0), Some(256.0)]);
+    }
+
+    #[test]
+    fn decodes_into_short_buffer() {
+        let block = block(8, 2.0, 66.0, vec![66, 68, 70]);
+        let mut values = [0.0; 2];
+        assert_eq!(block.decoded_values_into(&mut values), 2);
+        assert_eq!(values, [0.0, 1.0]);
+    }
+}
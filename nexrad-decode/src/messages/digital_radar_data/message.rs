@@ -104,7 +104,13 @@ impl Message {
             self.specific_diff_phase_data_block
                 .as_ref()
                 .map(|block| block.moment_data()),
-        ))
+        )
+        .with_nyquist_velocity_meters_per_second(
+            self.radial_data_block
+                .as_ref()
+                .map(|block| block.nyquist_velocity as f32 * 0.01),
+        )
+        .with_cut_sector_number(self.header.cut_sector_number))
     }
 
     /// Convert this digital radar data message into a common model radial, minimizing data copy.
@@ -146,6 +152,11 @@ impl Message {
                 .map(|block| block.into_moment_data()),
             self.specific_diff_phase_data_block
                 .map(|block| block.into_moment_data()),
-        ))
+        )
+        .with_nyquist_velocity_meters_per_second(
+            self.radial_data_block
+                .map(|block| block.nyquist_velocity as f32 * 0.01),
+        )
+        .with_cut_sector_number(self.header.cut_sector_number))
     }
 }
@@ -139,6 +139,16 @@ impl VolumeDataBlock {
             _ => ProcessingStatus::Other(self.processing_status),
         }
     }
+
+    /// Convert this block's differential reflectivity calibration into the common model. The RPG's
+    /// bias estimate is encoded as a signed value in hundredths of a dB.
+    #[cfg(feature = "nexrad-model")]
+    pub fn zdr_calibration(&self) -> nexrad_model::meta::ZdrCalibration {
+        nexrad_model::meta::ZdrCalibration::new(
+            self.system_differential_reflectivity,
+            self.zdr_bias_estimate_weighted_mean as i16 as f32 * 0.01,
+        )
+    }
 }
 
 #[cfg(not(feature = "uom"))]
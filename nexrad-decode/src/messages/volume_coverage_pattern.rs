@@ -0,0 +1,96 @@
+//!
+//! Message type 5 "Volume Coverage Pattern" defines the scanning strategy of the current volume
+//! scan: its pattern number, Doppler velocity resolution and pulse width, and each of its
+//! elevation cuts in the order they are scanned, including the supplemental SAILS and MRLE cuts
+//! which revisit lower elevations during the volume.
+//!
+
+mod header;
+pub use header::Header;
+
+mod elevation_cut;
+pub use elevation_cut::ElevationCut;
+
+mod message;
+pub use message::Message;
+
+use crate::result::Result;
+use crate::util::deserialize;
+use std::io::Read;
+
+/// Decodes a volume coverage pattern message type 5 from the provided reader.
+pub fn decode_volume_coverage_pattern<R: Read>(reader: &mut R) -> Result<Message> {
+    let header: Header = deserialize(reader)?;
+    let elevation_cut_count = header.number_of_elevation_cuts;
+
+    let mut message = Message::new(header);
+    for _ in 0..elevation_cut_count {
+        message.elevation_cuts.push(deserialize(reader)?);
+    }
+
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A volume coverage pattern 212 message with a split cut at 0.5 degrees followed by a SAILS
+    /// revisit of the split cut's surveillance sweep.
+    fn message() -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend(((22 + 3 * 46) / 2u16).to_be_bytes());
+        message.extend(2u16.to_be_bytes());
+        message.extend(212u16.to_be_bytes());
+        message.extend(3u16.to_be_bytes());
+        message.extend([1, 2, 2, 2]);
+        message.extend(0u16.to_be_bytes());
+        message.extend(0b0011u16.to_be_bytes());
+        message.extend([0; 6]);
+
+        for supplemental_data in [0u16, 0, 0b0011] {
+            let mut elevation_cut = vec![0; 46];
+            elevation_cut[0..2].copy_from_slice(&88u16.to_be_bytes());
+            elevation_cut[28..30].copy_from_slice(&supplemental_data.to_be_bytes());
+            message.extend(elevation_cut);
+        }
+
+        message
+    }
+
+    #[test]
+    fn test_decode_volume_coverage_pattern() {
+        let message = decode_volume_coverage_pattern(&mut message().as_slice()).unwrap();
+        assert_eq!(message.header.pattern_number, 212);
+        assert!(message.header.sails());
+        assert_eq!(message.header.sails_cut_count(), 1);
+        assert!(!message.header.mrle());
+
+        assert_eq!(message.elevation_cuts.len(), 3);
+        for elevation_cut in &message.elevation_cuts {
+            assert!((elevation_cut.elevation_angle_degrees() - 0.483).abs() < 0.001);
+        }
+
+        let sails_sequence_numbers: Vec<Option<u8>> = message
+            .elevation_cuts
+            .iter()
+            .map(ElevationCut::sails_sequence_number)
+            .collect();
+        assert_eq!(sails_sequence_numbers, vec![None, None, Some(1)]);
+    }
+
+    #[cfg(feature = "nexrad-model")]
+    #[test]
+    fn test_volume_coverage_pattern_model() {
+        let message = decode_volume_coverage_pattern(&mut message().as_slice()).unwrap();
+        let coverage_pattern = message.volume_coverage_pattern();
+        assert_eq!(coverage_pattern.pattern_number(), 212);
+
+        let supplemental: Vec<bool> = coverage_pattern
+            .elevation_cuts()
+            .iter()
+            .map(|elevation_cut| elevation_cut.supplemental())
+            .collect();
+        assert_eq!(supplemental, vec![false, false, true]);
+    }
+}
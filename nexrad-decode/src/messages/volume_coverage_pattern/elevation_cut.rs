@@ -0,0 +1,163 @@
+use crate::messages::primitive_aliases::{Code1, Code2, Integer1, Integer2, ScaledSInteger2};
+use serde::Deserialize;
+use std::fmt::Debug;
+
+#[cfg(feature = "uom")]
+use uom::si::angle::degree;
+#[cfg(feature = "uom")]
+use uom::si::f64::Angle;
+
+/// The number of degrees per unit of a coded angle, in which bit 15 represents 180 degrees and
+/// bits 0-2 are unused.
+const CODED_ANGLE_DEGREES: f32 = 180.0 / 32768.0;
+
+/// The definition of a single elevation cut in a volume coverage pattern, including its waveform,
+/// pulse repetition frequencies and thresholds, and whether it is a supplemental cut.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct ElevationCut {
+    /// Elevation angle of the cut, coded in units of 180/32768 degrees.
+    pub elevation_angle: Code2,
+
+    /// Channel configuration.
+    ///
+    /// Values:
+    ///   0 = Constant phase
+    ///   1 = Random phase
+    ///   2 = SZ2 phase
+    pub channel_configuration: Code1,
+
+    /// Waveform type.
+    ///
+    /// Values:
+    ///   1 = Contiguous surveillance
+    ///   2 = Contiguous Doppler with ambiguity resolution
+    ///   3 = Contiguous Doppler without ambiguity resolution
+    ///   4 = Batch
+    ///   5 = Staggered pulse pair
+    pub waveform_type: Code1,
+
+    /// Super resolution control flags.
+    ///
+    /// Flags:
+    ///   Bit 0 = 0.5 degree azimuths
+    ///   Bit 1 = 0.25 km reflectivity
+    ///   Bit 2 = Doppler to 300 km
+    ///   Bit 3 = Dual polarization to 300 km
+    pub super_resolution_control: Code1,
+
+    /// Surveillance pulse repetition frequency number, from 1 to 8.
+    pub surveillance_prf_number: Integer1,
+
+    /// Surveillance pulse count per radial.
+    pub surveillance_prf_pulse_count: Integer2,
+
+    /// Azimuth rate, coded in units of 22.5/16384 degrees per second.
+    pub azimuth_rate: Code2,
+
+    /// Signal-to-noise ratio threshold for reflectivity in 0.125 dB.
+    pub reflectivity_threshold: ScaledSInteger2,
+
+    /// Signal-to-noise ratio threshold for velocity in 0.125 dB.
+    pub velocity_threshold: ScaledSInteger2,
+
+    /// Signal-to-noise ratio threshold for spectrum width in 0.125 dB.
+    pub spectrum_width_threshold: ScaledSInteger2,
+
+    /// Signal-to-noise ratio threshold for differential reflectivity in 0.125 dB.
+    pub differential_reflectivity_threshold: ScaledSInteger2,
+
+    /// Signal-to-noise ratio threshold for differential phase in 0.125 dB.
+    pub differential_phase_threshold: ScaledSInteger2,
+
+    /// Signal-to-noise ratio threshold for correlation coefficient in 0.125 dB.
+    pub correlation_coefficient_threshold: ScaledSInteger2,
+
+    /// Azimuth angle at which the first Doppler sector begins, coded as the elevation angle is.
+    pub sector_1_edge_angle: Code2,
+
+    /// Doppler pulse repetition frequency number for the first sector, from 1 to 8.
+    pub sector_1_doppler_prf_number: Integer2,
+
+    /// Doppler pulse count per radial for the first sector.
+    pub sector_1_doppler_prf_pulse_count: Integer2,
+
+    /// Flags describing whether this is a supplemental cut.
+    ///
+    /// Flags:
+    ///   Bit 0    = SAILS cut
+    ///   Bits 1-3 = SAILS sequence number
+    ///   Bit 4    = MRLE cut
+    ///   Bits 5-7 = MRLE sequence number
+    ///   Bit 9    = MPDA cut
+    ///   Bit 10   = Base tilt cut
+    pub supplemental_data: Code2,
+
+    /// Azimuth angle at which the second Doppler sector begins, coded as the elevation angle is.
+    pub sector_2_edge_angle: Code2,
+
+    /// Doppler pulse repetition frequency number for the second sector, from 1 to 8.
+    pub sector_2_doppler_prf_number: Integer2,
+
+    /// Doppler pulse count per radial for the second sector.
+    pub sector_2_doppler_prf_pulse_count: Integer2,
+
+    /// Elevation angle used for beam blockage correction, coded as the elevation angle is.
+    pub ebc_angle: Code2,
+
+    /// Azimuth angle at which the third Doppler sector begins, coded as the elevation angle is.
+    pub sector_3_edge_angle: Code2,
+
+    /// Doppler pulse repetition frequency number for the third sector, from 1 to 8.
+    pub sector_3_doppler_prf_number: Integer2,
+
+    /// Doppler pulse count per radial for the third sector.
+    pub sector_3_doppler_prf_pulse_count: Integer2,
+
+    /// Reserved.
+    pub reserved: Integer2,
+}
+
+impl ElevationCut {
+    /// Elevation angle of the cut in degrees.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle as f32 * CODED_ANGLE_DEGREES
+    }
+
+    /// Elevation angle of the cut.
+    #[cfg(feature = "uom")]
+    pub fn elevation_angle(&self) -> Angle {
+        Angle::new::<degree>(self.elevation_angle_degrees() as f64)
+    }
+
+    /// Whether this is a SAILS cut, revisiting the lowest elevation.
+    pub fn sails_cut(&self) -> bool {
+        self.supplemental_data & 1 != 0
+    }
+
+    /// This cut's sequence number among the pattern's SAILS cuts, if it is a SAILS cut.
+    pub fn sails_sequence_number(&self) -> Option<u8> {
+        self.sails_cut()
+            .then_some((self.supplemental_data >> 1 & 0b111) as u8)
+    }
+
+    /// Whether this is an MRLE cut, revisiting one of the lowest elevations mid-volume.
+    pub fn mrle_cut(&self) -> bool {
+        self.supplemental_data & 1 << 4 != 0
+    }
+
+    /// This cut's sequence number among the pattern's MRLE cuts, if it is an MRLE cut.
+    pub fn mrle_sequence_number(&self) -> Option<u8> {
+        self.mrle_cut()
+            .then_some((self.supplemental_data >> 5 & 0b111) as u8)
+    }
+
+    /// Whether this cut uses multi-PRF dealiasing (MPDA).
+    pub fn mpda_cut(&self) -> bool {
+        self.supplemental_data & 1 << 9 != 0
+    }
+
+    /// Whether this is a base tilt cut.
+    pub fn base_tilt_cut(&self) -> bool {
+        self.supplemental_data & 1 << 10 != 0
+    }
+}
@@ -0,0 +1,102 @@
+use crate::messages::primitive_aliases::{Code1, Code2, Integer1, Integer2};
+use serde::Deserialize;
+use std::fmt::Debug;
+
+/// Header information for a volume coverage pattern to be read directly from the Archive II file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct Header {
+    /// Size of this message in half-words, including this header and all elevation cuts.
+    pub message_size: Integer2,
+
+    /// Pattern type, which is always 2 for a constant elevation cut pattern.
+    pub pattern_type: Code2,
+
+    /// Volume coverage pattern number, e.g. 212.
+    pub pattern_number: Integer2,
+
+    /// Number of elevation cuts in the pattern, from 1 to 25, including supplemental cuts. They
+    /// will follow this header in the order they are scanned.
+    pub number_of_elevation_cuts: Integer2,
+
+    /// Version of the volume coverage pattern definition.
+    pub version: Integer1,
+
+    /// Clutter map group number, from 1 to 2.
+    pub clutter_map_group_number: Integer1,
+
+    /// Doppler velocity resolution.
+    ///
+    /// Values:
+    ///   2 = 0.5 m/s
+    ///   4 = 1.0 m/s
+    pub doppler_velocity_resolution: Code1,
+
+    /// Pulse width.
+    ///
+    /// Values:
+    ///   2 = Short
+    ///   4 = Long
+    pub pulse_width: Code1,
+
+    /// Flags describing the sequencing of the pattern's elevations.
+    ///
+    /// Flags:
+    ///   Bits 0-4  = Number of elevations
+    ///   Bits 5-6  = Maximum number of SAILS cuts
+    ///   Bit 13    = Sequence active
+    ///   Bit 14    = Truncated VCP
+    pub vcp_sequencing: Code2,
+
+    /// Flags describing the supplemental cuts added to the pattern.
+    ///
+    /// Flags:
+    ///   Bit 0     = SAILS VCP
+    ///   Bits 1-3  = Number of SAILS cuts
+    ///   Bit 4     = MRLE VCP
+    ///   Bits 5-7  = Number of MRLE cuts
+    ///   Bit 11    = MPDA VCP
+    ///   Bit 12    = Base tilt VCP
+    ///   Bits 13-15 = Number of base tilts
+    pub vcp_supplemental_data: Code2,
+
+    /// Reserved.
+    pub reserved: [Integer2; 3],
+}
+
+impl Header {
+    /// Whether SAILS cuts, which revisit the lowest elevation, are added to this pattern.
+    pub fn sails(&self) -> bool {
+        self.vcp_supplemental_data & 1 != 0
+    }
+
+    /// The number of SAILS cuts added to this pattern.
+    pub fn sails_cut_count(&self) -> u8 {
+        (self.vcp_supplemental_data >> 1 & 0b111) as u8
+    }
+
+    /// Whether MRLE cuts, which revisit the lowest elevations mid-volume, are added to this
+    /// pattern.
+    pub fn mrle(&self) -> bool {
+        self.vcp_supplemental_data & 1 << 4 != 0
+    }
+
+    /// The number of MRLE cuts added to this pattern.
+    pub fn mrle_cut_count(&self) -> u8 {
+        (self.vcp_supplemental_data >> 5 & 0b111) as u8
+    }
+
+    /// Whether this pattern uses multi-PRF dealiasing (MPDA).
+    pub fn mpda(&self) -> bool {
+        self.vcp_supplemental_data & 1 << 11 != 0
+    }
+
+    /// Whether this pattern includes base tilts.
+    pub fn base_tilt(&self) -> bool {
+        self.vcp_supplemental_data & 1 << 12 != 0
+    }
+
+    /// The number of base tilts in this pattern.
+    pub fn base_tilt_count(&self) -> u8 {
+        (self.vcp_supplemental_data >> 13) as u8
+    }
+}
@@ -0,0 +1,43 @@
+use crate::messages::volume_coverage_pattern::elevation_cut::ElevationCut;
+use crate::messages::volume_coverage_pattern::header::Header;
+use std::fmt::Debug;
+
+/// A volume coverage pattern describing the elevation cuts scanned in a volume.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Message {
+    /// Decoded header information for this volume coverage pattern.
+    pub header: Header,
+
+    /// The elevation cuts defined in this volume coverage pattern in the order they are scanned.
+    pub elevation_cuts: Vec<ElevationCut>,
+}
+
+impl Message {
+    /// Creates a new volume coverage pattern from the coded header.
+    pub(crate) fn new(header: Header) -> Self {
+        Self {
+            elevation_cuts: Vec::with_capacity(header.number_of_elevation_cuts as usize),
+            header,
+        }
+    }
+
+    /// Convert this volume coverage pattern into the common model's volume coverage pattern.
+    #[cfg(feature = "nexrad-model")]
+    pub fn volume_coverage_pattern(&self) -> nexrad_model::meta::VolumeCoveragePattern {
+        use nexrad_model::meta::{ElevationCut, VolumeCoveragePattern};
+
+        VolumeCoveragePattern::new(
+            self.header.pattern_number,
+            self.elevation_cuts
+                .iter()
+                .map(|elevation_cut| {
+                    ElevationCut::new(
+                        elevation_cut.elevation_angle_degrees(),
+                        elevation_cut.sails_sequence_number(),
+                        elevation_cut.mrle_sequence_number(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
@@ -19,3 +19,15 @@ pub use radial::*;
 
 mod moment;
 pub use moment::*;
+
+mod sample;
+pub use sample::*;
+
+mod product;
+pub use product::*;
+
+mod columnar;
+pub use columnar::*;
+
+mod validation;
+pub use validation::*;
@@ -0,0 +1,597 @@
+use crate::data::moment;
+use crate::data::{MomentData, MomentValue, Product, Radial, RadialStatus, Sweep};
+use crate::result::{Error, Result};
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A sweep stored by column rather than by radial: each radial property is held in its own array
+/// and each product's data is held in a single dense array indexed by radial and then gate. This
+/// representation suits numeric work over whole sweeps and converts to and from [Sweep].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColumnarSweep {
+    elevation_number: u8,
+    collection_timestamps: Vec<i64>,
+    azimuth_numbers: Vec<u16>,
+    azimuth_angles_degrees: Vec<f32>,
+    azimuth_spacings_degrees: Vec<f32>,
+    radial_statuses: Vec<RadialStatus>,
+    elevation_numbers: Vec<u8>,
+    cut_sector_numbers: Vec<u8>,
+    elevation_angles_degrees: Vec<f32>,
+    nyquist_velocities_meters_per_second: Vec<Option<f32>>,
+    moments: Vec<MomentGrid>,
+}
+
+impl ColumnarSweep {
+    /// Converts a sweep into columnar storage, preserving the order of its radials. Each product
+    /// must have the same gate geometry and encoding in every radial which carries it.
+    pub fn from_sweep(sweep: &Sweep) -> Result<Self> {
+        let radials = sweep.radials();
+
+        let mut products: Vec<Product> = Vec::new();
+        for radial in radials {
+            for (product, _) in radial.moments() {
+                if !products.contains(&product) {
+                    products.push(product);
+                }
+            }
+        }
+
+        let moments = products
+            .into_iter()
+            .map(|product| MomentGrid::from_radials(product, radials))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            elevation_number: sweep.elevation_number(),
+            collection_timestamps: radials.iter().map(Radial::collection_timestamp).collect(),
+            azimuth_numbers: radials.iter().map(Radial::azimuth_number).collect(),
+            azimuth_angles_degrees: radials.iter().map(Radial::azimuth_angle_degrees).collect(),
+            azimuth_spacings_degrees: radials
+                .iter()
+                .map(Radial::azimuth_spacing_degrees)
+                .collect(),
+            radial_statuses: radials.iter().map(Radial::radial_status).collect(),
+            elevation_numbers: radials.iter().map(Radial::elevation_number).collect(),
+            cut_sector_numbers: radials.iter().map(Radial::cut_sector_number).collect(),
+            elevation_angles_degrees: radials
+                .iter()
+                .map(Radial::elevation_angle_degrees)
+                .collect(),
+            nyquist_velocities_meters_per_second: radials
+                .iter()
+                .map(Radial::nyquist_velocity_meters_per_second)
+                .collect(),
+            moments,
+        })
+    }
+
+    /// Converts this columnar sweep back into a sweep of radials.
+    pub fn to_sweep(&self) -> Sweep {
+        let radials = (0..self.radial_count())
+            .map(|radial_index| {
+                let mut radial = Radial::new(
+                    self.collection_timestamps[radial_index],
+                    self.azimuth_numbers[radial_index],
+                    self.azimuth_angles_degrees[radial_index],
+                    self.azimuth_spacings_degrees[radial_index],
+                    self.radial_statuses[radial_index],
+                    self.elevation_numbers[radial_index],
+                    self.elevation_angles_degrees[radial_index],
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .with_cut_sector_number(self.cut_sector_numbers[radial_index])
+                .with_nyquist_velocity_meters_per_second(
+                    self.nyquist_velocities_meters_per_second[radial_index],
+                );
+
+                for moment in &self.moments {
+                    if let Some(data) = moment.radial_moment_data(radial_index) {
+                        radial.set_moment(moment.product().clone(), data);
+                    }
+                }
+
+                radial
+            })
+            .collect();
+
+        Sweep::new(self.elevation_number, radials)
+    }
+
+    /// The index number for this sweep's elevation in the volume scan.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// The number of radials in this sweep.
+    pub fn radial_count(&self) -> usize {
+        self.collection_timestamps.len()
+    }
+
+    /// Each radial's collection timestamp in milliseconds since the epoch.
+    pub fn collection_timestamps(&self) -> &[i64] {
+        &self.collection_timestamps
+    }
+
+    /// Each radial's azimuth number.
+    pub fn azimuth_numbers(&self) -> &[u16] {
+        &self.azimuth_numbers
+    }
+
+    /// Each radial's azimuth angle in degrees.
+    pub fn azimuth_angles_degrees(&self) -> &[f32] {
+        &self.azimuth_angles_degrees
+    }
+
+    /// Each radial's azimuth spacing in degrees.
+    pub fn azimuth_spacings_degrees(&self) -> &[f32] {
+        &self.azimuth_spacings_degrees
+    }
+
+    /// Each radial's position within the sequence of radials comprising a scan.
+    pub fn radial_statuses(&self) -> &[RadialStatus] {
+        &self.radial_statuses
+    }
+
+    /// Each radial's elevation number.
+    pub fn elevation_numbers(&self) -> &[u8] {
+        &self.elevation_numbers
+    }
+
+    /// Each radial's sector number within its cut.
+    pub fn cut_sector_numbers(&self) -> &[u8] {
+        &self.cut_sector_numbers
+    }
+
+    /// Each radial's elevation angle in degrees.
+    pub fn elevation_angles_degrees(&self) -> &[f32] {
+        &self.elevation_angles_degrees
+    }
+
+    /// Each radial's Nyquist velocity in meters per second, if known.
+    pub fn nyquist_velocities_meters_per_second(&self) -> &[Option<f32>] {
+        &self.nyquist_velocities_meters_per_second
+    }
+
+    /// The data for each product carried by this sweep's radials.
+    pub fn moments(&self) -> &[MomentGrid] {
+        &self.moments
+    }
+
+    /// The data for the given product, if any of this sweep's radials carry it.
+    pub fn moment(&self, product: &Product) -> Option<&MomentGrid> {
+        self.moments
+            .iter()
+            .find(|moment| moment.product() == product)
+    }
+}
+
+impl TryFrom<&Sweep> for ColumnarSweep {
+    type Error = Error;
+
+    fn try_from(sweep: &Sweep) -> Result<Self> {
+        Self::from_sweep(sweep)
+    }
+}
+
+impl From<&ColumnarSweep> for Sweep {
+    fn from(sweep: &ColumnarSweep) -> Self {
+        sweep.to_sweep()
+    }
+}
+
+impl Debug for ColumnarSweep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnarSweep")
+            .field("elevation_number", &self.elevation_number())
+            .field("radial_count", &self.radial_count())
+            .field("moments", &self.moments())
+            .finish()
+    }
+}
+
+/// A sweep's data for a single product, stored densely by radial and then gate as raw fixed-point
+/// codes. Radials which are shorter than the longest radial, or which do not carry the product,
+/// are padded to the full row length. Padding gates are stored with code 0 but are tracked
+/// separately from below-threshold gates and decode to no value.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MomentGrid {
+    product: Product,
+    first_gate_range_meters: u16,
+    gate_interval_meters: u16,
+    word_size: u8,
+    scale: f32,
+    offset: f32,
+    gate_count: usize,
+    radial_gate_counts: Vec<Option<usize>>,
+    raw_values: Vec<u8>,
+}
+
+impl MomentGrid {
+    /// Gathers a product's data from the given radials. The product must have the same gate
+    /// geometry and encoding in every radial which carries it.
+    pub fn from_radials(product: Product, radials: &[Radial]) -> Result<Self> {
+        let mut encoding = None;
+        for data in radials.iter().filter_map(|radial| radial.moment(&product)) {
+            let radial_encoding = (
+                data.first_gate_range_meters(),
+                data.gate_interval_meters(),
+                data.word_size(),
+                data.scale(),
+                data.offset(),
+            );
+
+            match encoding {
+                None => encoding = Some(radial_encoding),
+                Some(encoding) if encoding != radial_encoding => {
+                    return Err(Error::InconsistentMomentEncoding);
+                }
+                Some(_) => {}
+            }
+        }
+
+        let (first_gate_range_meters, gate_interval_meters, word_size, scale, offset) =
+            encoding.unwrap_or((0, 0, 8, 0.0, 0.0));
+
+        let radial_gate_counts: Vec<Option<usize>> = radials
+            .iter()
+            .map(|radial| radial.moment(&product).map(MomentData::gate_count))
+            .collect();
+        let gate_count = radial_gate_counts
+            .iter()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or_default();
+
+        let row_length = gate_count_bytes(word_size, gate_count);
+        let mut raw_values = vec![0; radials.len() * row_length];
+        for (radial_index, radial) in radials.iter().enumerate() {
+            if let Some(data) = radial.moment(&product) {
+                let start = radial_index * row_length;
+                let length = gate_count_bytes(word_size, data.gate_count());
+                raw_values[start..start + length].copy_from_slice(&data.raw_values()[..length]);
+            }
+        }
+
+        Ok(Self {
+            product,
+            first_gate_range_meters,
+            gate_interval_meters,
+            word_size,
+            scale,
+            offset,
+            gate_count,
+            radial_gate_counts,
+            raw_values,
+        })
+    }
+
+    /// The product this data is for.
+    pub fn product(&self) -> &Product {
+        &self.product
+    }
+
+    /// The number of radials in this grid.
+    pub fn radial_count(&self) -> usize {
+        self.radial_gate_counts.len()
+    }
+
+    /// The number of gates in each of this grid's rows, the most of any radial.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+
+    /// The number of gates in the radial at the given index, or [None] if the radial does not
+    /// carry this product.
+    pub fn radial_gate_count(&self, radial_index: usize) -> Option<usize> {
+        self.radial_gate_counts.get(radial_index).copied().flatten()
+    }
+
+    /// Range to the center of the first gate in meters.
+    pub fn first_gate_range_meters(&self) -> u16 {
+        self.first_gate_range_meters
+    }
+
+    /// Distance between the centers of adjacent gates in meters.
+    pub fn gate_interval_meters(&self) -> u16 {
+        self.gate_interval_meters
+    }
+
+    /// Slant range from the radar to the center of the gate at the given index in meters.
+    pub fn gate_range_meters(&self, gate_index: usize) -> f32 {
+        self.first_gate_range_meters as f32 + gate_index as f32 * self.gate_interval_meters as f32
+    }
+
+    /// The number of bits in each gate's word, either 8 or 16.
+    pub fn word_size(&self) -> u8 {
+        self.word_size
+    }
+
+    /// The scale applied to decode raw values into floating-point values. A scale of zero
+    /// indicates raw values are unscaled.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// The offset applied to decode raw values into floating-point values.
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// All raw fixed-point words ordered by radial and then gate, one byte per gate for 8-bit data
+    /// and two big-endian bytes per gate for 16-bit data. A word of 0 indicates the gate was below
+    /// threshold and 1 indicates it was range folded. Padding gates, which are beyond their
+    /// radial's [MomentGrid::radial_gate_count], also have word 0.
+    pub fn raw_values(&self) -> &[u8] {
+        &self.raw_values
+    }
+
+    /// The raw fixed-point words of the radial at the given index, including any padding.
+    pub fn radial_raw_values(&self, radial_index: usize) -> &[u8] {
+        let row_length = gate_count_bytes(self.word_size, self.gate_count);
+        let start = radial_index * row_length;
+        &self.raw_values[start..start + row_length]
+    }
+
+    /// Whether each gate, ordered by radial and then gate, is padding rather than part of its
+    /// radial's data.
+    pub fn padding_mask(&self) -> Vec<bool> {
+        let mut mask = Vec::with_capacity(self.radial_count() * self.gate_count);
+        for radial_index in 0..self.radial_count() {
+            let gate_count = self.radial_gate_count(radial_index).unwrap_or_default();
+            mask.extend((0..self.gate_count).map(|gate_index| gate_index >= gate_count));
+        }
+        mask
+    }
+
+    /// Whether each gate, ordered by radial and then gate, has a valid value rather than being
+    /// below threshold, range folded or padding.
+    pub fn valid_mask(&self) -> Vec<bool> {
+        self.values().iter().map(|value| !value.is_nan()).collect()
+    }
+
+    /// All values decoded to floating point, ordered by radial and then gate. Gates without a
+    /// valid value, including padding, are NaN.
+    pub fn values(&self) -> Vec<f32> {
+        let mut values = vec![f32::NAN; self.radial_count() * self.gate_count];
+        for (radial_index, radial_values) in
+            values.chunks_exact_mut(self.gate_count.max(1)).enumerate()
+        {
+            self.radial_values_into(radial_index, radial_values);
+        }
+        values
+    }
+
+    /// Decodes the values of the radial at the given index into the provided buffer, which should
+    /// be [MomentGrid::gate_count] long. Gates without a valid value, including padding, are NaN.
+    pub fn radial_values_into(&self, radial_index: usize, values: &mut [f32]) {
+        let gate_count = self.radial_gate_count(radial_index).unwrap_or_default();
+        let (data, padding) = values.split_at_mut(gate_count.min(values.len()));
+
+        moment::decode_fixed_point_into(
+            self.word_size,
+            self.scale,
+            self.offset,
+            self.radial_raw_values(radial_index),
+            data,
+        );
+        padding.fill(f32::NAN);
+    }
+
+    /// The value at the given radial and gate, or [None] if the gate is out of bounds or is
+    /// padding.
+    pub fn value(&self, radial_index: usize, gate_index: usize) -> Option<MomentValue> {
+        if gate_index >= self.radial_gate_count(radial_index)? {
+            return None;
+        }
+
+        let raw_value = moment::raw_word(
+            self.word_size,
+            self.radial_raw_values(radial_index),
+            gate_index,
+        )?;
+        Some(moment::decode_value(raw_value, self.scale, self.offset))
+    }
+
+    /// Reconstructs the moment data of the radial at the given index, if it carries this product.
+    pub fn radial_moment_data(&self, radial_index: usize) -> Option<MomentData> {
+        let gate_count = self.radial_gate_count(radial_index)?;
+        let raw_values = self.radial_raw_values(radial_index);
+
+        Some(
+            MomentData::from_fixed_point(
+                self.scale,
+                self.offset,
+                raw_values[..gate_count_bytes(self.word_size, gate_count)].to_vec(),
+            )
+            .with_gate_geometry(self.first_gate_range_meters, self.gate_interval_meters)
+            .with_word_size(self.word_size),
+        )
+    }
+}
+
+impl Debug for MomentGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MomentGrid")
+            .field("product", &self.product())
+            .field("radial_count", &self.radial_count())
+            .field("gate_count", &self.gate_count())
+            .field("first_gate_range_meters", &self.first_gate_range_meters())
+            .field("gate_interval_meters", &self.gate_interval_meters())
+            .field("word_size", &self.word_size())
+            .field("scale", &self.scale())
+            .field("offset", &self.offset())
+            .finish()
+    }
+}
+
+/// The number of bytes holding the given number of gates' words of the given size in bits.
+fn gate_count_bytes(word_size: u8, gate_count: usize) -> usize {
+    gate_count * moment::word_size_bytes(word_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{radial, reflectivity};
+
+    /// A sweep whose radials carry reflectivity with differing gate counts, one radial without
+    /// reflectivity and one with a custom unscaled product.
+    fn ragged_sweep() -> Sweep {
+        let radials = vec![
+            radial(0, 0, 1.0, 1, 0.5)
+                .with_moment(Product::Reflectivity, reflectivity(vec![0, 1, 86, 106]))
+                .with_nyquist_velocity_meters_per_second(Some(28.5))
+                .with_cut_sector_number(1),
+            radial(1_000, 1, 1.0, 1, 0.5)
+                .with_moment(Product::Reflectivity, reflectivity(vec![86, 106]))
+                .with_cut_sector_number(2),
+            radial(2_000, 2, 1.0, 1, 0.5).with_moment(
+                Product::Custom("COUNT".to_string()),
+                MomentData::from_fixed_point(0.0, 0.0, vec![0, 3]),
+            ),
+        ];
+
+        Sweep::new(1, radials)
+    }
+
+    #[test]
+    fn round_trips_sweep() {
+        let sweep = ragged_sweep();
+        let columnar = ColumnarSweep::from_sweep(&sweep).unwrap();
+
+        assert_eq!(columnar.radial_count(), 3);
+        assert_eq!(columnar.moments().len(), 2);
+        assert_eq!(
+            columnar.nyquist_velocities_meters_per_second(),
+            &[Some(28.5), None, None]
+        );
+        assert_eq!(columnar.cut_sector_numbers(), &[1, 2, 0]);
+        assert_eq!(columnar.to_sweep(), sweep);
+    }
+
+    #[test]
+    fn rejects_inconsistent_encoding() {
+        let radials = vec![
+            radial(0, 0, 1.0, 1, 0.5).with_moment(Product::Reflectivity, reflectivity(vec![86])),
+            radial(1_000, 1, 1.0, 1, 0.5).with_moment(
+                Product::Reflectivity,
+                MomentData::from_fixed_point(1.0, 0.0, vec![86]),
+            ),
+        ];
+
+        assert!(matches!(
+            ColumnarSweep::from_sweep(&Sweep::new(1, radials)),
+            Err(Error::InconsistentMomentEncoding)
+        ));
+    }
+
+    #[test]
+    fn distinguishes_padding_from_below_threshold() {
+        let columnar = ColumnarSweep::from_sweep(&ragged_sweep()).unwrap();
+        let grid = columnar.moment(&Product::Reflectivity).unwrap();
+
+        assert_eq!(grid.gate_count(), 4);
+        assert_eq!(grid.radial_gate_count(1), Some(2));
+        assert_eq!(grid.radial_gate_count(2), None);
+
+        assert_eq!(grid.value(0, 0), Some(MomentValue::BelowThreshold));
+        assert_eq!(grid.value(0, 1), Some(MomentValue::RangeFolded));
+        assert_eq!(grid.value(1, 1), Some(MomentValue::Value(20.0)));
+        assert_eq!(grid.value(1, 2), None);
+        assert_eq!(grid.value(2, 0), None);
+
+        assert_eq!(
+            grid.padding_mask(),
+            vec![false, false, false, false, false, false, true, true, true, true, true, true]
+        );
+        assert_eq!(
+            grid.valid_mask(),
+            vec![false, false, true, true, true, true, false, false, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn decodes_values_with_padding_as_nan() {
+        let columnar = ColumnarSweep::from_sweep(&ragged_sweep()).unwrap();
+        let grid = columnar.moment(&Product::Reflectivity).unwrap();
+
+        let values = grid.values();
+        assert_eq!(values.len(), 12);
+        assert_eq!(&values[2..6], &[10.0, 20.0, 10.0, 20.0]);
+        assert!(values[..2].iter().all(|value| value.is_nan()));
+        assert!(values[6..].iter().all(|value| value.is_nan()));
+
+        let mut radial_values = [0.0; 4];
+        grid.radial_values_into(1, &mut radial_values);
+        assert_eq!(&radial_values[..2], &[10.0, 20.0]);
+        assert!(radial_values[2..].iter().all(|value| value.is_nan()));
+    }
+
+    #[test]
+    fn decodes_unscaled_values_with_padding_as_nan() {
+        let columnar = ColumnarSweep::from_sweep(&ragged_sweep()).unwrap();
+        let grid = columnar
+            .moment(&Product::Custom("COUNT".to_string()))
+            .unwrap();
+
+        assert_eq!(grid.value(2, 0), Some(MomentValue::Value(0.0)));
+        assert_eq!(grid.value(0, 0), None);
+
+        let values = grid.values();
+        assert_eq!(&values[4..], &[0.0, 3.0]);
+        assert!(values[..4].iter().all(|value| value.is_nan()));
+
+        let mut radial_values = [0.0; 2];
+        grid.radial_values_into(0, &mut radial_values);
+        assert!(radial_values.iter().all(|value| value.is_nan()));
+    }
+
+    #[test]
+    fn round_trips_16_bit_words() {
+        let phase = |values: Vec<u8>| {
+            MomentData::from_fixed_point(2.8361, 2.0, values)
+                .with_word_size(16)
+                .with_gate_geometry(2_000, 250)
+        };
+        let radials = vec![
+            radial(0, 0, 1.0, 1, 0.5).with_moment(
+                Product::DifferentialPhase,
+                phase(vec![0x01, 0xFE, 0, 1, 0x03, 0xFE]),
+            ),
+            radial(1_000, 1, 1.0, 1, 0.5)
+                .with_moment(Product::DifferentialPhase, phase(vec![0x02, 0x00])),
+        ];
+        let sweep = Sweep::new(1, radials);
+
+        let columnar = ColumnarSweep::from_sweep(&sweep).unwrap();
+        let grid = columnar.moment(&Product::DifferentialPhase).unwrap();
+        assert_eq!(grid.word_size(), 16);
+        assert_eq!(grid.gate_count(), 3);
+        assert_eq!(grid.raw_values().len(), 12);
+        assert_eq!(grid.value(0, 1), Some(MomentValue::RangeFolded));
+        assert_eq!(
+            grid.value(1, 0),
+            Some(MomentValue::Value((0x0200 as f32 - 2.0) / 2.8361))
+        );
+        assert_eq!(grid.value(1, 1), None);
+
+        let mut values = [0.0; 3];
+        grid.radial_values_into(0, &mut values);
+        assert_eq!(values[0], (0x01FE as f32 - 2.0) / 2.8361);
+        assert!(values[1].is_nan());
+
+        assert_eq!(columnar.to_sweep(), sweep);
+    }
+}
@@ -3,56 +3,277 @@ use std::fmt::Debug;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "uom")]
+use uom::si::{f32::Length, length::meter};
+
 /// Moment data from a radial for a particular product where each value corresponds to a gate.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MomentData {
+    #[cfg_attr(feature = "serde", serde(default))]
+    first_gate_range: u16,
+    #[cfg_attr(feature = "serde", serde(default))]
+    gate_interval: u16,
+    #[cfg_attr(feature = "serde", serde(default = "default_word_size"))]
+    word_size: u8,
     scale: f32,
     offset: f32,
     values: Vec<u8>,
 }
 
 impl MomentData {
-    /// Create new moment data from fixed-point encoding.
+    /// Create new moment data from fixed-point encoding with one 8-bit word per gate. The data's
+    /// gate geometry is unknown until set with [MomentData::with_gate_geometry].
     pub fn from_fixed_point(scale: f32, offset: f32, values: Vec<u8>) -> Self {
         Self {
+            first_gate_range: 0,
+            gate_interval: 0,
+            word_size: 8,
             scale,
             offset,
             values,
         }
     }
 
+    /// This moment data with the given range to the center of the first gate and distance between
+    /// the centers of adjacent gates in meters, describing the distance of each gate from the
+    /// radar.
+    pub fn with_gate_geometry(mut self, first_gate_range: u16, gate_interval: u16) -> Self {
+        self.first_gate_range = first_gate_range;
+        self.gate_interval = gate_interval;
+        self
+    }
+
+    /// This moment data with the given number of bits in each gate's word, either 8 or 16. The
+    /// raw values of 16-bit data hold each gate's word in big-endian byte order.
+    pub fn with_word_size(mut self, word_size: u8) -> Self {
+        self.word_size = word_size;
+        self
+    }
+
+    /// The number of gates in this data moment.
+    pub fn gate_count(&self) -> usize {
+        self.values.len() / word_size_bytes(self.word_size)
+    }
+
+    /// The number of bits in each gate's word, either 8 or 16.
+    pub fn word_size(&self) -> u8 {
+        self.word_size
+    }
+
+    /// Range from the radar to the center of the first gate in meters.
+    pub fn first_gate_range_meters(&self) -> u16 {
+        self.first_gate_range
+    }
+
+    /// Range from the radar to the center of the first gate.
+    #[cfg(feature = "uom")]
+    pub fn first_gate_range(&self) -> Length {
+        Length::new::<meter>(self.first_gate_range as f32)
+    }
+
+    /// Distance between the centers of adjacent gates in meters.
+    pub fn gate_interval_meters(&self) -> u16 {
+        self.gate_interval
+    }
+
+    /// Distance between the centers of adjacent gates.
+    #[cfg(feature = "uom")]
+    pub fn gate_interval(&self) -> Length {
+        Length::new::<meter>(self.gate_interval as f32)
+    }
+
+    /// Slant range from the radar to the center of the gate at the given index in meters.
+    pub fn gate_range_meters(&self, gate_index: usize) -> f32 {
+        self.first_gate_range as f32 + gate_index as f32 * self.gate_interval as f32
+    }
+
+    /// The index of the gate whose center is nearest the given slant range in meters, if the range
+    /// falls within this data moment's gates.
+    pub fn gate_index_meters(&self, range: f32) -> Option<usize> {
+        if self.gate_interval == 0 || self.gate_count() == 0 {
+            return None;
+        }
+
+        let index = ((range - self.first_gate_range as f32) / self.gate_interval as f32).round();
+        if index < 0.0 || index as usize >= self.gate_count() {
+            return None;
+        }
+
+        Some(index as usize)
+    }
+
+    /// The scale applied to decode raw values into floating-point values. A scale of zero
+    /// indicates raw values are unscaled.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// The offset applied to decode raw values into floating-point values.
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// The raw fixed-point words for each gate, one byte per gate for 8-bit data and two
+    /// big-endian bytes per gate for 16-bit data. A raw word of 0 indicates the gate was below
+    /// threshold and 1 indicates it was range folded.
+    pub fn raw_values(&self) -> &Vec<u8> {
+        self.values.as_ref()
+    }
+
+    /// The raw fixed-point word of the gate at the given index, if any.
+    pub fn raw_value(&self, gate_index: usize) -> Option<u16> {
+        raw_word(self.word_size, &self.values, gate_index)
+    }
+
+    /// The value of the gate at the given index, if any.
+    pub fn value(&self, gate_index: usize) -> Option<MomentValue> {
+        Some(decode_value(
+            self.raw_value(gate_index)?,
+            self.scale,
+            self.offset,
+        ))
+    }
+
     /// Values from this data moment corresponding to gates in the radial.
     pub fn values(&self) -> Vec<MomentValue> {
-        let copied_values = self.values.iter().copied();
+        (0..self.gate_count())
+            .filter_map(|gate_index| self.value(gate_index))
+            .collect()
+    }
 
-        if self.scale == 0.0 {
-            return copied_values
-                .map(|raw_value| MomentValue::Value(raw_value as f32))
-                .collect();
+    /// Decodes the values of this data moment into the provided buffer, which should be
+    /// [MomentData::gate_count] long, without matching each gate. Gates without a valid value are
+    /// NaN. Returns the number of values written.
+    pub fn values_into(&self, values: &mut [f32]) -> usize {
+        decode_fixed_point_into(
+            self.word_size,
+            self.scale,
+            self.offset,
+            &self.values,
+            values,
+        )
+    }
+}
+
+/// Decodes fixed-point words of the given size in bits into the provided buffer. 8-bit words are
+/// decoded through a lookup table and 16-bit words are read in big-endian byte order. Words
+/// without a valid value, those below threshold or range folded, are NaN. Returns the number of
+/// values written.
+pub(crate) fn decode_fixed_point_into(
+    word_size: u8,
+    scale: f32,
+    offset: f32,
+    raw_values: &[u8],
+    values: &mut [f32],
+) -> usize {
+    if word_size == 16 {
+        let words = raw_values.chunks_exact(2);
+        let count = values.len().min(words.len());
+        for (value, word) in values[..count].iter_mut().zip(words) {
+            *value = decode_word(u16::from_be_bytes([word[0], word[1]]), scale, offset);
         }
+        return count;
+    }
 
-        copied_values
-            .map(|raw_value| match raw_value {
-                0 => MomentValue::BelowThreshold,
-                1 => MomentValue::RangeFolded,
-                _ => MomentValue::Value((raw_value as f32 - self.offset) / self.scale),
-            })
-            .collect()
+    let table = decode_table(scale, offset);
+    let count = values.len().min(raw_values.len());
+    for (value, raw_value) in values[..count].iter_mut().zip(&raw_values[..count]) {
+        *value = table[*raw_value as usize];
+    }
+    count
+}
+
+/// The floating-point value of each 8-bit raw word for the given scale and offset, with NaN for
+/// the below threshold and range folded words.
+pub(crate) fn decode_table(scale: f32, offset: f32) -> [f32; 256] {
+    let mut table = [0.0; 256];
+    for (raw_value, value) in table.iter_mut().enumerate() {
+        *value = decode_word(raw_value as u16, scale, offset);
+    }
+    table
+}
+
+/// Decodes a single raw word, selecting NaN for special words rather than branching so that bulk
+/// loops over it can be vectorized. A scale of zero indicates the word is unscaled.
+#[inline(always)]
+fn decode_word(raw_value: u16, scale: f32, offset: f32) -> f32 {
+    if scale == 0.0 {
+        return raw_value as f32;
+    }
+
+    let value = (raw_value as f32 - offset) / scale;
+    if raw_value < 2 {
+        f32::NAN
+    } else {
+        value
+    }
+}
+
+/// Decodes a single raw word, identifying the below threshold and range folded words.
+pub(crate) fn decode_value(raw_value: u16, scale: f32, offset: f32) -> MomentValue {
+    if scale == 0.0 {
+        return MomentValue::Value(decode_word(raw_value, scale, offset));
+    }
+
+    match raw_value {
+        0 => MomentValue::BelowThreshold,
+        1 => MomentValue::RangeFolded,
+        _ => MomentValue::Value(decode_word(raw_value, scale, offset)),
+    }
+}
+
+/// The raw word at the given gate index of fixed-point data with words of the given size in bits.
+pub(crate) fn raw_word(word_size: u8, raw_values: &[u8], gate_index: usize) -> Option<u16> {
+    if word_size == 16 {
+        let word = raw_values.get(gate_index * 2..gate_index * 2 + 2)?;
+        return Some(u16::from_be_bytes([word[0], word[1]]));
     }
+
+    raw_values
+        .get(gate_index)
+        .map(|raw_value| *raw_value as u16)
+}
+
+/// The number of bytes in each word of fixed-point data with words of the given size in bits.
+pub(crate) fn word_size_bytes(word_size: u8) -> usize {
+    if word_size == 16 {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_word_size() -> u8 {
+    8
 }
 
 impl Debug for MomentData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("MomentData")
-            .field("values", &self.values())
-            .finish()
+        let mut debug = f.debug_struct("MomentData");
+
+        debug.field("first_gate_range_meters", &self.first_gate_range_meters());
+
+        #[cfg(feature = "uom")]
+        debug.field("first_gate_range", &self.first_gate_range());
+
+        debug.field("gate_interval_meters", &self.gate_interval_meters());
+
+        #[cfg(feature = "uom")]
+        debug.field("gate_interval", &self.gate_interval());
+
+        debug.field("word_size", &self.word_size());
+        debug.field("values", &self.values());
+
+        debug.finish()
     }
 }
 
 /// The data moment value for a product in a radial's gate. The value may be a floating-point number
 /// or a special case such as "below threshold" or "range folded".
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MomentValue {
     /// The data moment value for a gate.
     Value(f32),
@@ -61,3 +282,79 @@ pub enum MomentValue {
     /// The value for this gate exceeded the maximum unambiguous range.
     RangeFolded,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_8_bit_words() {
+        let data = MomentData::from_fixed_point(2.0, 66.0, vec![0, 1, 86, 106]);
+        assert_eq!(data.word_size(), 8);
+        assert_eq!(data.gate_count(), 4);
+        assert_eq!(data.raw_value(2), Some(86));
+        assert_eq!(
+            data.values(),
+            vec![
+                MomentValue::BelowThreshold,
+                MomentValue::RangeFolded,
+                MomentValue::Value(10.0),
+                MomentValue::Value(20.0),
+            ]
+        );
+
+        let mut values = [0.0; 4];
+        assert_eq!(data.values_into(&mut values), 4);
+        assert!(values[0].is_nan() && values[1].is_nan());
+        assert_eq!(&values[2..], &[10.0, 20.0]);
+    }
+
+    #[test]
+    fn decodes_16_bit_words() {
+        let data =
+            MomentData::from_fixed_point(2.8361, 2.0, vec![0, 0, 0, 1, 0x01, 0xFE, 0x03, 0xFE])
+                .with_word_size(16);
+        assert_eq!(data.gate_count(), 4);
+        assert_eq!(data.raw_value(2), Some(0x01FE));
+        assert_eq!(data.raw_value(4), None);
+        assert_eq!(data.value(0), Some(MomentValue::BelowThreshold));
+        assert_eq!(data.value(1), Some(MomentValue::RangeFolded));
+        assert_eq!(
+            data.value(2),
+            Some(MomentValue::Value((0x01FE as f32 - 2.0) / 2.8361))
+        );
+        assert_eq!(data.values().len(), 4);
+
+        let mut values = [0.0; 4];
+        assert_eq!(data.values_into(&mut values), 4);
+        assert!(values[0].is_nan() && values[1].is_nan());
+        assert_eq!(values[2], (0x01FE as f32 - 2.0) / 2.8361);
+        assert_eq!(values[3], (0x03FE as f32 - 2.0) / 2.8361);
+    }
+
+    #[test]
+    fn decodes_unscaled_words() {
+        let data = MomentData::from_fixed_point(0.0, 0.0, vec![0, 1, 2]);
+        assert_eq!(
+            data.values(),
+            vec![
+                MomentValue::Value(0.0),
+                MomentValue::Value(1.0),
+                MomentValue::Value(2.0),
+            ]
+        );
+
+        let mut values = [f32::NAN; 3];
+        data.values_into(&mut values);
+        assert_eq!(values, [0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn locates_gates_of_16_bit_words() {
+        let data = MomentData::from_fixed_point(1.0, 0.0, vec![0; 8])
+            .with_word_size(16)
+            .with_gate_geometry(2_000, 250);
+        assert_eq!(data.gate_index_meters(2_750.0), Some(3));
+        assert_eq!(data.gate_index_meters(3_000.0), None);
+    }
+}
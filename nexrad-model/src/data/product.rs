@@ -0,0 +1,143 @@
+use std::fmt::{Debug, Display};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A data moment product which a radial may carry. The base products are those provided by the
+/// NEXRAD Level II data, while custom products are identified by name and may hold derived or
+/// corrected data attached to radials after decoding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Product {
+    /// Reflectivity factor in dBZ.
+    Reflectivity,
+    /// Radial velocity in meters per second.
+    Velocity,
+    /// Spectrum width in meters per second.
+    SpectrumWidth,
+    /// Differential reflectivity in dB.
+    DifferentialReflectivity,
+    /// Differential phase in degrees.
+    DifferentialPhase,
+    /// Correlation coefficient, unitless from 0 to 1.
+    CorrelationCoefficient,
+    /// Specific differential phase in degrees per kilometer.
+    SpecificDifferentialPhase,
+    /// A derived or custom product identified by name.
+    Custom(String),
+}
+
+impl Product {
+    /// The base products provided by NEXRAD Level II data.
+    pub const BASE: [Product; 7] = [
+        Product::Reflectivity,
+        Product::Velocity,
+        Product::SpectrumWidth,
+        Product::DifferentialReflectivity,
+        Product::DifferentialPhase,
+        Product::CorrelationCoefficient,
+        Product::SpecificDifferentialPhase,
+    ];
+
+    /// A human-readable name for this product.
+    pub fn name(&self) -> &str {
+        match self {
+            Product::Reflectivity => "Reflectivity",
+            Product::Velocity => "Velocity",
+            Product::SpectrumWidth => "Spectrum Width",
+            Product::DifferentialReflectivity => "Differential Reflectivity",
+            Product::DifferentialPhase => "Differential Phase",
+            Product::CorrelationCoefficient => "Correlation Coefficient",
+            Product::SpecificDifferentialPhase => "Specific Differential Phase",
+            Product::Custom(name) => name,
+        }
+    }
+
+    /// The name of the data block carrying this product in Level II data, if it is a base product.
+    pub fn data_name(&self) -> Option<&'static str> {
+        match self {
+            Product::Reflectivity => Some("REF"),
+            Product::Velocity => Some("VEL"),
+            Product::SpectrumWidth => Some("SW "),
+            Product::DifferentialReflectivity => Some("ZDR"),
+            Product::DifferentialPhase => Some("PHI"),
+            Product::CorrelationCoefficient => Some("RHO"),
+            Product::SpecificDifferentialPhase => Some("CFP"),
+            Product::Custom(_) => None,
+        }
+    }
+
+    /// The units of this product's values, if it is a base product. The correlation coefficient
+    /// is unitless and has empty units.
+    pub fn units(&self) -> Option<&'static str> {
+        match self {
+            Product::Reflectivity => Some("dBZ"),
+            Product::Velocity | Product::SpectrumWidth => Some("m/s"),
+            Product::DifferentialReflectivity => Some("dB"),
+            Product::DifferentialPhase => Some("deg"),
+            Product::CorrelationCoefficient => Some(""),
+            Product::SpecificDifferentialPhase => Some("deg/km"),
+            Product::Custom(_) => None,
+        }
+    }
+
+    /// Whether this product's values are Doppler measurements, which are subject to velocity
+    /// aliasing and range folding.
+    pub fn is_doppler(&self) -> bool {
+        matches!(self, Product::Velocity | Product::SpectrumWidth)
+    }
+
+    /// Whether this product is a dual-polarization measurement.
+    pub fn is_dual_polarization(&self) -> bool {
+        matches!(
+            self,
+            Product::DifferentialReflectivity
+                | Product::DifferentialPhase
+                | Product::CorrelationCoefficient
+                | Product::SpecificDifferentialPhase
+        )
+    }
+
+    /// Whether this is a derived or custom product rather than a base product.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Product::Custom(_))
+    }
+}
+
+impl Display for Product {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_products_have_metadata() {
+        for product in Product::BASE {
+            assert!(!product.is_custom());
+            assert_eq!(product.data_name().map(str::len), Some(3));
+            assert!(product.units().is_some());
+        }
+
+        assert_eq!(Product::SpectrumWidth.data_name(), Some("SW "));
+        assert_eq!(Product::Velocity.units(), Some("m/s"));
+        assert!(Product::SpectrumWidth.is_doppler());
+        assert!(Product::CorrelationCoefficient.is_dual_polarization());
+        assert!(!Product::Reflectivity.is_doppler());
+        assert!(!Product::Reflectivity.is_dual_polarization());
+    }
+
+    #[test]
+    fn custom_products_are_named() {
+        let product = Product::Custom("KDP_corrected".to_string());
+        assert!(product.is_custom());
+        assert_eq!(product.to_string(), "KDP_corrected");
+        assert_eq!(product.data_name(), None);
+        assert_eq!(product.units(), None);
+        assert!(!product.is_doppler());
+        assert!(!product.is_dual_polarization());
+    }
+}
@@ -1,11 +1,15 @@
-use crate::data::MomentData;
+use crate::data::{MomentData, Product};
 use std::fmt::Debug;
 
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, Utc};
 
 #[cfg(feature = "uom")]
-use uom::si::{angle::degree, f32::Angle};
+use uom::si::{
+    angle::degree,
+    f32::{Angle, Velocity},
+    velocity::meter_per_second,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -25,9 +29,15 @@ pub struct Radial {
 
     radial_status: RadialStatus,
 
+    #[cfg_attr(feature = "serde", serde(default))]
+    cut_sector_number: u8,
+
     elevation_number: u8,
     elevation_angle_degrees: f32,
 
+    #[cfg_attr(feature = "serde", serde(default))]
+    nyquist_velocity_meters_per_second: Option<f32>,
+
     reflectivity: Option<MomentData>,
     velocity: Option<MomentData>,
     spectrum_width: Option<MomentData>,
@@ -35,6 +45,9 @@ pub struct Radial {
     differential_phase: Option<MomentData>,
     correlation_coefficient: Option<MomentData>,
     specific_differential_phase: Option<MomentData>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    custom_moments: Vec<(String, MomentData)>,
 }
 
 impl Radial {
@@ -61,8 +74,10 @@ impl Radial {
             azimuth_angle_degrees,
             azimuth_spacing_degrees,
             radial_status,
+            cut_sector_number: 0,
             elevation_number,
             elevation_angle_degrees,
+            nyquist_velocity_meters_per_second: None,
             reflectivity,
             velocity,
             spectrum_width,
@@ -70,6 +85,7 @@ impl Radial {
             differential_phase,
             correlation_coefficient,
             specific_differential_phase,
+            custom_moments: Vec::new(),
         }
     }
 
@@ -117,6 +133,18 @@ impl Radial {
         self.radial_status
     }
 
+    /// The sector number of this radial within its elevation cut. Sectors are numbered from 1,
+    /// and 0 is only valid for continuous surveillance cuts.
+    pub fn cut_sector_number(&self) -> u8 {
+        self.cut_sector_number
+    }
+
+    /// This radial with the given sector number within its elevation cut.
+    pub fn with_cut_sector_number(mut self, cut_sector_number: u8) -> Self {
+        self.cut_sector_number = cut_sector_number;
+        self
+    }
+
     /// The elevation number for this radial in the volume scan.
     pub fn elevation_number(&self) -> u8 {
         self.elevation_number
@@ -133,6 +161,29 @@ impl Radial {
         Angle::new::<degree>(self.elevation_angle_degrees)
     }
 
+    /// The maximum unambiguous velocity for this radial in meters per second, if known. Velocities
+    /// with a greater magnitude are aliased (folded) into the range of +/- this value.
+    pub fn nyquist_velocity_meters_per_second(&self) -> Option<f32> {
+        self.nyquist_velocity_meters_per_second
+    }
+
+    /// The maximum unambiguous velocity for this radial, if known. Velocities with a greater
+    /// magnitude are aliased (folded) into the range of +/- this value.
+    #[cfg(feature = "uom")]
+    pub fn nyquist_velocity(&self) -> Option<Velocity> {
+        self.nyquist_velocity_meters_per_second
+            .map(Velocity::new::<meter_per_second>)
+    }
+
+    /// This radial with the given Nyquist velocity in meters per second.
+    pub fn with_nyquist_velocity_meters_per_second(
+        mut self,
+        nyquist_velocity_meters_per_second: Option<f32>,
+    ) -> Self {
+        self.nyquist_velocity_meters_per_second = nyquist_velocity_meters_per_second;
+        self
+    }
+
     /// Reflectivity data for this radial if available.
     pub fn reflectivity(&self) -> Option<&MomentData> {
         self.reflectivity.as_ref()
@@ -167,6 +218,95 @@ impl Radial {
     pub fn specific_differential_phase(&self) -> Option<&MomentData> {
         self.specific_differential_phase.as_ref()
     }
+
+    /// Data for the given product in this radial if available.
+    pub fn moment(&self, product: &Product) -> Option<&MomentData> {
+        match product {
+            Product::Reflectivity => self.reflectivity(),
+            Product::Velocity => self.velocity(),
+            Product::SpectrumWidth => self.spectrum_width(),
+            Product::DifferentialReflectivity => self.differential_reflectivity(),
+            Product::DifferentialPhase => self.differential_phase(),
+            Product::CorrelationCoefficient => self.correlation_coefficient(),
+            Product::SpecificDifferentialPhase => self.specific_differential_phase(),
+            Product::Custom(name) => self
+                .custom_moments
+                .iter()
+                .find(|(custom_name, _)| custom_name == name)
+                .map(|(_, data)| data),
+        }
+    }
+
+    /// Each product available in this radial with its data, base products first followed by
+    /// custom products in the order they were attached.
+    pub fn moments(&self) -> impl Iterator<Item = (Product, &MomentData)> {
+        Product::BASE
+            .into_iter()
+            .filter_map(|product| {
+                let data = self.moment(&product)?;
+                Some((product, data))
+            })
+            .chain(
+                self.custom_moments
+                    .iter()
+                    .map(|(name, data)| (Product::Custom(name.clone()), data)),
+            )
+    }
+
+    /// Attaches data for the given product to this radial, replacing and returning any existing
+    /// data for the product.
+    pub fn set_moment(&mut self, product: Product, data: MomentData) -> Option<MomentData> {
+        match product {
+            Product::Custom(name) => {
+                match self
+                    .custom_moments
+                    .iter_mut()
+                    .find(|(custom_name, _)| *custom_name == name)
+                {
+                    Some((_, existing)) => Some(std::mem::replace(existing, data)),
+                    None => {
+                        self.custom_moments.push((name, data));
+                        None
+                    }
+                }
+            }
+            base => self.base_moment_mut(&base)?.replace(data),
+        }
+    }
+
+    /// This radial with data for the given product attached, replacing any existing data for the
+    /// product.
+    pub fn with_moment(mut self, product: Product, data: MomentData) -> Self {
+        self.set_moment(product, data);
+        self
+    }
+
+    /// Removes and returns this radial's data for the given product, if any.
+    pub fn remove_moment(&mut self, product: &Product) -> Option<MomentData> {
+        match product {
+            Product::Custom(name) => {
+                let index = self
+                    .custom_moments
+                    .iter()
+                    .position(|(custom_name, _)| custom_name == name)?;
+                Some(self.custom_moments.remove(index).1)
+            }
+            base => self.base_moment_mut(base)?.take(),
+        }
+    }
+
+    fn base_moment_mut(&mut self, product: &Product) -> Option<&mut Option<MomentData>> {
+        match product {
+            Product::Reflectivity => Some(&mut self.reflectivity),
+            Product::Velocity => Some(&mut self.velocity),
+            Product::SpectrumWidth => Some(&mut self.spectrum_width),
+            Product::DifferentialReflectivity => Some(&mut self.differential_reflectivity),
+            Product::DifferentialPhase => Some(&mut self.differential_phase),
+            Product::CorrelationCoefficient => Some(&mut self.correlation_coefficient),
+            Product::SpecificDifferentialPhase => Some(&mut self.specific_differential_phase),
+            Product::Custom(_) => None,
+        }
+    }
 }
 
 impl Debug for Radial {
@@ -192,6 +332,8 @@ impl Debug for Radial {
 
         debug.field("radial_status", &self.radial_status());
 
+        debug.field("cut_sector_number", &self.cut_sector_number());
+
         debug.field("elevation_number", &self.elevation_number());
 
         debug.field("elevation_angle_degrees", &self.elevation_angle_degrees());
@@ -199,6 +341,14 @@ impl Debug for Radial {
         #[cfg(feature = "uom")]
         debug.field("elevation_angle", &self.elevation_angle());
 
+        debug.field(
+            "nyquist_velocity_meters_per_second",
+            &self.nyquist_velocity_meters_per_second(),
+        );
+
+        #[cfg(feature = "uom")]
+        debug.field("nyquist_velocity", &self.nyquist_velocity());
+
         debug.field("reflectivity", &self.reflectivity());
 
         debug.field("velocity", &self.velocity());
@@ -219,6 +369,8 @@ impl Debug for Radial {
             &self.specific_differential_phase(),
         );
 
+        debug.field("custom_moments", &self.custom_moments);
+
         debug.finish()
     }
 }
@@ -235,3 +387,70 @@ pub enum RadialStatus {
     /// Start of new elevation which is the last in the VCP.
     ElevationStartVCPFinal,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{radial, reflectivity};
+
+    #[test]
+    fn sets_and_removes_base_moments() {
+        let mut radial = radial(0, 0, 1.0, 1, 0.5);
+        assert!(radial.moment(&Product::Reflectivity).is_none());
+
+        let first = reflectivity(vec![100; 10]);
+        let second = reflectivity(vec![120; 10]);
+        assert_eq!(
+            radial.set_moment(Product::Reflectivity, first.clone()),
+            None
+        );
+        assert_eq!(
+            radial.set_moment(Product::Reflectivity, second.clone()),
+            Some(first)
+        );
+        assert_eq!(radial.moment(&Product::Reflectivity), Some(&second));
+        assert_eq!(radial.reflectivity(), Some(&second));
+
+        assert_eq!(radial.remove_moment(&Product::Reflectivity), Some(second));
+        assert!(radial.reflectivity().is_none());
+        assert_eq!(radial.remove_moment(&Product::Reflectivity), None);
+    }
+
+    #[test]
+    fn sets_and_removes_custom_moments() {
+        let product = Product::Custom("KDP_corrected".to_string());
+        let first = reflectivity(vec![100; 10]);
+        let second = reflectivity(vec![120; 10]);
+
+        let mut radial = radial(0, 0, 1.0, 1, 0.5).with_moment(product.clone(), first.clone());
+        assert_eq!(radial.moment(&product), Some(&first));
+        assert_eq!(
+            radial.set_moment(product.clone(), second.clone()),
+            Some(first)
+        );
+        assert_eq!(radial.moment(&product), Some(&second));
+
+        assert_eq!(radial.remove_moment(&product), Some(second));
+        assert!(radial.moment(&product).is_none());
+    }
+
+    #[test]
+    fn enumerates_base_then_custom_moments() {
+        let radial = radial(0, 0, 1.0, 1, 0.5)
+            .with_moment(Product::Custom("B".to_string()), reflectivity(vec![1]))
+            .with_moment(Product::Velocity, reflectivity(vec![2]))
+            .with_moment(Product::Custom("A".to_string()), reflectivity(vec![3]))
+            .with_moment(Product::Reflectivity, reflectivity(vec![4]));
+
+        let products: Vec<Product> = radial.moments().map(|(product, _)| product).collect();
+        assert_eq!(
+            products,
+            vec![
+                Product::Reflectivity,
+                Product::Velocity,
+                Product::Custom("B".to_string()),
+                Product::Custom("A".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::data::MomentValue;
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A data moment's value in a single gate along with the gate's location, as found when querying a
+/// sweep or scan at a point.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GateSample {
+    collection_timestamp: i64,
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+    azimuth_angle_degrees: f32,
+    slant_range_meters: f32,
+    beam_height_meters: f32,
+    value: MomentValue,
+}
+
+impl GateSample {
+    /// Create a new gate sample with the given properties.
+    pub fn new(
+        collection_timestamp: i64,
+        elevation_number: u8,
+        elevation_angle_degrees: f32,
+        azimuth_angle_degrees: f32,
+        slant_range_meters: f32,
+        beam_height_meters: f32,
+        value: MomentValue,
+    ) -> Self {
+        Self {
+            collection_timestamp,
+            elevation_number,
+            elevation_angle_degrees,
+            azimuth_angle_degrees,
+            slant_range_meters,
+            beam_height_meters,
+            value,
+        }
+    }
+
+    /// Collection time of the gate's radial in milliseconds since the epoch.
+    pub fn collection_timestamp(&self) -> i64 {
+        self.collection_timestamp
+    }
+
+    /// The elevation number of the gate's sweep in the volume scan.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// Elevation angle of the gate's radial in degrees.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// Azimuth angle of the gate's radial in degrees.
+    pub fn azimuth_angle_degrees(&self) -> f32 {
+        self.azimuth_angle_degrees
+    }
+
+    /// Slant range from the radar to the center of the gate in meters.
+    pub fn slant_range_meters(&self) -> f32 {
+        self.slant_range_meters
+    }
+
+    /// Height of the beam's center above the radar antenna at the gate in meters.
+    pub fn beam_height_meters(&self) -> f32 {
+        self.beam_height_meters
+    }
+
+    /// The data moment's value in the gate.
+    pub fn value(&self) -> MomentValue {
+        self.value
+    }
+}
+
+impl Debug for GateSample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GateSample")
+            .field("collection_timestamp", &self.collection_timestamp())
+            .field("elevation_number", &self.elevation_number())
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("azimuth_angle_degrees", &self.azimuth_angle_degrees())
+            .field("slant_range_meters", &self.slant_range_meters())
+            .field("beam_height_meters", &self.beam_height_meters())
+            .field("value", &self.value())
+            .finish()
+    }
+}
@@ -1,9 +1,16 @@
-use crate::data::Sweep;
+use crate::data::validation;
+use crate::data::{GateSample, MomentData, Product, Radial, ScanIssue, Sweep};
+use crate::meta::{ElevationCut, Site, VolumeCoveragePattern};
 use std::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// The largest difference in degrees between the elevation angles of sweeps scanned at the same
+/// elevation, such as a split cut's surveillance and Doppler sweeps. This is the tolerance to pass
+/// to [Scan::sweeps_by_elevation_angle] to group sweeps as the scan's other methods do.
+pub const ELEVATION_TOLERANCE_DEGREES: f32 = 0.25;
+
 /// A single radar scan composed of a series of sweeps. This represents a single volume scan which
 /// is composed of multiple sweeps at different elevations. The pattern of sweeps, including
 /// elevations and resolution, is determined by the scanning strategy of the radar. This is
@@ -13,6 +20,8 @@ use serde::{Deserialize, Serialize};
 pub struct Scan {
     coverage_pattern_number: u16,
     sweeps: Vec<Sweep>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    coverage_pattern: Option<VolumeCoveragePattern>,
 }
 
 impl Scan {
@@ -21,18 +30,312 @@ impl Scan {
         Self {
             coverage_pattern_number,
             sweeps,
+            coverage_pattern: None,
         }
     }
 
+    /// This scan with the given definition of its volume coverage pattern, whose elevation cuts
+    /// identify its supplemental sweeps.
+    pub fn with_coverage_pattern(mut self, coverage_pattern: VolumeCoveragePattern) -> Self {
+        self.coverage_pattern = Some(coverage_pattern);
+        self
+    }
+
     /// This scan's volume coverage pattern number.
     pub fn coverage_pattern_number(&self) -> u16 {
         self.coverage_pattern_number
     }
 
+    /// The definition of this scan's volume coverage pattern, if it was provided with the scan's
+    /// data.
+    pub fn coverage_pattern(&self) -> Option<&VolumeCoveragePattern> {
+        self.coverage_pattern.as_ref()
+    }
+
     /// The elevation sweeps comprising this scan.
     pub fn sweeps(&self) -> &Vec<Sweep> {
         self.sweeps.as_ref()
     }
+
+    /// This scan's sweeps grouped by elevation angle, with groups ordered by ascending elevation
+    /// angle and sweeps within each group in collection order. Sweeps whose mean elevation angles
+    /// are within the given tolerance in degrees of a group's first sweep share that group, so split
+    /// cuts and SAILS or MRLE repeats of an elevation are grouped together.
+    pub fn sweeps_by_elevation_angle(&self, tolerance_degrees: f32) -> Vec<Vec<&Sweep>> {
+        let mut sweeps: Vec<(f32, &Sweep)> = self
+            .sweeps
+            .iter()
+            .filter_map(|sweep| Some((sweep.elevation_angle_degrees()?, sweep)))
+            .collect();
+        sweeps.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let mut groups: Vec<(f32, Vec<&Sweep>)> = Vec::new();
+        for (angle, sweep) in sweeps {
+            match groups.last_mut() {
+                Some((group_angle, group)) if angle - *group_angle <= tolerance_degrees => {
+                    group.push(sweep);
+                }
+                _ => groups.push((angle, vec![sweep])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(_, mut group)| {
+                group.sort_by_key(|sweep| {
+                    sweep
+                        .radials()
+                        .first()
+                        .map(|radial| radial.collection_timestamp())
+                });
+                group
+            })
+            .collect()
+    }
+
+    /// The split cuts in this scan as pairs of surveillance and Doppler sweeps. A surveillance
+    /// sweep carries reflectivity without velocity, and is paired with the sweep following it if
+    /// that sweep carries velocity at the same elevation angle.
+    pub fn split_cuts(&self) -> Vec<(&Sweep, &Sweep)> {
+        self.sweeps
+            .windows(2)
+            .filter(|pair| is_split_cut(&pair[0], &pair[1]))
+            .map(|pair| (&pair[0], &pair[1]))
+            .collect()
+    }
+
+    /// This scan's sweeps with split cuts handled as specified. When merging, each split cut's
+    /// surveillance and Doppler sweeps are replaced by a single sweep whose radials carry all of
+    /// their moments aligned by azimuth, per [Sweep::merge_split_cut]. Otherwise the sweeps are
+    /// returned as scanned.
+    pub fn sweeps_with_split_cuts(&self, split_cuts: SplitCuts) -> Vec<Sweep> {
+        if split_cuts == SplitCuts::Separate {
+            return self.sweeps.clone();
+        }
+
+        let mut sweeps = Vec::new();
+        let mut index = 0;
+        while index < self.sweeps.len() {
+            let sweep = &self.sweeps[index];
+            match self.sweeps.get(index + 1) {
+                Some(doppler) if is_split_cut(sweep, doppler) => {
+                    sweeps.push(sweep.merge_split_cut(doppler));
+                    index += 2;
+                }
+                _ => {
+                    sweeps.push(sweep.clone());
+                    index += 1;
+                }
+            }
+        }
+
+        sweeps
+    }
+
+    /// Whether each of this scan's sweeps is supplemental, revisiting an elevation already scanned
+    /// earlier in the volume as SAILS, MESO-SAILS and MRLE do. With the scan's
+    /// [Scan::coverage_pattern], a sweep is supplemental when the SAILS or MRLE elevation cut its
+    /// elevation number identifies is. Without it, supplemental sweeps are inferred from the
+    /// scanned elevation angles alone: a sweep is supplemental when an earlier sweep was scanned at
+    /// the same angle, except that the Doppler sweep of a split cut is supplemental only when its
+    /// surveillance sweep is.
+    pub fn supplemental_flags(&self) -> Vec<bool> {
+        if let Some(coverage_pattern) = &self.coverage_pattern {
+            return self
+                .sweeps
+                .iter()
+                .map(|sweep| {
+                    coverage_pattern
+                        .elevation_cut(sweep.elevation_number())
+                        .is_some_and(ElevationCut::supplemental)
+                })
+                .collect();
+        }
+
+        let mut flags: Vec<bool> = Vec::with_capacity(self.sweeps.len());
+        for (index, sweep) in self.sweeps.iter().enumerate() {
+            if index > 0 && is_split_cut(&self.sweeps[index - 1], sweep) {
+                flags.push(flags[index - 1]);
+                continue;
+            }
+
+            let revisit = sweep.elevation_angle_degrees().is_some_and(|angle| {
+                self.sweeps[..index].iter().any(|earlier| {
+                    earlier
+                        .elevation_angle_degrees()
+                        .is_some_and(|earlier_angle| same_elevation(angle, earlier_angle))
+                })
+            });
+            flags.push(revisit);
+        }
+
+        flags
+    }
+
+    /// This scan's supplemental sweeps, which revisit an elevation already scanned earlier in the
+    /// volume. See [Scan::supplemental_flags].
+    pub fn supplemental_sweeps(&self) -> Vec<&Sweep> {
+        self.sweeps
+            .iter()
+            .zip(self.supplemental_flags())
+            .filter(|(_, supplemental)| *supplemental)
+            .map(|(sweep, _)| sweep)
+            .collect()
+    }
+
+    /// The time series of sweeps at this scan's lowest elevation angle, including the original
+    /// sweep and its SAILS, MESO-SAILS or MRLE revisits, ordered by start time. Split cuts are
+    /// handled as specified.
+    pub fn low_level_sweeps(&self, split_cuts: SplitCuts) -> Vec<Sweep> {
+        let lowest_angle = self
+            .sweeps
+            .iter()
+            .filter_map(|sweep| sweep.elevation_angle_degrees())
+            .min_by(|a, b| a.total_cmp(b));
+        let lowest_angle = match lowest_angle {
+            Some(angle) => angle,
+            None => return Vec::new(),
+        };
+
+        let mut sweeps: Vec<Sweep> = self
+            .sweeps_with_split_cuts(split_cuts)
+            .into_iter()
+            .filter(|sweep| {
+                sweep
+                    .elevation_angle_degrees()
+                    .is_some_and(|angle| same_elevation(angle, lowest_angle))
+            })
+            .collect();
+        sweeps.sort_by_key(|sweep| sweep.start_timestamp());
+
+        sweeps
+    }
+
+    /// Inspects this scan for problems such as missing or duplicate azimuths, incomplete sweeps,
+    /// out-of-order timestamps and an elevation sequence that does not follow the coverage
+    /// pattern. The coverage pattern's elevations are expected to be numbered consecutively and to
+    /// ascend in angle, except for supplemental revisits as identified by
+    /// [Scan::supplemental_flags]. With the scan's [Scan::coverage_pattern], each of its elevation
+    /// cuts is also expected to be scanned in order from the first, at the cut's angle.
+    pub fn validate(&self) -> Vec<ScanIssue> {
+        validation::validate_scan(self)
+    }
+
+    /// Samples the vertical column of gates above the given latitude and longitude from each of
+    /// this scan's sweeps with the data moment, selected from each radial by the provided function
+    /// such as [Radial::reflectivity]. Samples are ordered by ascending elevation angle. The scan's
+    /// radar is located by the given site.
+    pub fn column<F>(
+        &self,
+        site: &Site,
+        latitude: f32,
+        longitude: f32,
+        moment: F,
+    ) -> Vec<GateSample>
+    where
+        F: Fn(&Radial) -> Option<&MomentData>,
+    {
+        let mut samples: Vec<GateSample> = self
+            .sweeps
+            .iter()
+            .filter_map(|sweep| sweep.sample_at(site, latitude, longitude, &moment))
+            .collect();
+
+        samples.sort_by(|a, b| {
+            a.elevation_angle_degrees()
+                .total_cmp(&b.elevation_angle_degrees())
+        });
+
+        samples
+    }
+
+    /// Samples the gate above the given latitude and longitude in the sweep nearest the given
+    /// elevation angle in degrees with the data moment, selected from each radial by the provided
+    /// function such as [Radial::reflectivity]. The scan's radar is located by the given site.
+    pub fn sample_at<F>(
+        &self,
+        site: &Site,
+        latitude: f32,
+        longitude: f32,
+        elevation_angle_degrees: f32,
+        moment: F,
+    ) -> Option<GateSample>
+    where
+        F: Fn(&Radial) -> Option<&MomentData>,
+    {
+        let mut sweeps: Vec<(f32, &Sweep)> = self
+            .sweeps
+            .iter()
+            .filter_map(|sweep| {
+                let difference = (sweep.elevation_angle_degrees()? - elevation_angle_degrees).abs();
+                Some((difference, sweep))
+            })
+            .collect();
+        sweeps.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        // Split cuts repeat an elevation, so the nearest sweep with the moment is sampled.
+        sweeps
+            .into_iter()
+            .find_map(|(_, sweep)| sweep.sample_at(site, latitude, longitude, &moment))
+    }
+
+    /// Samples a time series of the gate above the given latitude and longitude from the sweep
+    /// nearest the given elevation angle in degrees in each of the scans, with the data moment
+    /// selected from each radial by the provided function such as [Radial::reflectivity]. Scans
+    /// without such a sample are skipped, and samples are ordered by collection time.
+    pub fn time_series<F>(
+        scans: &[Scan],
+        site: &Site,
+        latitude: f32,
+        longitude: f32,
+        elevation_angle_degrees: f32,
+        moment: F,
+    ) -> Vec<GateSample>
+    where
+        F: Fn(&Radial) -> Option<&MomentData>,
+    {
+        let mut samples: Vec<GateSample> = scans
+            .iter()
+            .filter_map(|scan| {
+                scan.sample_at(site, latitude, longitude, elevation_angle_degrees, &moment)
+            })
+            .collect();
+
+        samples.sort_by_key(|sample| sample.collection_timestamp());
+        samples
+    }
+}
+
+/// How a scan's split cuts, where an elevation is scanned by a surveillance sweep and then a
+/// Doppler sweep, are presented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplitCuts {
+    /// Merge each split cut's surveillance and Doppler sweeps into a single sweep.
+    Merge,
+    /// Keep each split cut's surveillance and Doppler sweeps separate.
+    Separate,
+}
+
+/// Whether the given elevation angles in degrees are the same elevation.
+fn same_elevation(a: f32, b: f32) -> bool {
+    (a - b).abs() <= ELEVATION_TOLERANCE_DEGREES
+}
+
+/// Whether the given sweeps are the surveillance and Doppler sweeps of a split cut.
+fn is_split_cut(surveillance: &Sweep, doppler: &Sweep) -> bool {
+    let angles = (
+        surveillance.elevation_angle_degrees(),
+        doppler.elevation_angle_degrees(),
+    );
+    let at_same_elevation = match angles {
+        (Some(a), Some(b)) => same_elevation(a, b),
+        _ => false,
+    };
+
+    at_same_elevation
+        && surveillance.has_product(&Product::Reflectivity)
+        && !surveillance.has_product(&Product::Velocity)
+        && doppler.has_product(&Product::Velocity)
 }
 
 impl Debug for Scan {
@@ -40,6 +343,264 @@ impl Debug for Scan {
         f.debug_struct("Scan")
             .field("coverage_pattern_number", &self.coverage_pattern_number())
             .field("sweeps", &self.sweeps())
+            .field("coverage_pattern", &self.coverage_pattern())
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MomentValue;
+    use crate::geometry::destination;
+    use crate::testing::{encode_velocity, site, uniform_sweep, velocity};
+
+    /// The latitude and longitude 20 km east of the test site.
+    fn east_location() -> (f32, f32) {
+        let site = site();
+        destination(site.latitude(), site.longitude(), 90.0, 20_000.0)
+    }
+
+    #[test]
+    fn samples_column_by_elevation() {
+        let scan = Scan::new(
+            212,
+            vec![
+                uniform_sweep(0, 2, 1.5, 1.0, 30.0),
+                uniform_sweep(0, 1, 0.5, 1.0, 20.0),
+                uniform_sweep(0, 3, 2.4, 1.0, 40.0),
+            ],
+        );
+
+        let (latitude, longitude) = east_location();
+        let column = scan.column(&site(), latitude, longitude, Radial::reflectivity);
+        let values: Vec<(u8, MomentValue)> = column
+            .iter()
+            .map(|sample| (sample.elevation_number(), sample.value()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (1, MomentValue::Value(20.0)),
+                (2, MomentValue::Value(30.0)),
+                (3, MomentValue::Value(40.0)),
+            ]
+        );
+        assert!(column[0].beam_height_meters() < column[2].beam_height_meters());
+    }
+
+    #[test]
+    fn samples_time_series_at_nearest_elevation() {
+        let scan = |start_timestamp: i64, dbz: f32| {
+            Scan::new(
+                212,
+                vec![
+                    uniform_sweep(start_timestamp, 1, 0.5, 1.0, 0.0),
+                    uniform_sweep(start_timestamp + 30_000, 2, 1.5, 1.0, dbz),
+                ],
+            )
+        };
+        let scans = [scan(600_000, 35.0), scan(300_000, 25.0)];
+
+        let (latitude, longitude) = east_location();
+        let series = Scan::time_series(
+            &scans,
+            &site(),
+            latitude,
+            longitude,
+            1.4,
+            Radial::reflectivity,
+        );
+        let values: Vec<MomentValue> = series.iter().map(GateSample::value).collect();
+        assert_eq!(
+            values,
+            vec![MomentValue::Value(25.0), MomentValue::Value(35.0)]
+        );
+        assert!(series[0].collection_timestamp() < series[1].collection_timestamp());
+        assert!(series.iter().all(|sample| sample.elevation_number() == 2));
+    }
+
+    /// A full rotation at the given elevation with 30 dBZ reflectivity, 10 m/s velocity, 2 m/s
+    /// spectrum width and a 28 m/s Nyquist velocity.
+    fn doppler_sweep(start_timestamp: i64, elevation_number: u8, elevation_angle: f32) -> Sweep {
+        let radials = uniform_sweep(
+            start_timestamp,
+            elevation_number,
+            elevation_angle,
+            1.0,
+            30.0,
+        )
+        .radials()
+        .iter()
+        .map(|radial| {
+            radial
+                .clone()
+                .with_moment(
+                    Product::Velocity,
+                    velocity(vec![encode_velocity(10.0); 100]),
+                )
+                .with_moment(
+                    Product::SpectrumWidth,
+                    velocity(vec![encode_velocity(2.0); 100]),
+                )
+                .with_nyquist_velocity_meters_per_second(Some(28.0))
+        })
+        .collect();
+
+        Sweep::new(elevation_number, radials)
+    }
+
+    /// A scan whose lowest elevation is a split cut, followed by a Doppler sweep at 1.5 degrees.
+    fn split_cut_scan() -> Scan {
+        Scan::new(
+            212,
+            vec![
+                uniform_sweep(0, 1, 0.48, 1.0, 20.0),
+                doppler_sweep(360_000, 2, 0.52),
+                doppler_sweep(720_000, 3, 1.45),
+            ],
+        )
+    }
+
+    #[test]
+    fn detects_split_cuts() {
+        let scan = split_cut_scan();
+
+        let split_cuts: Vec<(u8, u8)> = scan
+            .split_cuts()
+            .into_iter()
+            .map(|(surveillance, doppler)| {
+                (surveillance.elevation_number(), doppler.elevation_number())
+            })
+            .collect();
+        assert_eq!(split_cuts, vec![(1, 2)]);
+    }
+
+    #[test]
+    fn merges_split_cuts() {
+        let sweeps = split_cut_scan().sweeps_with_split_cuts(SplitCuts::Merge);
+
+        let elevation_numbers: Vec<u8> = sweeps.iter().map(Sweep::elevation_number).collect();
+        assert_eq!(elevation_numbers, vec![1, 3]);
+
+        let merged = &sweeps[0];
+        assert_eq!(merged.radials().len(), 360);
+        for radial in merged.radials() {
+            let value = |data: Option<&MomentData>| data.and_then(|data| data.value(0));
+            assert_eq!(value(radial.reflectivity()), Some(MomentValue::Value(20.0)));
+            assert_eq!(value(radial.velocity()), Some(MomentValue::Value(10.0)));
+            assert_eq!(
+                value(radial.spectrum_width()),
+                Some(MomentValue::Value(2.0))
+            );
+            assert_eq!(radial.nyquist_velocity_meters_per_second(), Some(28.0));
+        }
+    }
+
+    #[test]
+    fn keeps_split_cuts_separate() {
+        let scan = split_cut_scan();
+        assert_eq!(
+            &scan.sweeps_with_split_cuts(SplitCuts::Separate),
+            scan.sweeps()
+        );
+    }
+
+    /// A scan with a SAILS revisit of its lowest elevation, a split cut, after its 1.5 degree
+    /// sweep.
+    fn sails_scan() -> Scan {
+        Scan::new(
+            212,
+            vec![
+                uniform_sweep(0, 1, 0.48, 1.0, 20.0),
+                doppler_sweep(360_000, 2, 0.52),
+                doppler_sweep(720_000, 3, 1.45),
+                uniform_sweep(1_080_000, 4, 0.48, 1.0, 25.0),
+                doppler_sweep(1_440_000, 5, 0.52),
+                doppler_sweep(1_800_000, 6, 2.4),
+            ],
+        )
+    }
+
+    #[test]
+    fn flags_supplemental_sweeps() {
+        let scan = sails_scan();
+        assert_eq!(
+            scan.supplemental_flags(),
+            vec![false, false, false, true, true, false]
+        );
+
+        let supplemental: Vec<u8> = scan
+            .supplemental_sweeps()
+            .into_iter()
+            .map(Sweep::elevation_number)
+            .collect();
+        assert_eq!(supplemental, vec![4, 5]);
+    }
+
+    /// The coverage pattern scanned by [sails_scan], with its SAILS revisit of the lowest
+    /// elevation.
+    fn sails_pattern() -> VolumeCoveragePattern {
+        VolumeCoveragePattern::new(
+            212,
+            vec![
+                ElevationCut::new(0.48, None, None),
+                ElevationCut::new(0.52, None, None),
+                ElevationCut::new(1.45, None, None),
+                ElevationCut::new(0.48, Some(1), None),
+                ElevationCut::new(0.52, Some(1), None),
+                ElevationCut::new(2.4, None, None),
+            ],
+        )
+    }
+
+    #[test]
+    fn flags_supplemental_sweeps_from_coverage_pattern() {
+        let scan = sails_scan().with_coverage_pattern(sails_pattern());
+        assert_eq!(
+            scan.supplemental_flags(),
+            vec![false, false, false, true, true, false]
+        );
+
+        // Without the volume's first sweeps, the SAILS revisit is not seen to repeat an angle
+        let partial = Scan::new(212, sails_scan().sweeps()[2..].to_vec());
+        assert_eq!(
+            partial.supplemental_flags(),
+            vec![false, false, false, false]
+        );
+        assert_eq!(
+            partial
+                .with_coverage_pattern(sails_pattern())
+                .supplemental_flags(),
+            vec![false, true, true, false]
+        );
+    }
+
+    #[test]
+    fn flags_no_supplemental_sweeps_without_revisits() {
+        assert_eq!(
+            split_cut_scan().supplemental_flags(),
+            vec![false, false, false]
+        );
+    }
+
+    #[test]
+    fn selects_low_level_sweeps() {
+        let scan = sails_scan();
+        let elevation_numbers = |sweeps: Vec<Sweep>| -> Vec<u8> {
+            sweeps.iter().map(Sweep::elevation_number).collect()
+        };
+
+        assert_eq!(
+            elevation_numbers(scan.low_level_sweeps(SplitCuts::Separate)),
+            vec![1, 2, 4, 5]
+        );
+        assert_eq!(
+            elevation_numbers(scan.low_level_sweeps(SplitCuts::Merge)),
+            vec![1, 4]
+        );
+        assert!(Scan::new(212, Vec::new())
+            .low_level_sweeps(SplitCuts::Merge)
+            .is_empty());
+    }
+}
@@ -1,7 +1,14 @@
-use crate::data::Radial;
+use crate::data::{GateSample, MomentData, MomentValue, Product, Radial, RadialStatus};
+use crate::geometry::{
+    azimuth_difference_degrees, beam_height_meters, bearing_and_distance, slant_range_meters,
+};
+use crate::meta::Site;
 use crate::result::{Error, Result};
 use std::fmt::{Debug, Display};
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -26,23 +33,63 @@ impl Sweep {
         }
     }
 
-    /// Create a new radar sweep from a list of radials by splitting them by elevation.
+    /// Create new radar sweeps from a volume's radials in collection order. A sweep begins at each
+    /// radial marked as an elevation or volume start and ends at each radial marked as an elevation
+    /// or volume end. When none of the radials carry these markers, sweeps are instead split where
+    /// the elevation number changes or the cut sector number restarts. Every sweep is kept,
+    /// including the last one in the volume and repeated elevations such as split cuts and SAILS or
+    /// MRLE cuts, which are distinguished by their elevation numbers.
     pub fn from_radials(radials: Vec<Radial>) -> Vec<Self> {
-        let mut sweeps = Vec::new();
+        let marked = radials
+            .iter()
+            .any(|radial| radial.radial_status() != RadialStatus::IntermediateRadialData);
 
-        let mut sweep_elevation_number = None;
-        let mut sweep_radials = Vec::new();
+        let mut sweeps = Vec::new();
+        let mut sweep_radials: Vec<Radial> = Vec::new();
 
         for radial in radials {
-            if let Some(elevation_number) = sweep_elevation_number {
-                if elevation_number != radial.elevation_number() {
-                    sweeps.push(Sweep::new(elevation_number, sweep_radials));
-                    sweep_radials = Vec::new();
+            if let Some(previous) = sweep_radials.last() {
+                let starts_sweep = if marked {
+                    matches!(
+                        radial.radial_status(),
+                        RadialStatus::ElevationStart
+                            | RadialStatus::VolumeScanStart
+                            | RadialStatus::ElevationStartVCPFinal
+                    )
+                } else {
+                    let sector_restarted = radial.cut_sector_number() != 0
+                        && radial.cut_sector_number() < previous.cut_sector_number();
+                    sector_restarted || radial.elevation_number() != previous.elevation_number()
+                };
+
+                if starts_sweep {
+                    let elevation_number = previous.elevation_number();
+                    sweeps.push(Sweep::new(
+                        elevation_number,
+                        std::mem::take(&mut sweep_radials),
+                    ));
                 }
             }
 
-            sweep_elevation_number = Some(radial.elevation_number());
+            let ends_sweep = matches!(
+                radial.radial_status(),
+                RadialStatus::ElevationEnd | RadialStatus::VolumeScanEnd
+            );
+
             sweep_radials.push(radial);
+
+            if ends_sweep {
+                let elevation_number = sweep_radials[0].elevation_number();
+                sweeps.push(Sweep::new(
+                    elevation_number,
+                    std::mem::take(&mut sweep_radials),
+                ));
+            }
+        }
+
+        if let Some(first) = sweep_radials.first() {
+            let elevation_number = first.elevation_number();
+            sweeps.push(Sweep::new(elevation_number, sweep_radials));
         }
 
         sweeps
@@ -59,6 +106,242 @@ impl Sweep {
         self.radials.as_ref()
     }
 
+    /// The earliest collection timestamp of this sweep's radials in milliseconds since the epoch,
+    /// if it has any.
+    pub fn start_timestamp(&self) -> Option<i64> {
+        self.radials
+            .iter()
+            .map(|radial| radial.collection_timestamp())
+            .min()
+    }
+
+    /// The latest collection timestamp of this sweep's radials in milliseconds since the epoch,
+    /// if it has any.
+    pub fn end_timestamp(&self) -> Option<i64> {
+        self.radials
+            .iter()
+            .map(|radial| radial.collection_timestamp())
+            .max()
+    }
+
+    /// The time this sweep's collection started, if it has any radials.
+    #[cfg(feature = "chrono")]
+    pub fn start_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(self.start_timestamp()?)
+    }
+
+    /// The time this sweep's collection ended, if it has any radials.
+    #[cfg(feature = "chrono")]
+    pub fn end_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(self.end_timestamp()?)
+    }
+
+    /// The mean elevation angle in degrees of this sweep's radials, if it has any.
+    pub fn elevation_angle_degrees(&self) -> Option<f32> {
+        if self.radials.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.radials
+                .iter()
+                .map(|radial| radial.elevation_angle_degrees())
+                .sum::<f32>()
+                / self.radials.len() as f32,
+        )
+    }
+
+    /// The radial whose azimuth is nearest the given azimuth in degrees, if it is within the
+    /// radial's azimuth spacing.
+    pub fn radial_at(&self, azimuth_degrees: f32) -> Option<&Radial> {
+        self.radials
+            .iter()
+            .map(|radial| {
+                (
+                    azimuth_difference_degrees(radial.azimuth_angle_degrees(), azimuth_degrees),
+                    radial,
+                )
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .filter(|(difference, radial)| *difference <= radial.azimuth_spacing_degrees())
+            .map(|(_, radial)| radial)
+    }
+
+    /// Samples the gate nearest the given azimuth in degrees and slant range in meters for a data
+    /// moment, selected from the radial by the provided function such as [Radial::reflectivity].
+    pub fn sample<F>(
+        &self,
+        azimuth_degrees: f32,
+        slant_range_meters: f32,
+        moment: F,
+    ) -> Option<GateSample>
+    where
+        F: Fn(&Radial) -> Option<&MomentData>,
+    {
+        let radial = self.radial_at(azimuth_degrees)?;
+        let data = moment(radial)?;
+        let gate_index = data.gate_index_meters(slant_range_meters)?;
+        let gate_range = data.gate_range_meters(gate_index);
+
+        Some(GateSample::new(
+            radial.collection_timestamp(),
+            radial.elevation_number(),
+            radial.elevation_angle_degrees(),
+            radial.azimuth_angle_degrees(),
+            gate_range,
+            beam_height_meters(gate_range, radial.elevation_angle_degrees()),
+            data.value(gate_index)?,
+        ))
+    }
+
+    /// Samples the gate nearest the beam above the given latitude and longitude for a data moment,
+    /// selected from the radial by the provided function such as [Radial::reflectivity]. The
+    /// sweep's radar is located by the given site.
+    pub fn sample_at<F>(
+        &self,
+        site: &Site,
+        latitude: f32,
+        longitude: f32,
+        moment: F,
+    ) -> Option<GateSample>
+    where
+        F: Fn(&Radial) -> Option<&MomentData>,
+    {
+        let (azimuth, ground_range) =
+            bearing_and_distance(site.latitude(), site.longitude(), latitude, longitude);
+        let radial = self.radial_at(azimuth)?;
+        let range = slant_range_meters(ground_range, radial.elevation_angle_degrees())?;
+
+        self.sample(azimuth, range, moment)
+    }
+
+    /// The value of a data moment, selected from each radial by the provided function such as
+    /// [Radial::reflectivity], in the beam above the given latitude and longitude. The value is
+    /// interpolated bilinearly in azimuth and range between the surrounding gates, ignoring gates
+    /// without a valid value. The sweep's radar is located by the given site.
+    pub fn interpolate_at<F>(
+        &self,
+        site: &Site,
+        latitude: f32,
+        longitude: f32,
+        moment: F,
+    ) -> Option<f32>
+    where
+        F: Fn(&Radial) -> Option<&MomentData>,
+    {
+        let (azimuth, ground_range) =
+            bearing_and_distance(site.latitude(), site.longitude(), latitude, longitude);
+
+        // The nearest radials counterclockwise and clockwise of the azimuth, with their distance.
+        let mut counterclockwise: Option<(f32, &Radial)> = None;
+        let mut clockwise: Option<(f32, &Radial)> = None;
+        for radial in &self.radials {
+            let offset =
+                (radial.azimuth_angle_degrees() - azimuth + 180.0).rem_euclid(360.0) - 180.0;
+            if offset.abs() > radial.azimuth_spacing_degrees() {
+                continue;
+            }
+
+            let nearest = if offset <= 0.0 {
+                &mut counterclockwise
+            } else {
+                &mut clockwise
+            };
+            if nearest.is_none_or(|(distance, _)| offset.abs() < distance) {
+                *nearest = Some((offset.abs(), radial));
+            }
+        }
+
+        let mut total = 0.0;
+        let mut weight = 0.0;
+        let bracketing: Vec<(f32, &Radial)> = [counterclockwise, clockwise]
+            .into_iter()
+            .flatten()
+            .collect();
+        let spacing: f32 = bracketing.iter().map(|(distance, _)| distance).sum();
+        for &(distance, radial) in &bracketing {
+            // A lone radial, such as at a sector edge or beside a missing radial, is used as is.
+            let radial_weight = if bracketing.len() > 1 && spacing > 0.0 {
+                1.0 - distance / spacing
+            } else {
+                1.0
+            };
+
+            let data = match moment(radial) {
+                Some(data) => data,
+                None => continue,
+            };
+            let range = match slant_range_meters(ground_range, radial.elevation_angle_degrees()) {
+                Some(range) => range,
+                None => continue,
+            };
+
+            let interval = data.gate_interval_meters() as f32;
+            if interval <= 0.0 || data.gate_count() == 0 {
+                continue;
+            }
+
+            let position = (range - data.first_gate_range_meters() as f32) / interval;
+            if position < -0.5 || position > data.gate_count() as f32 - 0.5 {
+                continue;
+            }
+
+            let position = position.clamp(0.0, (data.gate_count() - 1) as f32);
+            let nearer = position.floor();
+            for (gate_index, gate_weight) in [
+                (nearer as usize, 1.0 - (position - nearer)),
+                (nearer as usize + 1, position - nearer),
+            ] {
+                if let Some(MomentValue::Value(value)) = data.value(gate_index) {
+                    total += radial_weight * gate_weight * value;
+                    weight += radial_weight * gate_weight;
+                }
+            }
+        }
+
+        (weight > 0.0).then(|| total / weight)
+    }
+
+    /// Whether any of this sweep's radials carry data for the given product.
+    pub fn has_product(&self, product: &Product) -> bool {
+        self.radials
+            .iter()
+            .any(|radial| radial.moment(product).is_some())
+    }
+
+    /// Merges this surveillance cut with the Doppler cut scanned at the same elevation in a split
+    /// cut. Each of this sweep's radials is given the moments it lacks, such as velocity and
+    /// spectrum width, from the Doppler radial nearest its azimuth, along with that radial's
+    /// Nyquist velocity. Unlike [Sweep::merge], the cuts' elevation numbers may differ, and the
+    /// merged sweep keeps this sweep's elevation number and radials.
+    pub fn merge_split_cut(&self, doppler: &Sweep) -> Self {
+        let radials = self
+            .radials
+            .iter()
+            .map(|radial| {
+                let doppler_radial = match doppler.radial_at(radial.azimuth_angle_degrees()) {
+                    Some(doppler_radial) => doppler_radial,
+                    None => return radial.clone(),
+                };
+
+                let mut merged = radial.clone().with_nyquist_velocity_meters_per_second(
+                    doppler_radial.nyquist_velocity_meters_per_second(),
+                );
+                for (product, data) in doppler_radial.moments() {
+                    if merged.moment(&product).is_none() {
+                        merged.set_moment(product, data.clone());
+                    }
+                }
+                merged
+            })
+            .collect();
+
+        Self {
+            elevation_number: self.elevation_number,
+            radials,
+        }
+    }
+
     /// Merges this sweep with another sweep, combining their radials into a single sweep. The
     /// sweeps must be at the same elevation, and they should not have duplicate azimuth radials.
     pub fn merge(self, other: Self) -> Result<Self> {
@@ -102,3 +385,215 @@ impl Debug for Sweep {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{destination, ground_range_meters};
+    use crate::testing::{encode_reflectivity, radial, reflectivity, site};
+
+    /// A 1 degree sweep at 0.5 degrees elevation whose reflectivity increases by 1 dBZ per radial,
+    /// repeating every 40 radials, and by 0.5 dBZ per gate.
+    fn gradient_sweep() -> Sweep {
+        let radials = (0..360)
+            .map(|azimuth_index| {
+                let values = (0..100)
+                    .map(|gate| {
+                        encode_reflectivity((azimuth_index % 40) as f32 + gate as f32 * 0.5)
+                    })
+                    .collect();
+                radial(0, azimuth_index, 1.0, 1, 0.5)
+                    .with_moment(Product::Reflectivity, reflectivity(values))
+            })
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    /// The latitude and longitude of the point beneath the beam at the given azimuth and slant
+    /// range at 0.5 degrees elevation.
+    fn location(azimuth_degrees: f32, slant_range_meters: f32) -> (f32, f32) {
+        let site = site();
+        destination(
+            site.latitude(),
+            site.longitude(),
+            azimuth_degrees,
+            ground_range_meters(slant_range_meters, 0.5),
+        )
+    }
+
+    #[test]
+    fn samples_nearest_gate() {
+        let sweep = gradient_sweep();
+
+        let sample = sweep.sample(45.2, 4_500.0, Radial::reflectivity).unwrap();
+        assert_eq!(sample.azimuth_angle_degrees(), 45.5);
+        assert_eq!(sample.slant_range_meters(), 4_500.0);
+        assert_eq!(sample.value(), MomentValue::Value(10.0));
+
+        assert!(sweep
+            .sample(45.2, 100_000.0, Radial::reflectivity)
+            .is_none());
+        assert!(sweep.sample(45.2, 4_500.0, Radial::velocity).is_none());
+    }
+
+    #[test]
+    fn samples_at_location() {
+        let sweep = gradient_sweep();
+        let (latitude, longitude) = location(45.5, 4_500.0);
+
+        let sample = sweep
+            .sample_at(&site(), latitude, longitude, Radial::reflectivity)
+            .unwrap();
+        assert_eq!(sample.azimuth_angle_degrees(), 45.5);
+        assert_eq!(sample.value(), MomentValue::Value(10.0));
+    }
+
+    #[test]
+    fn interpolates_between_radials_and_gates() {
+        let sweep = gradient_sweep();
+
+        // Midway between radials 45 and 46 and between gates 10 and 11.
+        let (latitude, longitude) = location(46.0, 4_625.0);
+        let value = sweep
+            .interpolate_at(&site(), latitude, longitude, Radial::reflectivity)
+            .unwrap();
+        assert!((value - 10.75).abs() < 0.05, "value {value}");
+    }
+
+    #[test]
+    fn interpolates_beside_missing_radial() {
+        let mut sweep = gradient_sweep();
+        sweep.radials.retain(|radial| radial.azimuth_number() != 47);
+
+        // Only radial 45 is within its spacing of this azimuth.
+        let (latitude, longitude) = location(45.75, 4_500.0);
+        let value = sweep
+            .interpolate_at(&site(), latitude, longitude, Radial::reflectivity)
+            .unwrap();
+        assert!((value - 10.0).abs() < 0.05, "value {value}");
+
+        let sample = sweep
+            .sample_at(&site(), latitude, longitude, Radial::reflectivity)
+            .unwrap();
+        assert_eq!(sample.value(), MomentValue::Value(10.0));
+    }
+
+    /// A rotation of one degree radials at the given elevation and cut sector, with the first and
+    /// last radials marked with the given statuses.
+    fn rotation(
+        elevation_number: u8,
+        cut_sector_number: u8,
+        start: RadialStatus,
+        end: RadialStatus,
+    ) -> Vec<Radial> {
+        (0..360)
+            .map(|azimuth_index| {
+                let status = match azimuth_index {
+                    0 => start,
+                    359 => end,
+                    _ => RadialStatus::IntermediateRadialData,
+                };
+                Radial::new(
+                    azimuth_index as i64 * 1_000,
+                    azimuth_index + 1,
+                    azimuth_index as f32 + 0.5,
+                    1.0,
+                    status,
+                    elevation_number,
+                    elevation_number as f32 * 0.5,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .with_cut_sector_number(cut_sector_number)
+            })
+            .collect()
+    }
+
+    fn sweep_summary(sweeps: &[Sweep]) -> Vec<(u8, usize)> {
+        sweeps
+            .iter()
+            .map(|sweep| (sweep.elevation_number(), sweep.radials().len()))
+            .collect()
+    }
+
+    #[test]
+    fn splits_sweeps_at_markers() {
+        let mut radials = rotation(
+            1,
+            1,
+            RadialStatus::VolumeScanStart,
+            RadialStatus::ElevationEnd,
+        );
+        radials.extend(rotation(
+            2,
+            1,
+            RadialStatus::ElevationStart,
+            RadialStatus::ElevationEnd,
+        ));
+        radials.extend(rotation(
+            3,
+            1,
+            RadialStatus::ElevationStartVCPFinal,
+            RadialStatus::VolumeScanEnd,
+        ));
+
+        let sweeps = Sweep::from_radials(radials);
+        assert_eq!(sweep_summary(&sweeps), vec![(1, 360), (2, 360), (3, 360)]);
+    }
+
+    #[test]
+    fn keeps_last_elevation_without_end_marker() {
+        let mut radials = rotation(
+            1,
+            1,
+            RadialStatus::VolumeScanStart,
+            RadialStatus::ElevationEnd,
+        );
+        radials.extend(rotation(
+            2,
+            1,
+            RadialStatus::ElevationStartVCPFinal,
+            RadialStatus::IntermediateRadialData,
+        ));
+
+        let sweeps = Sweep::from_radials(radials);
+        assert_eq!(sweep_summary(&sweeps), vec![(1, 360), (2, 360)]);
+    }
+
+    #[test]
+    fn ignores_sector_changes_within_marked_sweep() {
+        let mut radials = rotation(
+            1,
+            2,
+            RadialStatus::VolumeScanStart,
+            RadialStatus::IntermediateRadialData,
+        );
+        radials.extend(rotation(
+            1,
+            1,
+            RadialStatus::IntermediateRadialData,
+            RadialStatus::VolumeScanEnd,
+        ));
+
+        let sweeps = Sweep::from_radials(radials);
+        assert_eq!(sweep_summary(&sweeps), vec![(1, 720)]);
+    }
+
+    #[test]
+    fn splits_unmarked_radials_on_elevation_and_sector() {
+        let intermediate = RadialStatus::IntermediateRadialData;
+        let mut radials = rotation(1, 1, intermediate, intermediate);
+        radials.extend(rotation(1, 2, intermediate, intermediate));
+        radials.extend(rotation(1, 1, intermediate, intermediate));
+        radials.extend(rotation(2, 0, intermediate, intermediate));
+
+        let sweeps = Sweep::from_radials(radials);
+        assert_eq!(sweep_summary(&sweeps), vec![(1, 720), (1, 360), (2, 360)]);
+    }
+}
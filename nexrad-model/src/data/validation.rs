@@ -0,0 +1,643 @@
+use crate::data::{Scan, Sweep, ELEVATION_TOLERANCE_DEGREES};
+use crate::meta::VolumeCoveragePattern;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Sweeps covering less than this fraction of a full rotation are considered incomplete.
+const MIN_ROTATION_FRACTION: f32 = 0.9;
+
+/// How severe a problem found while validating radar data is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Severity {
+    /// Likely to degrade algorithms using the data.
+    Warning,
+    /// The data is incomplete or inconsistent and should not be trusted.
+    Error,
+}
+
+/// A problem found while validating a scan.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanIssue {
+    severity: Severity,
+    kind: ScanIssueKind,
+}
+
+impl ScanIssue {
+    /// Create a new scan issue of the given kind, with its default severity.
+    pub fn new(kind: ScanIssueKind) -> Self {
+        Self {
+            severity: kind.severity(),
+            kind,
+        }
+    }
+
+    /// How severe this issue is.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// What this issue is and where it was found.
+    pub fn kind(&self) -> &ScanIssueKind {
+        &self.kind
+    }
+}
+
+impl Display for ScanIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.kind)
+    }
+}
+
+/// The kinds of problems found while validating a scan. Sweeps are identified by their index in
+/// [Scan::sweeps] and radials by their index in [Sweep::radials].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ScanIssueKind {
+    /// The scan has no sweeps.
+    NoSweeps,
+    /// A sweep has no radials.
+    EmptySweep { sweep_index: usize },
+    /// A sweep's radials cover less than a full rotation.
+    IncompleteRotation {
+        sweep_index: usize,
+        covered_degrees: f32,
+    },
+    /// A sweep is missing radials at the given azimuth numbers.
+    MissingAzimuths {
+        sweep_index: usize,
+        azimuth_numbers: Vec<u16>,
+    },
+    /// A sweep has more than one radial at the given azimuth number.
+    DuplicateAzimuth {
+        sweep_index: usize,
+        azimuth_number: u16,
+    },
+    /// A radial was collected before the radial preceding it in its sweep.
+    NonMonotonicRadialTimestamp {
+        sweep_index: usize,
+        radial_index: usize,
+    },
+    /// A sweep started before the sweep preceding it in the scan ended.
+    NonMonotonicSweepTimestamp { sweep_index: usize },
+    /// A sweep's elevation number does not follow the preceding sweep's in the coverage pattern.
+    UnexpectedElevationNumber {
+        sweep_index: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// A sweep's elevation angle is below the preceding sweep's, without being a supplemental
+    /// revisit of an earlier elevation.
+    DescendingElevationAngle {
+        sweep_index: usize,
+        elevation_angle_degrees: f32,
+    },
+    /// The coverage pattern's elevation cut with the given elevation number has no sweep.
+    MissingElevationCut {
+        elevation_number: u8,
+        elevation_angle_degrees: f32,
+    },
+    /// A sweep's elevation angle differs from that of its elevation cut in the coverage pattern.
+    ElevationAngleMismatch {
+        sweep_index: usize,
+        expected_degrees: f32,
+        found_degrees: f32,
+    },
+    /// A sweep's elevation number identifies no elevation cut in the coverage pattern.
+    ElevationNumberOutsidePattern {
+        sweep_index: usize,
+        elevation_number: u8,
+    },
+}
+
+impl ScanIssueKind {
+    /// The default severity of this kind of issue.
+    pub fn severity(&self) -> Severity {
+        match self {
+            ScanIssueKind::NoSweeps
+            | ScanIssueKind::EmptySweep { .. }
+            | ScanIssueKind::IncompleteRotation { .. }
+            | ScanIssueKind::MissingElevationCut { .. } => Severity::Error,
+            ScanIssueKind::MissingAzimuths { .. }
+            | ScanIssueKind::DuplicateAzimuth { .. }
+            | ScanIssueKind::NonMonotonicRadialTimestamp { .. }
+            | ScanIssueKind::NonMonotonicSweepTimestamp { .. }
+            | ScanIssueKind::UnexpectedElevationNumber { .. }
+            | ScanIssueKind::DescendingElevationAngle { .. }
+            | ScanIssueKind::ElevationAngleMismatch { .. }
+            | ScanIssueKind::ElevationNumberOutsidePattern { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl Display for ScanIssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanIssueKind::NoSweeps => write!(f, "scan has no sweeps"),
+            ScanIssueKind::EmptySweep { sweep_index } => {
+                write!(f, "sweep {sweep_index} has no radials")
+            }
+            ScanIssueKind::IncompleteRotation {
+                sweep_index,
+                covered_degrees,
+            } => write!(
+                f,
+                "sweep {sweep_index} covers only {covered_degrees:.1} degrees"
+            ),
+            ScanIssueKind::MissingAzimuths {
+                sweep_index,
+                azimuth_numbers,
+            } => write!(
+                f,
+                "sweep {sweep_index} is missing {} azimuths",
+                azimuth_numbers.len()
+            ),
+            ScanIssueKind::DuplicateAzimuth {
+                sweep_index,
+                azimuth_number,
+            } => write!(
+                f,
+                "sweep {sweep_index} repeats azimuth number {azimuth_number}"
+            ),
+            ScanIssueKind::NonMonotonicRadialTimestamp {
+                sweep_index,
+                radial_index,
+            } => write!(
+                f,
+                "radial {radial_index} of sweep {sweep_index} was collected before its predecessor"
+            ),
+            ScanIssueKind::NonMonotonicSweepTimestamp { sweep_index } => write!(
+                f,
+                "sweep {sweep_index} started before its predecessor ended"
+            ),
+            ScanIssueKind::UnexpectedElevationNumber {
+                sweep_index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "sweep {sweep_index} has elevation number {found}, expected {expected}"
+            ),
+            ScanIssueKind::DescendingElevationAngle {
+                sweep_index,
+                elevation_angle_degrees,
+            } => write!(
+                f,
+                "sweep {sweep_index} descends to {elevation_angle_degrees:.2} degrees"
+            ),
+            ScanIssueKind::MissingElevationCut {
+                elevation_number,
+                elevation_angle_degrees,
+            } => write!(
+                f,
+                "elevation cut {elevation_number} at {elevation_angle_degrees:.2} degrees has no sweep"
+            ),
+            ScanIssueKind::ElevationAngleMismatch {
+                sweep_index,
+                expected_degrees,
+                found_degrees,
+            } => write!(
+                f,
+                "sweep {sweep_index} is at {found_degrees:.2} degrees, expected {expected_degrees:.2}"
+            ),
+            ScanIssueKind::ElevationNumberOutsidePattern {
+                sweep_index,
+                elevation_number,
+            } => write!(
+                f,
+                "sweep {sweep_index} has elevation number {elevation_number}, outside the coverage pattern"
+            ),
+        }
+    }
+}
+
+/// Inspects a scan for problems. See [Scan::validate].
+pub(crate) fn validate_scan(scan: &Scan) -> Vec<ScanIssue> {
+    let mut issues = Vec::new();
+    if scan.sweeps().is_empty() {
+        issues.push(ScanIssue::new(ScanIssueKind::NoSweeps));
+        return issues;
+    }
+
+    for (sweep_index, sweep) in scan.sweeps().iter().enumerate() {
+        validate_sweep(sweep_index, sweep, &mut issues);
+    }
+
+    let supplemental = scan.supplemental_flags();
+    let mut highest_angle: Option<f32> = None;
+    for (sweep_index, pair) in scan.sweeps().windows(2).enumerate() {
+        let (previous, sweep) = (&pair[0], &pair[1]);
+        let sweep_index = sweep_index + 1;
+
+        if let (Some(end), Some(start)) = (previous.end_timestamp(), sweep.start_timestamp()) {
+            if start < end {
+                issues.push(ScanIssue::new(ScanIssueKind::NonMonotonicSweepTimestamp {
+                    sweep_index,
+                }));
+            }
+        }
+
+        let expected = previous.elevation_number().wrapping_add(1);
+        if sweep.elevation_number() != expected {
+            issues.push(ScanIssue::new(ScanIssueKind::UnexpectedElevationNumber {
+                sweep_index,
+                expected,
+                found: sweep.elevation_number(),
+            }));
+        }
+
+        if let Some(angle) = previous.elevation_angle_degrees() {
+            if !supplemental[sweep_index - 1] {
+                highest_angle = Some(highest_angle.map_or(angle, |highest| highest.max(angle)));
+            }
+        }
+        if let (Some(highest), Some(angle)) = (highest_angle, sweep.elevation_angle_degrees()) {
+            if !supplemental[sweep_index] && angle < highest - ELEVATION_TOLERANCE_DEGREES {
+                issues.push(ScanIssue::new(ScanIssueKind::DescendingElevationAngle {
+                    sweep_index,
+                    elevation_angle_degrees: angle,
+                }));
+            }
+        }
+    }
+
+    if let Some(coverage_pattern) = scan.coverage_pattern() {
+        validate_elevation_cuts(scan, coverage_pattern, &mut issues);
+    }
+
+    issues
+}
+
+/// Inspects a scan's sweeps against its coverage pattern's elevation cuts, which are expected to
+/// each be scanned in order from the first, at the cut's elevation angle.
+fn validate_elevation_cuts(
+    scan: &Scan,
+    coverage_pattern: &VolumeCoveragePattern,
+    issues: &mut Vec<ScanIssue>,
+) {
+    if let Some(first) = scan.sweeps().first() {
+        if first.elevation_number() != 1 {
+            issues.push(ScanIssue::new(ScanIssueKind::UnexpectedElevationNumber {
+                sweep_index: 0,
+                expected: 1,
+                found: first.elevation_number(),
+            }));
+        }
+    }
+
+    for (sweep_index, sweep) in scan.sweeps().iter().enumerate() {
+        let elevation_cut = match coverage_pattern.elevation_cut(sweep.elevation_number()) {
+            Some(elevation_cut) => elevation_cut,
+            None => {
+                issues.push(ScanIssue::new(
+                    ScanIssueKind::ElevationNumberOutsidePattern {
+                        sweep_index,
+                        elevation_number: sweep.elevation_number(),
+                    },
+                ));
+                continue;
+            }
+        };
+
+        let expected_degrees = elevation_cut.elevation_angle_degrees();
+        if let Some(found_degrees) = sweep.elevation_angle_degrees() {
+            if (found_degrees - expected_degrees).abs() > ELEVATION_TOLERANCE_DEGREES {
+                issues.push(ScanIssue::new(ScanIssueKind::ElevationAngleMismatch {
+                    sweep_index,
+                    expected_degrees,
+                    found_degrees,
+                }));
+            }
+        }
+    }
+
+    for (index, elevation_cut) in coverage_pattern.elevation_cuts().iter().enumerate() {
+        let elevation_number = (index + 1) as u8;
+        let scanned = scan
+            .sweeps()
+            .iter()
+            .any(|sweep| sweep.elevation_number() == elevation_number);
+        if !scanned {
+            issues.push(ScanIssue::new(ScanIssueKind::MissingElevationCut {
+                elevation_number,
+                elevation_angle_degrees: elevation_cut.elevation_angle_degrees(),
+            }));
+        }
+    }
+}
+
+/// Inspects a sweep's radials for missing and duplicate azimuths, incomplete rotation and
+/// out-of-order timestamps.
+fn validate_sweep(sweep_index: usize, sweep: &Sweep, issues: &mut Vec<ScanIssue>) {
+    let radials = sweep.radials();
+    let spacing = match radials.first() {
+        Some(radial) => radial.azimuth_spacing_degrees(),
+        None => {
+            issues.push(ScanIssue::new(ScanIssueKind::EmptySweep { sweep_index }));
+            return;
+        }
+    };
+
+    let mut azimuth_numbers = HashSet::new();
+    for radial in radials {
+        if !azimuth_numbers.insert(radial.azimuth_number()) {
+            issues.push(ScanIssue::new(ScanIssueKind::DuplicateAzimuth {
+                sweep_index,
+                azimuth_number: radial.azimuth_number(),
+            }));
+        }
+    }
+
+    if spacing > 0.0 {
+        let expected_count = (360.0 / spacing).round() as u16;
+        let covered_degrees = azimuth_numbers.len() as f32 * spacing;
+        if covered_degrees < 360.0 * MIN_ROTATION_FRACTION {
+            issues.push(ScanIssue::new(ScanIssueKind::IncompleteRotation {
+                sweep_index,
+                covered_degrees,
+            }));
+        }
+
+        let missing: Vec<u16> = (1..=expected_count)
+            .filter(|azimuth_number| !azimuth_numbers.contains(azimuth_number))
+            .collect();
+        if !missing.is_empty() {
+            issues.push(ScanIssue::new(ScanIssueKind::MissingAzimuths {
+                sweep_index,
+                azimuth_numbers: missing,
+            }));
+        }
+    }
+
+    for (radial_index, pair) in radials.windows(2).enumerate() {
+        if pair[1].collection_timestamp() < pair[0].collection_timestamp() {
+            issues.push(ScanIssue::new(ScanIssueKind::NonMonotonicRadialTimestamp {
+                sweep_index,
+                radial_index: radial_index + 1,
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Radial;
+    use crate::meta::ElevationCut;
+    use crate::testing::{radial, uniform_sweep};
+
+    /// A full rotation of one degree radials at the given elevation, starting at the given time.
+    fn full_sweep(
+        start_timestamp: i64,
+        elevation_number: u8,
+        elevation_angle_degrees: f32,
+    ) -> Sweep {
+        uniform_sweep(
+            start_timestamp,
+            elevation_number,
+            elevation_angle_degrees,
+            1.0,
+            20.0,
+        )
+    }
+
+    /// A sweep of the given radials at the first elevation.
+    fn sweep_of(radials: Vec<Radial>) -> Scan {
+        Scan::new(212, vec![Sweep::new(1, radials)])
+    }
+
+    /// The radials of a full one degree rotation at the first elevation, except those at the
+    /// given azimuth indexes.
+    fn radials_except(omitted_azimuth_indexes: &[u16]) -> Vec<Radial> {
+        (0..360)
+            .filter(|azimuth_index| !omitted_azimuth_indexes.contains(azimuth_index))
+            .map(|azimuth_index| radial(azimuth_index as i64 * 1_000, azimuth_index, 1.0, 1, 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_complete_scan() {
+        let scan = Scan::new(
+            212,
+            vec![full_sweep(0, 1, 0.5), full_sweep(360_000, 2, 1.5)],
+        );
+        assert_eq!(validate_scan(&scan), Vec::new());
+    }
+
+    #[test]
+    fn reports_missing_azimuths() {
+        let issues = validate_scan(&sweep_of(radials_except(&[10, 11])));
+        assert_eq!(
+            issues,
+            vec![ScanIssue::new(ScanIssueKind::MissingAzimuths {
+                sweep_index: 0,
+                azimuth_numbers: vec![11, 12],
+            })]
+        );
+        assert_eq!(issues[0].severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn reports_duplicate_azimuths() {
+        let mut radials = radials_except(&[]);
+        radials.insert(21, radial(20_500, 20, 1.0, 1, 0.5));
+
+        assert_eq!(
+            validate_scan(&sweep_of(radials)),
+            vec![ScanIssue::new(ScanIssueKind::DuplicateAzimuth {
+                sweep_index: 0,
+                azimuth_number: 21,
+            })]
+        );
+    }
+
+    #[test]
+    fn reports_incomplete_rotation() {
+        let omitted: Vec<u16> = (180..360).collect();
+        let issues = validate_scan(&sweep_of(radials_except(&omitted)));
+
+        assert_eq!(
+            issues[0],
+            ScanIssue::new(ScanIssueKind::IncompleteRotation {
+                sweep_index: 0,
+                covered_degrees: 180.0,
+            })
+        );
+        assert_eq!(issues[0].severity(), Severity::Error);
+        assert!(matches!(
+            issues[1].kind(),
+            ScanIssueKind::MissingAzimuths { azimuth_numbers, .. } if azimuth_numbers.len() == 180
+        ));
+    }
+
+    #[test]
+    fn reports_radial_timestamps_out_of_order() {
+        let mut radials = radials_except(&[]);
+        radials[50] = radial(10_000, 50, 1.0, 1, 0.5);
+
+        assert_eq!(
+            validate_scan(&sweep_of(radials)),
+            vec![ScanIssue::new(ScanIssueKind::NonMonotonicRadialTimestamp {
+                sweep_index: 0,
+                radial_index: 50,
+            })]
+        );
+    }
+
+    #[test]
+    fn reports_descending_elevation() {
+        let scan = Scan::new(
+            212,
+            vec![
+                full_sweep(0, 1, 0.5),
+                full_sweep(360_000, 2, 2.4),
+                full_sweep(720_000, 3, 1.5),
+            ],
+        );
+
+        assert_eq!(
+            validate_scan(&scan),
+            vec![ScanIssue::new(ScanIssueKind::DescendingElevationAngle {
+                sweep_index: 2,
+                elevation_angle_degrees: 1.5,
+            })]
+        );
+    }
+
+    #[test]
+    fn accepts_supplemental_revisits() {
+        let scan = Scan::new(
+            212,
+            vec![
+                full_sweep(0, 1, 0.5),
+                full_sweep(360_000, 2, 1.5),
+                full_sweep(720_000, 3, 2.4),
+                full_sweep(1_080_000, 4, 0.5),
+                full_sweep(1_440_000, 5, 3.4),
+            ],
+        );
+
+        assert_eq!(
+            scan.supplemental_flags(),
+            [false, false, false, true, false]
+        );
+        assert_eq!(validate_scan(&scan), Vec::new());
+    }
+
+    /// A coverage pattern with a SAILS revisit of its lowest elevation after its second cut.
+    fn sails_pattern() -> VolumeCoveragePattern {
+        VolumeCoveragePattern::new(
+            212,
+            vec![
+                ElevationCut::new(0.5, None, None),
+                ElevationCut::new(1.5, None, None),
+                ElevationCut::new(0.5, Some(1), None),
+                ElevationCut::new(2.5, None, None),
+            ],
+        )
+    }
+
+    /// Full sweeps at the given elevation angles, numbered consecutively from 1.
+    fn sweeps_at(elevation_angles: &[f32]) -> Vec<Sweep> {
+        elevation_angles
+            .iter()
+            .enumerate()
+            .map(|(index, angle)| full_sweep(index as i64 * 360_000, index as u8 + 1, *angle))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_scan_following_coverage_pattern() {
+        let scan =
+            Scan::new(212, sweeps_at(&[0.5, 1.5, 0.5, 2.5])).with_coverage_pattern(sails_pattern());
+        assert_eq!(validate_scan(&scan), Vec::new());
+    }
+
+    #[test]
+    fn reports_missing_elevation_cut() {
+        let mut sweeps = sweeps_at(&[0.5, 1.5, 0.5, 2.5]);
+        sweeps.remove(1);
+        let scan = Scan::new(212, sweeps).with_coverage_pattern(sails_pattern());
+
+        let issues = validate_scan(&scan);
+        assert_eq!(
+            issues,
+            vec![
+                ScanIssue::new(ScanIssueKind::UnexpectedElevationNumber {
+                    sweep_index: 1,
+                    expected: 2,
+                    found: 3,
+                }),
+                ScanIssue::new(ScanIssueKind::MissingElevationCut {
+                    elevation_number: 2,
+                    elevation_angle_degrees: 1.5,
+                }),
+            ]
+        );
+        assert_eq!(issues[1].severity(), Severity::Error);
+    }
+
+    #[test]
+    fn reports_sweep_at_wrong_angle() {
+        let scan =
+            Scan::new(212, sweeps_at(&[0.5, 1.5, 0.5, 3.5])).with_coverage_pattern(sails_pattern());
+
+        assert_eq!(
+            validate_scan(&scan),
+            vec![ScanIssue::new(ScanIssueKind::ElevationAngleMismatch {
+                sweep_index: 3,
+                expected_degrees: 2.5,
+                found_degrees: 3.5,
+            })]
+        );
+    }
+
+    #[test]
+    fn reports_out_of_place_revisit() {
+        let sweeps = sweeps_at(&[0.5, 1.5, 2.5, 0.5]);
+
+        // Inferred from angles alone, the late revisit of the lowest elevation is supplemental
+        assert_eq!(validate_scan(&Scan::new(212, sweeps.clone())), Vec::new());
+
+        let scan = Scan::new(212, sweeps).with_coverage_pattern(sails_pattern());
+        assert_eq!(
+            validate_scan(&scan),
+            vec![
+                ScanIssue::new(ScanIssueKind::DescendingElevationAngle {
+                    sweep_index: 3,
+                    elevation_angle_degrees: 0.5,
+                }),
+                ScanIssue::new(ScanIssueKind::ElevationAngleMismatch {
+                    sweep_index: 2,
+                    expected_degrees: 0.5,
+                    found_degrees: 2.5,
+                }),
+                ScanIssue::new(ScanIssueKind::ElevationAngleMismatch {
+                    sweep_index: 3,
+                    expected_degrees: 2.5,
+                    found_degrees: 0.5,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_elevation_number_outside_pattern() {
+        let scan = Scan::new(212, sweeps_at(&[0.5, 1.5, 0.5, 2.5, 3.5]))
+            .with_coverage_pattern(sails_pattern());
+
+        assert_eq!(
+            validate_scan(&scan),
+            vec![ScanIssue::new(
+                ScanIssueKind::ElevationNumberOutsidePattern {
+                    sweep_index: 4,
+                    elevation_number: 5,
+                }
+            )]
+        );
+    }
+}
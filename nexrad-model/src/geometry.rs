@@ -0,0 +1,117 @@
+//!
+//! This module contains functions describing the geometry of a radar beam as it propagates away from
+//! the radar, and of locations on the Earth's surface relative to a radar site. Beam propagation
+//! uses the standard "4/3 Earth radius" model which accounts for typical atmospheric refraction.
+//!
+
+/// The mean radius of the Earth in meters.
+pub const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+/// The effective Earth radius in meters used for beam propagation under standard atmospheric
+/// refraction, being 4/3 of the Earth's actual radius.
+pub const EFFECTIVE_EARTH_RADIUS_METERS: f64 = EARTH_RADIUS_METERS * 4.0 / 3.0;
+
+/// The height of the beam's center above the radar antenna in meters at the given slant range in
+/// meters and elevation angle in degrees.
+pub fn beam_height_meters(slant_range_meters: f32, elevation_angle_degrees: f32) -> f32 {
+    let range = slant_range_meters as f64;
+    let elevation = (elevation_angle_degrees as f64).to_radians();
+    let radius = EFFECTIVE_EARTH_RADIUS_METERS;
+
+    ((range * range + radius * radius + 2.0 * range * radius * elevation.sin()).sqrt() - radius)
+        as f32
+}
+
+/// The distance along the Earth's surface in meters from the radar to the point beneath the beam's
+/// center at the given slant range in meters and elevation angle in degrees.
+pub fn ground_range_meters(slant_range_meters: f32, elevation_angle_degrees: f32) -> f32 {
+    let range = slant_range_meters as f64;
+    let elevation = (elevation_angle_degrees as f64).to_radians();
+    let radius = EFFECTIVE_EARTH_RADIUS_METERS;
+    let height = beam_height_meters(slant_range_meters, elevation_angle_degrees) as f64;
+
+    (radius * (range * elevation.cos() / (radius + height)).asin()) as f32
+}
+
+/// The slant range in meters along a beam at the given elevation angle in degrees which is above
+/// the point at the given distance along the Earth's surface in meters from the radar. Returns
+/// [None] if the beam never reaches that distance.
+pub fn slant_range_meters(ground_range_meters: f32, elevation_angle_degrees: f32) -> Option<f32> {
+    let radius = EFFECTIVE_EARTH_RADIUS_METERS;
+    let arc_angle = ground_range_meters as f64 / radius;
+    let elevation = (elevation_angle_degrees as f64).to_radians();
+
+    let denominator = (elevation + arc_angle).cos();
+    if denominator <= 0.0 {
+        return None;
+    }
+
+    Some((radius * arc_angle.sin() / denominator) as f32)
+}
+
+/// The latitude and longitude in degrees of the point at the given distance in meters along the
+/// Earth's surface from an origin point, traveling along the given initial bearing in degrees
+/// clockwise from north.
+pub fn destination(
+    latitude_degrees: f32,
+    longitude_degrees: f32,
+    bearing_degrees: f32,
+    distance_meters: f32,
+) -> (f32, f32) {
+    let latitude = (latitude_degrees as f64).to_radians();
+    let longitude = (longitude_degrees as f64).to_radians();
+    let bearing = (bearing_degrees as f64).to_radians();
+    let angular_distance = distance_meters as f64 / EARTH_RADIUS_METERS;
+
+    let destination_latitude = (latitude.sin() * angular_distance.cos()
+        + latitude.cos() * angular_distance.sin() * bearing.cos())
+    .asin();
+
+    let destination_longitude = longitude
+        + (bearing.sin() * angular_distance.sin() * latitude.cos())
+            .atan2(angular_distance.cos() - latitude.sin() * destination_latitude.sin());
+
+    (
+        destination_latitude.to_degrees() as f32,
+        normalize_longitude(destination_longitude.to_degrees()) as f32,
+    )
+}
+
+/// The initial bearing in degrees clockwise from north and the distance along the Earth's surface in
+/// meters from an origin point to a target point.
+pub fn bearing_and_distance(
+    origin_latitude_degrees: f32,
+    origin_longitude_degrees: f32,
+    target_latitude_degrees: f32,
+    target_longitude_degrees: f32,
+) -> (f32, f32) {
+    let origin_latitude = (origin_latitude_degrees as f64).to_radians();
+    let target_latitude = (target_latitude_degrees as f64).to_radians();
+    let delta_latitude = target_latitude - origin_latitude;
+    let delta_longitude =
+        (target_longitude_degrees as f64 - origin_longitude_degrees as f64).to_radians();
+
+    let haversine = (delta_latitude / 2.0).sin().powi(2)
+        + origin_latitude.cos() * target_latitude.cos() * (delta_longitude / 2.0).sin().powi(2);
+    let distance = 2.0 * EARTH_RADIUS_METERS * haversine.sqrt().atan2((1.0 - haversine).sqrt());
+
+    let bearing = (delta_longitude.sin() * target_latitude.cos()).atan2(
+        origin_latitude.cos() * target_latitude.sin()
+            - origin_latitude.sin() * target_latitude.cos() * delta_longitude.cos(),
+    );
+
+    (
+        bearing.to_degrees().rem_euclid(360.0) as f32,
+        distance as f32,
+    )
+}
+
+/// The smallest angular difference in degrees between two azimuths, from 0 to 180.
+pub fn azimuth_difference_degrees(first_degrees: f32, second_degrees: f32) -> f32 {
+    let difference = (first_degrees - second_degrees).rem_euclid(360.0);
+    difference.min(360.0 - difference)
+}
+
+fn normalize_longitude(longitude_degrees: f64) -> f64 {
+    (longitude_degrees + 540.0).rem_euclid(360.0) - 180.0
+}
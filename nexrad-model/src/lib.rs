@@ -16,5 +16,9 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod data;
+pub mod geometry;
 pub mod meta;
 pub mod result;
+
+#[cfg(test)]
+mod testing;
@@ -107,3 +107,251 @@ impl Debug for Site {
         debug.finish()
     }
 }
+
+/// The behavior of the RDA's clutter filter for a zone of the clutter filter map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ClutterFilterAction {
+    /// The clutter filter is bypassed for the zone.
+    Bypass,
+    /// The clutter bypass map determines whether the zone is filtered.
+    BypassMapInControl,
+    /// The clutter filter is always applied to the zone.
+    Force,
+    /// The zone's operation code is not recognized. The zone still bounds the ranges of its
+    /// neighbors, but its behavior is unknown.
+    Unknown(u16),
+}
+
+/// A span of range along an azimuth of the clutter filter map sharing a clutter filter action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClutterFilterZone {
+    end_range_meters: u32,
+    action: ClutterFilterAction,
+}
+
+impl ClutterFilterZone {
+    /// Create a new clutter filter zone ending at the given range in meters. The zone begins where
+    /// the previous zone along the same azimuth ends, or at the radar.
+    pub fn new(end_range_meters: u32, action: ClutterFilterAction) -> Self {
+        Self {
+            end_range_meters,
+            action,
+        }
+    }
+
+    /// The range in meters at which this zone ends.
+    pub fn end_range_meters(&self) -> u32 {
+        self.end_range_meters
+    }
+
+    /// The clutter filter's behavior in this zone.
+    pub fn action(&self) -> ClutterFilterAction {
+        self.action
+    }
+}
+
+/// A radar site's clutter filter map, describing where the RDA applies its clutter filter. The map
+/// is divided into elevation segments in increasing elevation, each of which is divided into 360
+/// one-degree azimuth segments containing zones of increasing range.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClutterFilterMap {
+    elevation_segments: Vec<Vec<Vec<ClutterFilterZone>>>,
+}
+
+impl ClutterFilterMap {
+    /// Create a new clutter filter map from its elevation segments, each containing the zones for
+    /// one-degree azimuth segments starting from north.
+    pub fn new(elevation_segments: Vec<Vec<Vec<ClutterFilterZone>>>) -> Self {
+        Self { elevation_segments }
+    }
+
+    /// The number of elevation segments in this map.
+    pub fn elevation_segment_count(&self) -> usize {
+        self.elevation_segments.len()
+    }
+
+    /// The zones for each azimuth segment of the given elevation segment, indexed from 0.
+    pub fn azimuth_segments(
+        &self,
+        elevation_segment: usize,
+    ) -> Option<&Vec<Vec<ClutterFilterZone>>> {
+        self.elevation_segments.get(elevation_segment)
+    }
+
+    /// The clutter filter action at the given azimuth in degrees and range in meters within the
+    /// given elevation segment, indexed from 0.
+    pub fn action(
+        &self,
+        elevation_segment: usize,
+        azimuth_degrees: f32,
+        range_meters: f32,
+    ) -> Option<ClutterFilterAction> {
+        let azimuth_segments = self.azimuth_segments(elevation_segment)?;
+        if azimuth_segments.is_empty() {
+            return None;
+        }
+
+        let azimuth_segment = azimuth_degrees.rem_euclid(360.0) as usize % azimuth_segments.len();
+        azimuth_segments[azimuth_segment]
+            .iter()
+            .find(|zone| range_meters < zone.end_range_meters() as f32)
+            .map(|zone| zone.action())
+    }
+}
+
+impl Debug for ClutterFilterMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClutterFilterMap")
+            .field("elevation_segment_count", &self.elevation_segment_count())
+            .finish()
+    }
+}
+
+/// A radar's differential reflectivity (ZDR) calibration as reported with its volume data: the
+/// system ZDR offset applied by the RDA and the RPG's running estimate of the remaining bias.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZdrCalibration {
+    system_differential_reflectivity_db: f32,
+    bias_estimate_db: f32,
+}
+
+impl ZdrCalibration {
+    /// Create a new ZDR calibration with the given system ZDR and estimated bias in dB.
+    pub fn new(system_differential_reflectivity_db: f32, bias_estimate_db: f32) -> Self {
+        Self {
+            system_differential_reflectivity_db,
+            bias_estimate_db,
+        }
+    }
+
+    /// The system differential reflectivity calibration in dB applied by the RDA.
+    pub fn system_differential_reflectivity_db(&self) -> f32 {
+        self.system_differential_reflectivity_db
+    }
+
+    /// The RPG's weighted mean estimate of the differential reflectivity bias in dB.
+    pub fn bias_estimate_db(&self) -> f32 {
+        self.bias_estimate_db
+    }
+}
+
+impl Debug for ZdrCalibration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZdrCalibration")
+            .field(
+                "system_differential_reflectivity_db",
+                &self.system_differential_reflectivity_db(),
+            )
+            .field("bias_estimate_db", &self.bias_estimate_db())
+            .finish()
+    }
+}
+
+/// A volume coverage pattern's definition of a single elevation cut, scanned as one sweep of the
+/// volume, including whether it is a supplemental SAILS or MRLE cut revisiting a lower elevation.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ElevationCut {
+    elevation_angle_degrees: f32,
+    sails_sequence_number: Option<u8>,
+    mrle_sequence_number: Option<u8>,
+}
+
+impl ElevationCut {
+    /// Create a new elevation cut at the given angle in degrees with its sequence number among the
+    /// pattern's SAILS or MRLE cuts if it is one.
+    pub fn new(
+        elevation_angle_degrees: f32,
+        sails_sequence_number: Option<u8>,
+        mrle_sequence_number: Option<u8>,
+    ) -> Self {
+        Self {
+            elevation_angle_degrees,
+            sails_sequence_number,
+            mrle_sequence_number,
+        }
+    }
+
+    /// The elevation angle of this cut in degrees.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// This cut's sequence number among the pattern's SAILS cuts, if it is a SAILS cut revisiting
+    /// the lowest elevation.
+    pub fn sails_sequence_number(&self) -> Option<u8> {
+        self.sails_sequence_number
+    }
+
+    /// This cut's sequence number among the pattern's MRLE cuts, if it is an MRLE cut revisiting
+    /// one of the lowest elevations mid-volume.
+    pub fn mrle_sequence_number(&self) -> Option<u8> {
+        self.mrle_sequence_number
+    }
+
+    /// Whether this cut is a supplemental SAILS or MRLE cut, revisiting an elevation already
+    /// scanned earlier in the volume.
+    pub fn supplemental(&self) -> bool {
+        self.sails_sequence_number.is_some() || self.mrle_sequence_number.is_some()
+    }
+}
+
+impl Debug for ElevationCut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElevationCut")
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("sails_sequence_number", &self.sails_sequence_number())
+            .field("mrle_sequence_number", &self.mrle_sequence_number())
+            .finish()
+    }
+}
+
+/// A volume coverage pattern's definition, as broadcast by the RDA with its volume data, listing
+/// the elevation cuts scanned in the volume in order. Each cut is scanned as one sweep whose
+/// elevation number is the cut's position in the pattern, counting from 1.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VolumeCoveragePattern {
+    pattern_number: u16,
+    elevation_cuts: Vec<ElevationCut>,
+}
+
+impl VolumeCoveragePattern {
+    /// Create a new volume coverage pattern with the given number and elevation cuts in the order
+    /// they are scanned.
+    pub fn new(pattern_number: u16, elevation_cuts: Vec<ElevationCut>) -> Self {
+        Self {
+            pattern_number,
+            elevation_cuts,
+        }
+    }
+
+    /// This volume coverage pattern's number, e.g. 212.
+    pub fn pattern_number(&self) -> u16 {
+        self.pattern_number
+    }
+
+    /// The elevation cuts in this pattern in the order they are scanned.
+    pub fn elevation_cuts(&self) -> &Vec<ElevationCut> {
+        &self.elevation_cuts
+    }
+
+    /// The elevation cut scanned as the sweep with the given elevation number, counting from 1.
+    pub fn elevation_cut(&self, elevation_number: u8) -> Option<&ElevationCut> {
+        self.elevation_cuts
+            .get((elevation_number as usize).checked_sub(1)?)
+    }
+}
+
+impl Debug for VolumeCoveragePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VolumeCoveragePattern")
+            .field("pattern_number", &self.pattern_number())
+            .field("elevation_cuts", &self.elevation_cuts())
+            .finish()
+    }
+}
@@ -10,4 +10,6 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     #[error("two sweeps' elevation numbers do not match")]
     ElevationMismatchError,
+    #[error("a product's gate geometry or encoding differs between radials")]
+    InconsistentMomentEncoding,
 }
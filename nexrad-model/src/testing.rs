@@ -0,0 +1,93 @@
+//! Builders for the synthetic radials, sweeps and sites used throughout this crate's tests.
+
+use crate::data::{MomentData, Product, Radial, RadialStatus, Sweep};
+use crate::meta::Site;
+
+/// Range from the radar to the center of the first gate of test moments in meters.
+pub(crate) const FIRST_GATE_RANGE: u16 = 2_000;
+
+/// Distance between the centers of adjacent gates of test moments in meters.
+pub(crate) const GATE_INTERVAL: u16 = 250;
+
+/// The site of the test radar.
+pub(crate) fn site() -> Site {
+    Site::new(*b"KTLX", 35.33, -97.28, 370, 20)
+}
+
+/// An intermediate radial at the given index of a sweep whose first radial starts at north, with
+/// no moment data.
+pub(crate) fn radial(
+    collection_timestamp: i64,
+    azimuth_index: u16,
+    azimuth_spacing_degrees: f32,
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+) -> Radial {
+    Radial::new(
+        collection_timestamp,
+        azimuth_index + 1,
+        (azimuth_index as f32 + 0.5) * azimuth_spacing_degrees,
+        azimuth_spacing_degrees,
+        RadialStatus::IntermediateRadialData,
+        elevation_number,
+        elevation_angle_degrees,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Reflectivity moment data with the standard test gate geometry and Level II encoding.
+pub(crate) fn reflectivity(values: Vec<u8>) -> MomentData {
+    MomentData::from_fixed_point(2.0, 66.0, values)
+        .with_gate_geometry(FIRST_GATE_RANGE, GATE_INTERVAL)
+}
+
+/// Velocity moment data with the standard test gate geometry and Level II encoding.
+pub(crate) fn velocity(values: Vec<u8>) -> MomentData {
+    MomentData::from_fixed_point(2.0, 129.0, values)
+        .with_gate_geometry(FIRST_GATE_RANGE, GATE_INTERVAL)
+}
+
+/// The raw velocity value encoding the given velocity or spectrum width in meters per second.
+pub(crate) fn encode_velocity(meters_per_second: f32) -> u8 {
+    (meters_per_second * 2.0 + 129.0).round() as u8
+}
+
+/// The raw reflectivity value encoding the given reflectivity in dBZ.
+pub(crate) fn encode_reflectivity(dbz: f32) -> u8 {
+    (dbz * 2.0 + 66.0).round() as u8
+}
+
+/// A full rotation of radials one second apart at the given elevation, each with the given
+/// reflectivity in dBZ at every gate.
+pub(crate) fn uniform_sweep(
+    start_timestamp: i64,
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+    azimuth_spacing_degrees: f32,
+    dbz: f32,
+) -> Sweep {
+    let radial_count = (360.0 / azimuth_spacing_degrees).round() as u16;
+    let radials = (0..radial_count)
+        .map(|azimuth_index| {
+            radial(
+                start_timestamp + azimuth_index as i64 * 1_000,
+                azimuth_index,
+                azimuth_spacing_degrees,
+                elevation_number,
+                elevation_angle_degrees,
+            )
+            .with_moment(
+                Product::Reflectivity,
+                reflectivity(vec![encode_reflectivity(dbz); 100]),
+            )
+        })
+        .collect();
+
+    Sweep::new(elevation_number, radials)
+}
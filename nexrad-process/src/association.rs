@@ -0,0 +1,88 @@
+//!
+//! Locating features on the ground and associating features detected on successive sweeps into
+//! vertically aligned columns.
+//!
+
+/// The eastward and northward distance in meters from the radar to a point at the given azimuth in
+/// degrees and ground range in meters.
+pub(crate) fn east_north(azimuth_degrees: f32, ground_range_meters: f32) -> (f32, f32) {
+    let (sin, cos) = azimuth_degrees.to_radians().sin_cos();
+    (ground_range_meters * sin, ground_range_meters * cos)
+}
+
+/// The horizontal distance in meters between two points given by their eastward and northward
+/// distances in meters from the radar.
+pub(crate) fn distance(
+    (first_east, first_north): (f32, f32),
+    (second_east, second_north): (f32, f32),
+) -> f32 {
+    (first_east - second_east).hypot(first_north - second_north)
+}
+
+/// Groups features into levels of equal elevation angle, ordered by ascending elevation.
+pub(crate) fn elevation_levels<T>(
+    mut features: Vec<T>,
+    elevation: impl Fn(&T) -> f32,
+) -> Vec<Vec<T>> {
+    features.sort_by(|a, b| elevation(a).total_cmp(&elevation(b)));
+
+    let mut levels: Vec<Vec<T>> = Vec::new();
+    for feature in features {
+        match levels.last_mut() {
+            Some(level)
+                if level
+                    .last()
+                    .is_some_and(|last| elevation(last) == elevation(&feature)) =>
+            {
+                level.push(feature)
+            }
+            _ => levels.push(vec![feature]),
+        }
+    }
+
+    levels
+}
+
+/// Stacks features from levels ordered by ascending elevation into vertically aligned columns.
+/// Each feature joins the column whose top, from a lower level, is nearest its position within the
+/// first of the search distances in meters to find one, or otherwise starts a new column. When
+/// `adjacent_only` is set, a column may only be extended from the level immediately below.
+pub(crate) fn stack_levels<T>(
+    levels: impl IntoIterator<Item = Vec<T>>,
+    search_distances_meters: &[f32],
+    adjacent_only: bool,
+    position: impl Fn(&T) -> (f32, f32),
+) -> Vec<Vec<T>> {
+    let mut columns: Vec<(usize, Vec<T>)> = Vec::new();
+
+    for (level, features) in levels.into_iter().enumerate() {
+        for feature in features {
+            let location = position(&feature);
+
+            let nearest = search_distances_meters.iter().find_map(|search_distance| {
+                columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (column_level, _))| {
+                        *column_level < level && (!adjacent_only || *column_level + 1 == level)
+                    })
+                    .filter_map(|(index, (_, column))| {
+                        let distance = distance(location, position(column.last()?));
+                        (distance <= *search_distance).then_some((distance, index))
+                    })
+                    .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            });
+
+            match nearest {
+                Some((_, index)) => {
+                    let (column_level, column) = &mut columns[index];
+                    *column_level = level;
+                    column.push(feature);
+                }
+                None => columns.push((level, vec![feature])),
+            }
+        }
+    }
+
+    columns.into_iter().map(|(_, column)| column).collect()
+}
@@ -0,0 +1,536 @@
+//!
+//! Classification of biological echoes and detection of roost rings. Birds, bats and insects are
+//! distinguished from precipitation by their dual-polarization signature: low correlation
+//! coefficient, high differential reflectivity and noisy differential phase, typically at modest
+//! reflectivity. Insects have especially high differential reflectivity. Classification is most
+//! meaningful on low-elevation sweeps, where biological scatterers are concentrated.
+//!
+//! Birds and bats leaving communal roosts around sunrise and sunset appear as rings of echo which
+//! expand over consecutive scans. Rings are found by fitting circles to regions of echo in each
+//! scan's lowest sweep and associated across scans where their centers coincide and their radii
+//! grow at a plausible rate.
+//!
+
+mod classification;
+pub use classification::*;
+
+mod options;
+pub use options::*;
+
+mod roost;
+pub use roost::*;
+
+use crate::association::{distance, east_north};
+use crate::field::PolarField;
+use crate::math::{ramp, solve_3x3, standard_deviation};
+use crate::regions::connected_regions;
+use crate::result::{Error, Result};
+use nexrad_model::data::{Radial, Scan, Sweep};
+use nexrad_model::geometry::{destination, ground_range_meters};
+use nexrad_model::meta::Site;
+
+/// The number of azimuthal sectors around a ring's center used to measure its angular coverage.
+const RING_SECTORS: usize = 36;
+
+/// Classifies the echoes in a sweep. Returns [None] if the sweep has no reflectivity data.
+pub fn classify_echoes(
+    sweep: &Sweep,
+    options: &BiologyOptions,
+) -> Result<Option<EchoClassification>> {
+    options.validate()?;
+
+    let reflectivity = match PolarField::from_sweep(sweep, Radial::reflectivity) {
+        Some(reflectivity) => reflectivity,
+        None => return Ok(None),
+    };
+
+    let differential_reflectivity =
+        PolarField::from_sweep(sweep, Radial::differential_reflectivity);
+    let correlation = PolarField::from_sweep(sweep, Radial::correlation_coefficient);
+    let differential_phase = PolarField::from_sweep(sweep, Radial::differential_phase);
+
+    let gate_count = reflectivity.gate_count();
+    let half_window = options.texture_window_gates / 2;
+
+    let mut likelihood = reflectivity.empty_like();
+    let mut classes = vec![None; reflectivity.values().len()];
+
+    for (radial_index, azimuth) in reflectivity.azimuths_degrees().iter().enumerate() {
+        let sample = |field: &Option<PolarField>| -> Vec<f32> {
+            (0..gate_count)
+                .map(|gate_index| {
+                    field
+                        .as_ref()
+                        .and_then(|field| {
+                            field.value_at(*azimuth, reflectivity.gate_range_meters(gate_index))
+                        })
+                        .unwrap_or(f32::NAN)
+                })
+                .collect()
+        };
+
+        let differential_reflectivity_values = sample(&differential_reflectivity);
+        let correlation_values = sample(&correlation);
+        let differential_phase_values = sample(&differential_phase);
+
+        for gate_index in 0..gate_count {
+            let reflectivity_value = match reflectivity.value(radial_index, gate_index) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            let differential_reflectivity_value = differential_reflectivity_values[gate_index];
+            let correlation_value = correlation_values[gate_index];
+            let window = gate_index.saturating_sub(half_window)
+                ..(gate_index + half_window + 1).min(gate_count);
+            let phase_texture = standard_deviation(&differential_phase_values[window]);
+
+            let mut memberships = Vec::new();
+            if !correlation_value.is_nan() {
+                memberships.push((1.0 - ramp(correlation_value, 0.6, 0.9), 2.0));
+            }
+            if !differential_reflectivity_value.is_nan() {
+                memberships.push((ramp(differential_reflectivity_value, 1.0, 4.0), 1.0));
+            }
+            if let Some(phase_texture) = phase_texture {
+                memberships.push((ramp(phase_texture, 10.0, 30.0), 1.0));
+            }
+            if memberships.is_empty() {
+                continue;
+            }
+            memberships.push((1.0 - ramp(reflectivity_value, 30.0, 45.0), 0.5));
+
+            let (total, weight) = memberships.iter().fold(
+                (0.0, 0.0),
+                |(total, weight), (membership, membership_weight)| {
+                    (
+                        total + membership * membership_weight,
+                        weight + membership_weight,
+                    )
+                },
+            );
+            let value = total / weight;
+            likelihood.set(radial_index, gate_index, value);
+
+            classes[radial_index * gate_count + gate_index] =
+                if value >= options.biological_threshold {
+                    if differential_reflectivity_value
+                        >= options.insect_min_differential_reflectivity_db
+                    {
+                        Some(EchoClass::Insects)
+                    } else {
+                        Some(EchoClass::BirdsOrBats)
+                    }
+                } else if correlation_value >= options.precipitation_min_correlation {
+                    Some(EchoClass::Precipitation)
+                } else {
+                    None
+                };
+        }
+    }
+
+    Ok(Some(EchoClassification::new(
+        sweep.elevation_number(),
+        likelihood,
+        classes,
+    )))
+}
+
+/// Classifies the echoes in each of a scan's sweeps at or below
+/// [BiologyOptions::max_elevation_degrees].
+pub fn classify_scan(scan: &Scan, options: &BiologyOptions) -> Result<Vec<EchoClassification>> {
+    options.validate()?;
+
+    let mut classifications = Vec::new();
+    for sweep in scan.sweeps() {
+        if sweep
+            .elevation_angle_degrees()
+            .is_some_and(|elevation| elevation <= options.max_elevation_degrees)
+        {
+            classifications.extend(classify_echoes(sweep, options)?);
+        }
+    }
+
+    Ok(classifications)
+}
+
+/// Finds rings of echo in a single sweep.
+pub fn sweep_roost_rings(sweep: &Sweep, options: &RoostOptions) -> Result<Vec<RoostRing>> {
+    options.validate()?;
+
+    let reflectivity = match PolarField::from_sweep(sweep, Radial::reflectivity) {
+        Some(reflectivity) => reflectivity,
+        None => return Ok(Vec::new()),
+    };
+    let classification = classify_echoes(sweep, &options.biology_options)?;
+
+    let elevation_angle = reflectivity.elevation_angle_degrees().unwrap_or_default();
+    let gate_count = reflectivity.gate_count();
+    let collection_timestamp = sweep
+        .radials()
+        .iter()
+        .map(|radial| radial.collection_timestamp())
+        .min()
+        .unwrap_or_default();
+
+    let flagged: Vec<bool> = reflectivity
+        .values()
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let precipitation = classification.as_ref().is_some_and(|classification| {
+                classification.classes()[index] == Some(EchoClass::Precipitation)
+            });
+
+            *value >= options.min_reflectivity_dbz
+                && *value <= options.max_reflectivity_dbz
+                && !precipitation
+        })
+        .collect();
+
+    Ok(
+        connected_regions(reflectivity.radial_count(), gate_count, &flagged)
+            .into_iter()
+            .filter(|region| region.len() >= options.min_gates)
+            .filter_map(|region| {
+                let positions: Vec<(f64, f64)> = region
+                    .iter()
+                    .map(|(radial_index, gate_index)| {
+                        let azimuth = reflectivity.azimuths_degrees()[*radial_index].to_radians();
+                        let ground_range = ground_range_meters(
+                            reflectivity.gate_range_meters(*gate_index),
+                            elevation_angle,
+                        ) as f64;
+                        (
+                            ground_range * azimuth.sin() as f64,
+                            ground_range * azimuth.cos() as f64,
+                        )
+                    })
+                    .collect();
+
+                let (center, radius) = fit_circle(&positions)?;
+                if radius < options.min_radius_meters as f64
+                    || radius > options.max_radius_meters as f64
+                {
+                    return None;
+                }
+
+                let mut squared_residual = 0.0;
+                let mut sectors = [false; RING_SECTORS];
+                for (east, north) in &positions {
+                    let (east, north) = (east - center.0, north - center.1);
+                    squared_residual += (east.hypot(north) - radius).powi(2);
+
+                    let bearing = east.atan2(north).to_degrees().rem_euclid(360.0);
+                    sectors[(bearing / 360.0 * RING_SECTORS as f64) as usize % RING_SECTORS] = true;
+                }
+
+                let residual = (squared_residual / positions.len() as f64).sqrt();
+                if residual > options.max_residual_fraction as f64 * radius {
+                    return None;
+                }
+
+                let coverage =
+                    sectors.iter().filter(|covered| **covered).count() as f32 / RING_SECTORS as f32;
+                if coverage < options.min_angular_coverage {
+                    return None;
+                }
+
+                Some(RoostRing::new(
+                    collection_timestamp,
+                    center.0.atan2(center.1).to_degrees().rem_euclid(360.0) as f32,
+                    center.0.hypot(center.1) as f32,
+                    radius as f32,
+                    coverage,
+                    region.len(),
+                ))
+            })
+            .collect(),
+    )
+}
+
+/// Detects roost rings expanding across consecutive scans, which should be ordered by time. Rings
+/// are found in each scan's lowest sweep.
+pub fn detect_roosts(
+    scans: &[Scan],
+    site: &Site,
+    options: &RoostOptions,
+) -> Result<Vec<ExpandingRoost>> {
+    options.validate()?;
+
+    let mut chains: Vec<(usize, Vec<RoostRing>)> = Vec::new();
+    let mut previous_timestamp = None;
+
+    for (scan_index, scan) in scans.iter().enumerate() {
+        let lowest = scan
+            .sweeps()
+            .iter()
+            .filter(|sweep| {
+                sweep
+                    .radials()
+                    .iter()
+                    .any(|radial| radial.reflectivity().is_some())
+            })
+            .filter_map(|sweep| Some((sweep.elevation_angle_degrees()?, sweep)))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let rings = match lowest {
+            Some((_, sweep)) => sweep_roost_rings(sweep, options)?,
+            None => continue,
+        };
+
+        if let Some(timestamp) = rings.iter().map(|ring| ring.collection_timestamp()).min() {
+            if previous_timestamp.is_some_and(|previous| timestamp <= previous) {
+                return Err(Error::OutOfOrderScan);
+            }
+            previous_timestamp = Some(timestamp);
+        }
+
+        for ring in rings {
+            let center = position(&ring);
+            let nearest = chains
+                .iter()
+                .enumerate()
+                .filter(|(_, (chain_scan, _))| *chain_scan + 1 == scan_index)
+                .filter_map(|(index, (_, chain))| {
+                    let last = chain.last()?;
+                    let distance = distance(center, position(last));
+
+                    let elapsed =
+                        (ring.collection_timestamp() - last.collection_timestamp()) as f32 / 1000.0;
+                    if elapsed <= 0.0 {
+                        return None;
+                    }
+
+                    let expansion = (ring.radius_meters() - last.radius_meters()) / elapsed;
+                    (distance <= options.max_center_distance_meters
+                        && expansion >= options.min_expansion_meters_per_second
+                        && expansion <= options.max_expansion_meters_per_second)
+                        .then_some((distance, index))
+                })
+                .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+            match nearest {
+                Some((_, index)) => {
+                    let (chain_scan, chain) = &mut chains[index];
+                    *chain_scan = scan_index;
+                    chain.push(ring);
+                }
+                None => chains.push((scan_index, vec![ring])),
+            }
+        }
+    }
+
+    Ok(chains
+        .into_iter()
+        .map(|(_, chain)| chain)
+        .filter(|chain| chain.len() >= options.min_scans.max(2))
+        .filter_map(|chain| {
+            let first = chain.first()?;
+            let (latitude, longitude) = destination(
+                site.latitude(),
+                site.longitude(),
+                first.center_azimuth_degrees(),
+                first.center_ground_range_meters(),
+            );
+
+            let expansion = expansion_rate(&chain)?;
+            Some(ExpandingRoost::new(latitude, longitude, expansion, chain))
+        })
+        .collect())
+}
+
+/// Fits a circle to positions using the algebraic least-squares method, returning its center and
+/// radius.
+fn fit_circle(positions: &[(f64, f64)]) -> Option<((f64, f64), f64)> {
+    let count = positions.len() as f64;
+    let mean_east = positions.iter().map(|(east, _)| east).sum::<f64>() / count;
+    let mean_north = positions.iter().map(|(_, north)| north).sum::<f64>() / count;
+
+    // Positions are fit in kilometers relative to their mean to keep the system well conditioned.
+    let mut normal = [[0.0; 3]; 3];
+    let mut target = [0.0; 3];
+    for (east, north) in positions {
+        let (x, y) = ((east - mean_east) / 1000.0, (north - mean_north) / 1000.0);
+        let basis = [x, y, 1.0];
+        for row in 0..3 {
+            for column in 0..3 {
+                normal[row][column] += basis[row] * basis[column];
+            }
+            target[row] -= basis[row] * (x * x + y * y);
+        }
+    }
+
+    let [d, e, f] = solve_3x3(normal, target)?;
+    let (x, y) = (-d / 2.0, -e / 2.0);
+    let squared_radius = x * x + y * y - f;
+    if squared_radius <= 0.0 {
+        return None;
+    }
+
+    Some((
+        (mean_east + x * 1000.0, mean_north + y * 1000.0),
+        squared_radius.sqrt() * 1000.0,
+    ))
+}
+
+/// The least-squares rate of growth in meters per second of a series of rings' radii.
+fn expansion_rate(rings: &[RoostRing]) -> Option<f32> {
+    let count = rings.len() as f64;
+    let times: Vec<f64> = rings
+        .iter()
+        .map(|ring| ring.collection_timestamp() as f64 / 1000.0)
+        .collect();
+    let mean_time = times.iter().sum::<f64>() / count;
+    let mean_radius = rings
+        .iter()
+        .map(|ring| ring.radius_meters() as f64)
+        .sum::<f64>()
+        / count;
+
+    let mut variance = 0.0;
+    let mut covariance = 0.0;
+    for (time, ring) in times.iter().zip(rings) {
+        variance += (time - mean_time).powi(2);
+        covariance += (time - mean_time) * (ring.radius_meters() as f64 - mean_radius);
+    }
+
+    (variance > 0.0).then(|| (covariance / variance) as f32)
+}
+
+/// The eastward and northward distance in meters from the radar to a ring's center.
+fn position(ring: &RoostRing) -> (f32, f32) {
+    east_north(
+        ring.center_azimuth_degrees(),
+        ring.center_ground_range_meters(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::Product;
+
+    /// Builds a 0.5 degree sweep at the given time in seconds whose gates' reflectivity,
+    /// differential reflectivity and correlation coefficient are given by a function of their
+    /// position in meters east and north of the radar.
+    fn sweep<F>(time_seconds: i64, gate: F) -> Sweep
+    where
+        F: Fn(f32, f32) -> Option<(f32, f32, f32)>,
+    {
+        let radials = (0..720)
+            .map(|azimuth_number| {
+                let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                let (sin, cos) = azimuth.to_radians().sin_cos();
+
+                let mut reflectivity = Vec::new();
+                let mut differential_reflectivity = Vec::new();
+                let mut correlation = Vec::new();
+                for gate_index in 0..240 {
+                    let range = 2_000.0 + gate_index as f32 * 250.0;
+                    let (z, zdr, cc) = match gate(range * sin, range * cos) {
+                        Some(moments) => moments,
+                        None => {
+                            reflectivity.push(0);
+                            differential_reflectivity.push(0);
+                            correlation.push(0);
+                            continue;
+                        }
+                    };
+
+                    reflectivity.push((z * 2.0 + 66.0).round() as u8);
+                    differential_reflectivity.push((zdr * 16.0 + 128.0).round() as u8);
+                    correlation.push((cc * 300.0 - 60.5).round() as u8);
+                }
+
+                radial(time_seconds * 1000, azimuth_number, 0.5, 1, 0.5)
+                    .with_moment(
+                        Product::Reflectivity,
+                        moment(&Product::Reflectivity, reflectivity),
+                    )
+                    .with_moment(
+                        Product::DifferentialReflectivity,
+                        moment(
+                            &Product::DifferentialReflectivity,
+                            differential_reflectivity,
+                        ),
+                    )
+                    .with_moment(
+                        Product::CorrelationCoefficient,
+                        moment(&Product::CorrelationCoefficient, correlation),
+                    )
+            })
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    #[test]
+    fn classifies_echoes() {
+        // Rain to the north, insects to the east and birds to the south.
+        let sweep = sweep(0, |east, north| {
+            if north > 20_000.0 {
+                Some((40.0, 0.5, 0.99))
+            } else if east > 20_000.0 {
+                Some((5.0, 7.0, 0.4))
+            } else if north < -20_000.0 {
+                Some((20.0, 2.5, 0.45))
+            } else {
+                None
+            }
+        });
+
+        let classification = classify_echoes(&sweep, &BiologyOptions::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            classification.class_at(0.0, 40_000.0),
+            Some(EchoClass::Precipitation)
+        );
+        assert_eq!(
+            classification.class_at(90.0, 40_000.0),
+            Some(EchoClass::Insects)
+        );
+        assert_eq!(
+            classification.class_at(180.0, 40_000.0),
+            Some(EchoClass::BirdsOrBats)
+        );
+        assert_eq!(classification.class_at(270.0, 40_000.0), None);
+    }
+
+    #[test]
+    fn detects_expanding_roost() {
+        // A ring centered 30 km east of the radar whose radius grows by 3 km every five minutes.
+        let scans: Vec<Scan> = (0..3)
+            .map(|scan_number| {
+                let radius = 5_000.0 + 3_000.0 * scan_number as f32;
+                let ring = sweep(scan_number as i64 * 300, |east, north| {
+                    let distance = (east - 30_000.0).hypot(north);
+                    ((distance - radius).abs() < 500.0).then_some((15.0, 3.0, 0.5))
+                });
+                Scan::new(35, vec![ring])
+            })
+            .collect();
+
+        let site = Site::new(*b"KTLX", 35.33, -97.28, 370, 20);
+        let roosts = detect_roosts(&scans, &site, &RoostOptions::default()).unwrap();
+        assert_eq!(roosts.len(), 1);
+
+        let roost = &roosts[0];
+        assert_eq!(roost.rings().len(), 3);
+        assert!((roost.expansion_meters_per_second() - 10.0).abs() < 1.0);
+
+        let first = &roost.rings()[0];
+        assert!((first.center_azimuth_degrees() - 90.0).abs() < 1.0);
+        assert!((first.center_ground_range_meters() - 30_000.0).abs() < 500.0);
+        assert!((first.radius_meters() - 5_000.0).abs() < 500.0);
+
+        // The ring would have had no radius about 500 seconds before the first scan.
+        let departure = roost.departure_timestamp().unwrap();
+        assert!(
+            (departure + 500_000).abs() < 60_000,
+            "departure {departure}"
+        );
+    }
+}
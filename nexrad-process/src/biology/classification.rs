@@ -0,0 +1,100 @@
+use crate::field::PolarField;
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The kind of scatterer an echo is attributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EchoClass {
+    /// Rain, snow or hail.
+    Precipitation,
+    /// Insects, characterized by very high differential reflectivity.
+    Insects,
+    /// Birds or bats, which have similar polarimetric signatures.
+    BirdsOrBats,
+}
+
+/// The classification of each gate of a sweep, along with the biological likelihood from which it
+/// was derived.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EchoClassification {
+    elevation_number: u8,
+    likelihood: PolarField,
+    classes: Vec<Option<EchoClass>>,
+}
+
+impl EchoClassification {
+    /// Create a new classification from a biological likelihood field and the class of each of its
+    /// gates, ordered by radial and then gate.
+    pub fn new(
+        elevation_number: u8,
+        likelihood: PolarField,
+        classes: Vec<Option<EchoClass>>,
+    ) -> Self {
+        Self {
+            elevation_number,
+            likelihood,
+            classes,
+        }
+    }
+
+    /// The elevation number of the classified sweep.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// The likelihood, from 0 to 1, that each gate's echo is biological. Gates without echo or
+    /// without the moments required to classify them are NaN.
+    pub fn likelihood(&self) -> &PolarField {
+        &self.likelihood
+    }
+
+    /// The class of each gate ordered by radial and then gate, or [None] for gates without echo or
+    /// which could not be classified.
+    pub fn classes(&self) -> &Vec<Option<EchoClass>> {
+        self.classes.as_ref()
+    }
+
+    /// The class of the gate at the given radial and gate.
+    pub fn class(&self, radial_index: usize, gate_index: usize) -> Option<EchoClass> {
+        if radial_index >= self.likelihood.radial_count()
+            || gate_index >= self.likelihood.gate_count()
+        {
+            return None;
+        }
+
+        self.classes[radial_index * self.likelihood.gate_count() + gate_index]
+    }
+
+    /// The class of the gate nearest the given azimuth in degrees and slant range in meters.
+    pub fn class_at(&self, azimuth_degrees: f32, range_meters: f32) -> Option<EchoClass> {
+        self.likelihood.value_at(azimuth_degrees, range_meters)?;
+        self.class(
+            self.likelihood.radial_index(azimuth_degrees)?,
+            self.likelihood.gate_index(range_meters)?,
+        )
+    }
+
+    /// The number of gates of the given class.
+    pub fn count(&self, class: EchoClass) -> usize {
+        self.classes
+            .iter()
+            .filter(|gate_class| **gate_class == Some(class))
+            .count()
+    }
+}
+
+impl Debug for EchoClassification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EchoClassification")
+            .field("elevation_number", &self.elevation_number())
+            .field("likelihood", &self.likelihood())
+            .field("precipitation", &self.count(EchoClass::Precipitation))
+            .field("insects", &self.count(EchoClass::Insects))
+            .field("birds_or_bats", &self.count(EchoClass::BirdsOrBats))
+            .finish()
+    }
+}
@@ -0,0 +1,144 @@
+use crate::result::{Error, Result};
+
+/// Options controlling classification of biological echoes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BiologyOptions {
+    /// Only sweeps at or below this elevation angle in degrees are classified when classifying a
+    /// scan. Biological scatterers are concentrated in the lowest few kilometers.
+    pub max_elevation_degrees: f32,
+
+    /// The biological likelihood, from 0 to 1, at or above which a gate is classified as
+    /// biological. The likelihood combines low correlation coefficient, high differential
+    /// reflectivity, high differential phase texture and modest reflectivity where those moments
+    /// are available.
+    pub biological_threshold: f32,
+
+    /// Biological gates with differential reflectivity in dB at or above this value are
+    /// classified as insects, and otherwise as birds or bats.
+    pub insect_min_differential_reflectivity_db: f32,
+
+    /// Non-biological gates with correlation coefficient at or above this value are classified as
+    /// precipitation.
+    pub precipitation_min_correlation: f32,
+
+    /// The number of gates along each radial over which differential phase texture is computed.
+    pub texture_window_gates: usize,
+}
+
+impl BiologyOptions {
+    /// Checks that these options are usable for classification.
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.biological_threshold) {
+            return Err(Error::InvalidOptions(
+                "biological threshold must be between 0 and 1",
+            ));
+        }
+
+        if self.texture_window_gates < 2 {
+            return Err(Error::InvalidOptions(
+                "texture window must span at least two gates",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BiologyOptions {
+    fn default() -> Self {
+        Self {
+            max_elevation_degrees: 2.0,
+            biological_threshold: 0.6,
+            insect_min_differential_reflectivity_db: 5.0,
+            precipitation_min_correlation: 0.9,
+            texture_window_gates: 5,
+        }
+    }
+}
+
+/// Options controlling detection of roost rings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoostOptions {
+    /// Echoes with reflectivity in dBZ below this value are ignored.
+    pub min_reflectivity_dbz: f32,
+
+    /// Echoes with reflectivity in dBZ above this value are ignored.
+    pub max_reflectivity_dbz: f32,
+
+    /// The minimum number of gates in a region of echo for a ring to be fit to it.
+    pub min_gates: usize,
+
+    /// The smallest radius in meters of a ring.
+    pub min_radius_meters: f32,
+
+    /// The largest radius in meters of a ring.
+    pub max_radius_meters: f32,
+
+    /// The largest root-mean-square distance of a region's gates from its fitted circle, as a
+    /// fraction of the circle's radius, for the region to be considered a ring.
+    pub max_residual_fraction: f32,
+
+    /// The minimum fraction of a ring's circumference which must contain echo.
+    pub min_angular_coverage: f32,
+
+    /// The largest distance in meters between the centers of rings in consecutive scans for them
+    /// to be associated.
+    pub max_center_distance_meters: f32,
+
+    /// The slowest rate in meters per second at which an associated ring's radius must grow.
+    pub min_expansion_meters_per_second: f32,
+
+    /// The fastest rate in meters per second at which an associated ring's radius may grow.
+    pub max_expansion_meters_per_second: f32,
+
+    /// The minimum number of consecutive scans an expanding roost must be observed in.
+    pub min_scans: usize,
+
+    /// Options for classifying echoes. Where dual-polarization moments are available, echoes
+    /// classified as precipitation are ignored.
+    pub biology_options: BiologyOptions,
+}
+
+impl RoostOptions {
+    /// Checks that these options are usable for detection.
+    pub fn validate(&self) -> Result<()> {
+        if self.min_gates < 3 {
+            return Err(Error::InvalidOptions(
+                "at least three gates are required to fit a ring",
+            ));
+        }
+
+        if self.min_radius_meters < 0.0 || self.max_radius_meters <= self.min_radius_meters {
+            return Err(Error::InvalidOptions("ring radius bounds are invalid"));
+        }
+
+        if self.min_expansion_meters_per_second > self.max_expansion_meters_per_second {
+            return Err(Error::InvalidOptions("expansion rate bounds are invalid"));
+        }
+
+        if self.min_scans == 0 {
+            return Err(Error::InvalidOptions("at least one scan is required"));
+        }
+
+        self.biology_options.validate()
+    }
+}
+
+impl Default for RoostOptions {
+    fn default() -> Self {
+        Self {
+            min_reflectivity_dbz: 0.0,
+            max_reflectivity_dbz: 35.0,
+            min_gates: 40,
+            min_radius_meters: 2_000.0,
+            max_radius_meters: 60_000.0,
+            max_residual_fraction: 0.2,
+            min_angular_coverage: 0.5,
+            max_center_distance_meters: 5_000.0,
+            min_expansion_meters_per_second: 2.0,
+            max_expansion_meters_per_second: 30.0,
+            min_scans: 2,
+            biology_options: BiologyOptions::default(),
+        }
+    }
+}
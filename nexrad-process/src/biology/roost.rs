@@ -0,0 +1,158 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A ring of echo observed in a single sweep, as produced by birds or bats dispersing from a roost.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoostRing {
+    collection_timestamp: i64,
+    center_azimuth_degrees: f32,
+    center_ground_range_meters: f32,
+    radius_meters: f32,
+    angular_coverage: f32,
+    gate_count: usize,
+}
+
+impl RoostRing {
+    /// Create a new roost ring with the given properties.
+    pub fn new(
+        collection_timestamp: i64,
+        center_azimuth_degrees: f32,
+        center_ground_range_meters: f32,
+        radius_meters: f32,
+        angular_coverage: f32,
+        gate_count: usize,
+    ) -> Self {
+        Self {
+            collection_timestamp,
+            center_azimuth_degrees,
+            center_ground_range_meters,
+            radius_meters,
+            angular_coverage,
+            gate_count,
+        }
+    }
+
+    /// The collection timestamp of the sweep this ring was found in, in milliseconds since the
+    /// UNIX epoch.
+    pub fn collection_timestamp(&self) -> i64 {
+        self.collection_timestamp
+    }
+
+    /// The azimuth in degrees from the radar to this ring's center.
+    pub fn center_azimuth_degrees(&self) -> f32 {
+        self.center_azimuth_degrees
+    }
+
+    /// The distance along the ground in meters from the radar to this ring's center.
+    pub fn center_ground_range_meters(&self) -> f32 {
+        self.center_ground_range_meters
+    }
+
+    /// This ring's radius in meters.
+    pub fn radius_meters(&self) -> f32 {
+        self.radius_meters
+    }
+
+    /// The fraction of this ring's circumference containing echo, from 0 to 1.
+    pub fn angular_coverage(&self) -> f32 {
+        self.angular_coverage
+    }
+
+    /// The number of gates comprising this ring.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+}
+
+impl Debug for RoostRing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoostRing")
+            .field("collection_timestamp", &self.collection_timestamp())
+            .field("center_azimuth_degrees", &self.center_azimuth_degrees())
+            .field(
+                "center_ground_range_meters",
+                &self.center_ground_range_meters(),
+            )
+            .field("radius_meters", &self.radius_meters())
+            .field("angular_coverage", &self.angular_coverage())
+            .field("gate_count", &self.gate_count())
+            .finish()
+    }
+}
+
+/// A roost ring expanding across consecutive scans.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpandingRoost {
+    latitude_degrees: f32,
+    longitude_degrees: f32,
+    expansion_meters_per_second: f32,
+    rings: Vec<RoostRing>,
+}
+
+impl ExpandingRoost {
+    /// Create a new expanding roost from its rings, which should be ordered by time.
+    pub fn new(
+        latitude_degrees: f32,
+        longitude_degrees: f32,
+        expansion_meters_per_second: f32,
+        rings: Vec<RoostRing>,
+    ) -> Self {
+        Self {
+            latitude_degrees,
+            longitude_degrees,
+            expansion_meters_per_second,
+            rings,
+        }
+    }
+
+    /// The latitude in degrees of the roost, taken from its first ring's center.
+    pub fn latitude_degrees(&self) -> f32 {
+        self.latitude_degrees
+    }
+
+    /// The longitude in degrees of the roost, taken from its first ring's center.
+    pub fn longitude_degrees(&self) -> f32 {
+        self.longitude_degrees
+    }
+
+    /// The rate in meters per second at which the ring's radius grows.
+    pub fn expansion_meters_per_second(&self) -> f32 {
+        self.expansion_meters_per_second
+    }
+
+    /// The ring observed in each scan ordered by time.
+    pub fn rings(&self) -> &Vec<RoostRing> {
+        self.rings.as_ref()
+    }
+
+    /// The estimated time the animals departed the roost in milliseconds since the UNIX epoch,
+    /// extrapolated back to when the ring's radius was zero.
+    pub fn departure_timestamp(&self) -> Option<i64> {
+        let first = self.rings.first()?;
+        if self.expansion_meters_per_second <= 0.0 {
+            return None;
+        }
+
+        let elapsed_seconds = first.radius_meters() / self.expansion_meters_per_second;
+        Some(first.collection_timestamp() - (elapsed_seconds * 1000.0) as i64)
+    }
+}
+
+impl Debug for ExpandingRoost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExpandingRoost")
+            .field("latitude_degrees", &self.latitude_degrees())
+            .field("longitude_degrees", &self.longitude_degrees())
+            .field(
+                "expansion_meters_per_second",
+                &self.expansion_meters_per_second(),
+            )
+            .field("departure_timestamp", &self.departure_timestamp())
+            .field("rings", &self.rings())
+            .finish()
+    }
+}
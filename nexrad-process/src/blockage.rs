@@ -0,0 +1,384 @@
+//!
+//! Beam blockage computation from a digital elevation model (DEM). Terrain which rises into the
+//! beam blocks a fraction of its power, biasing reflectivity low beyond the obstruction. The
+//! fraction of the beam's circular cross-section below the terrain is computed along each radial
+//! for a given elevation angle, accumulating with range since power blocked nearer the radar does
+//! not return. Blockage fractions are between 0 and 1, where 1 indicates the beam is totally
+//! blocked.
+//!
+//! A hybrid scan selects, at each location, the lowest elevation which is not substantially
+//! blocked and whose beam clears the terrain, as precipitation estimates require.
+//!
+
+mod dem;
+pub use dem::*;
+
+mod options;
+pub use options::*;
+
+use crate::field::PolarField;
+use crate::result::Result;
+use nexrad_model::data::{Radial, Scan};
+use nexrad_model::geometry::{
+    beam_height_meters, destination, ground_range_meters, slant_range_meters,
+};
+use nexrad_model::meta::Site;
+use std::f32::consts::PI;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Beam blockage for a set of elevations and the hybrid scan selected from them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HybridScan {
+    /// The cumulative blockage fraction for each elevation, in the order they were provided,
+    /// indexed by slant range.
+    pub blockage: Vec<PolarField>,
+
+    /// The elevation angle in degrees selected at each location, indexed by ground range. NaN
+    /// where no elevation meets the selection criteria.
+    pub elevation_angle: PolarField,
+
+    /// The cumulative blockage fraction of the selected elevation at each location, indexed by
+    /// ground range.
+    pub selected_blockage: PolarField,
+}
+
+/// Computes the cumulative beam blockage fraction for the given elevation angle in degrees from a
+/// radar site, indexed by slant range. Terrain outside of the DEM is assumed not to block the beam.
+pub fn beam_blockage(
+    site: &Site,
+    dem: &Dem,
+    elevation_angle_degrees: f32,
+    options: &BlockageOptions,
+) -> Result<PolarField> {
+    options.validate()?;
+
+    let mut blockage = empty_field(Some(elevation_angle_degrees), options);
+    let antenna_height = antenna_height_meters(site);
+    let half_beam_width = (options.beam_width_degrees / 2.0).to_radians();
+
+    for (radial_index, azimuth) in blockage.azimuths_degrees().clone().iter().enumerate() {
+        let mut cumulative = 0.0f32;
+        for gate_index in 0..blockage.gate_count() {
+            let range = blockage.gate_range_meters(gate_index);
+            let ground_range = ground_range_meters(range, elevation_angle_degrees);
+            let (latitude, longitude) =
+                destination(site.latitude(), site.longitude(), *azimuth, ground_range);
+
+            if let Some(terrain) = dem.height_at(latitude, longitude) {
+                let center = antenna_height + beam_height_meters(range, elevation_angle_degrees);
+                let radius = range * half_beam_width.tan();
+                cumulative = cumulative.max(blocked_fraction(terrain - center, radius));
+            }
+
+            blockage.set(radial_index, gate_index, cumulative);
+        }
+    }
+
+    Ok(blockage)
+}
+
+/// Computes beam blockage for each of the given elevation angles in degrees, such as those of a
+/// volume coverage pattern, and selects a hybrid scan from them.
+pub fn hybrid_scan(
+    site: &Site,
+    dem: &Dem,
+    elevation_angles_degrees: &[f32],
+    options: &BlockageOptions,
+) -> Result<HybridScan> {
+    options.validate()?;
+
+    let mut elevation_angles: Vec<(f32, PolarField)> = Vec::new();
+    for elevation_angle in elevation_angles_degrees {
+        elevation_angles.push((
+            *elevation_angle,
+            beam_blockage(site, dem, *elevation_angle, options)?,
+        ));
+    }
+
+    let mut ascending: Vec<&(f32, PolarField)> = elevation_angles.iter().collect();
+    ascending.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let mut elevation_angle = empty_field(None, options);
+    let mut selected_blockage = elevation_angle.empty_like();
+    let antenna_height = antenna_height_meters(site);
+    let half_beam_width = (options.beam_width_degrees / 2.0).to_radians();
+
+    for (radial_index, azimuth) in elevation_angle
+        .azimuths_degrees()
+        .clone()
+        .iter()
+        .enumerate()
+    {
+        for gate_index in 0..elevation_angle.gate_count() {
+            let ground_range = elevation_angle.gate_range_meters(gate_index);
+            let (latitude, longitude) =
+                destination(site.latitude(), site.longitude(), *azimuth, ground_range);
+            let terrain = dem.height_at(latitude, longitude);
+
+            let selected = ascending.iter().find_map(|(angle, blockage)| {
+                let range = slant_range_meters(ground_range, *angle)?;
+                let fraction = blockage.value_at(*azimuth, range)?;
+                if fraction > options.max_hybrid_blockage_fraction {
+                    return None;
+                }
+
+                let bottom = antenna_height + beam_height_meters(range, *angle)
+                    - range * half_beam_width.tan();
+                if terrain
+                    .is_some_and(|terrain| bottom - terrain < options.min_hybrid_clearance_meters)
+                {
+                    return None;
+                }
+
+                Some((*angle, fraction))
+            });
+
+            if let Some((angle, fraction)) = selected {
+                elevation_angle.set(radial_index, gate_index, angle);
+                selected_blockage.set(radial_index, gate_index, fraction);
+            }
+        }
+    }
+
+    Ok(HybridScan {
+        blockage: elevation_angles
+            .into_iter()
+            .map(|(_, blockage)| blockage)
+            .collect(),
+        elevation_angle,
+        selected_blockage,
+    })
+}
+
+/// Samples a scan's reflectivity according to a hybrid scan, taking each location's value from the
+/// sweep nearest in elevation to the one selected there. The result is indexed by ground range.
+pub fn hybrid_scan_reflectivity(scan: &Scan, hybrid_scan: &HybridScan) -> PolarField {
+    let sweeps: Vec<PolarField> = scan
+        .sweeps()
+        .iter()
+        .filter_map(|sweep| PolarField::from_sweep(sweep, Radial::reflectivity))
+        .collect();
+
+    let mut reflectivity = hybrid_scan.elevation_angle.empty_like();
+    for (radial_index, azimuth) in reflectivity.azimuths_degrees().clone().iter().enumerate() {
+        for gate_index in 0..reflectivity.gate_count() {
+            let selected_angle = match hybrid_scan.elevation_angle.value(radial_index, gate_index) {
+                Some(angle) => angle,
+                None => continue,
+            };
+
+            let sweep = sweeps.iter().min_by(|a, b| {
+                let difference = |field: &PolarField| {
+                    (field.elevation_angle_degrees().unwrap_or_default() - selected_angle).abs()
+                };
+                difference(a).total_cmp(&difference(b))
+            });
+
+            let ground_range = reflectivity.gate_range_meters(gate_index);
+            let value = sweep.and_then(|sweep| {
+                let range = slant_range_meters(
+                    ground_range,
+                    sweep.elevation_angle_degrees().unwrap_or_default(),
+                )?;
+                sweep.value_at(*azimuth, range)
+            });
+
+            if let Some(value) = value {
+                reflectivity.set(radial_index, gate_index, value);
+            }
+        }
+    }
+
+    reflectivity
+}
+
+/// The fraction of a beam's circular cross-section of the given radius in meters which lies below
+/// terrain the given height in meters above the beam's center.
+fn blocked_fraction(terrain_above_center: f32, radius: f32) -> f32 {
+    if radius <= 0.0 || terrain_above_center >= radius {
+        return if terrain_above_center >= 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    if terrain_above_center <= -radius {
+        return 0.0;
+    }
+
+    let y = terrain_above_center;
+    let area = y * (radius * radius - y * y).sqrt()
+        + radius * radius * (y / radius).asin()
+        + PI * radius * radius / 2.0;
+    (area / (PI * radius * radius)).clamp(0.0, 1.0)
+}
+
+/// The height of the radar antenna in meters above sea level.
+fn antenna_height_meters(site: &Site) -> f32 {
+    site.height_meters() as f32 + site.feedhorn_height_meters() as f32
+}
+
+/// An empty field spanning the configured azimuths and ranges.
+fn empty_field(elevation_angle_degrees: Option<f32>, options: &BlockageOptions) -> PolarField {
+    let radial_count = (360.0 / options.azimuth_spacing_degrees).round().max(1.0) as usize;
+    let gate_count = (options.max_range_meters / options.gate_interval_meters).floor() as usize;
+
+    PolarField::empty(
+        elevation_angle_degrees,
+        (0..radial_count)
+            .map(|index| (index as f32 + 0.5) * 360.0 / radial_count as f32)
+            .collect(),
+        options.gate_interval_meters / 2.0,
+        options.gate_interval_meters,
+        gate_count,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat terrain level with the radar site, with a 600 meter ridge 20 km east and a 900 meter
+    /// ridge 20 km north, each 2 km thick.
+    fn ridges(site: &Site) -> Dem {
+        let spacing = 0.005;
+        let (north, west) = (site.latitude() as f64 + 0.7, site.longitude() as f64 - 0.9);
+
+        let (rows, columns) = (281, 361);
+        let heights = (0..rows * columns)
+            .map(|index| {
+                let latitude = north - (index / columns) as f64 * spacing;
+                let longitude = west + (index % columns) as f64 * spacing;
+                let (east, north) = (
+                    (longitude - site.longitude() as f64)
+                        * 111_195.0
+                        * (site.latitude() as f64).to_radians().cos(),
+                    (latitude - site.latitude() as f64) * 111_195.0,
+                );
+
+                if (19_000.0..21_000.0).contains(&east) && north.abs() < 5_000.0 {
+                    600.0
+                } else if (19_000.0..21_000.0).contains(&north) && east.abs() < 5_000.0 {
+                    900.0
+                } else {
+                    site.height_meters() as f32
+                }
+            })
+            .collect();
+
+        Dem::new(north, west, spacing, spacing, columns, heights).unwrap()
+    }
+
+    #[cfg(feature = "tiff")]
+    #[test]
+    fn rejects_multi_band_geotiff() {
+        use std::io::Cursor;
+        use tiff::encoder::{colortype::RGB8, TiffEncoder};
+
+        let mut data = Cursor::new(Vec::new());
+        TiffEncoder::new(&mut data)
+            .unwrap()
+            .write_image::<RGB8>(2, 2, &[0; 12])
+            .unwrap();
+        data.set_position(0);
+
+        assert!(matches!(
+            Dem::from_geotiff(data),
+            Err(crate::result::Error::InvalidDem(_))
+        ));
+    }
+
+    #[test]
+    fn computes_blockage_and_hybrid_scan() {
+        let site = Site::new(*b"KTLX", 35.33, -97.28, 370, 20);
+        let dem = ridges(&site);
+        let options = BlockageOptions {
+            gate_interval_meters: 500.0,
+            max_range_meters: 70_000.0,
+            ..Default::default()
+        };
+
+        let blockage = beam_blockage(&site, &dem, 0.5, &options).unwrap();
+        assert_eq!(blockage.value_at(90.0, 10_000.0), Some(0.0));
+        let east = blockage.value_at(90.0, 40_000.0).unwrap();
+        assert!(0.2 < east && east < 0.9, "east blockage {east}");
+        assert_eq!(blockage.value_at(0.0, 40_000.0), Some(1.0));
+        assert_eq!(blockage.value_at(180.0, 40_000.0), Some(0.0));
+
+        let hybrid = hybrid_scan(&site, &dem, &[0.5, 2.4], &options).unwrap();
+        assert_eq!(hybrid.blockage.len(), 2);
+        assert_eq!(hybrid.elevation_angle.value_at(0.0, 40_000.0), Some(2.4));
+        assert_eq!(hybrid.elevation_angle.value_at(180.0, 60_000.0), Some(0.5));
+        assert_eq!(hybrid.selected_blockage.value_at(0.0, 40_000.0), Some(0.0));
+
+        // Near the radar, neither beam clears the terrain by enough to avoid clutter.
+        assert_eq!(hybrid.elevation_angle.value_at(180.0, 2_000.0), None);
+    }
+
+    #[test]
+    fn decodes_hgt() {
+        let mut data = Vec::new();
+        for height in [100i16, 200, 300, 400, -32768, 600, 700, 800, 900] {
+            data.extend(height.to_be_bytes());
+        }
+
+        let dem = Dem::from_hgt(&data, 35, -98).unwrap();
+        assert_eq!(dem.row_count(), 3);
+        assert_eq!(dem.north_latitude(), 36.0);
+        assert_eq!(dem.height_at(36.0, -98.0), Some(100.0));
+        assert_eq!(dem.height_at(35.0, -97.0), Some(900.0));
+        assert_eq!(dem.height_at(35.5, -97.5), None);
+        assert_eq!(dem.height_at(35.5, -97.25), Some(600.0));
+        // Voids are excluded from interpolation.
+        assert!((dem.height_at(35.75, -97.75).unwrap() - 233.3).abs() < 0.1);
+        assert_eq!(dem.height_at(37.0, -98.0), None);
+
+        let path = std::env::temp_dir().join("S12E045.hgt");
+        std::fs::write(&path, &data).unwrap();
+        let dem = Dem::from_hgt_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(dem.north_latitude(), -11.0);
+        assert_eq!(dem.west_longitude(), 45.0);
+
+        assert!(Dem::from_hgt(&data[..10], 35, -98).is_err());
+    }
+
+    #[cfg(feature = "tiff")]
+    #[test]
+    fn decodes_geotiff() {
+        use tiff::encoder::{colortype::Gray32Float, TiffEncoder};
+        use tiff::tags::Tag;
+
+        let mut data = std::io::Cursor::new(Vec::new());
+        let mut encoder = TiffEncoder::new(&mut data).unwrap();
+        let mut image = encoder.new_image::<Gray32Float>(3, 2).unwrap();
+        image
+            .encoder()
+            .write_tag(Tag::ModelPixelScaleTag, &[0.5, 0.25, 0.0][..])
+            .unwrap();
+        image
+            .encoder()
+            .write_tag(
+                Tag::ModelTiepointTag,
+                &[0.0, 0.0, 0.0, -98.0, 36.0, 0.0][..],
+            )
+            .unwrap();
+        image.encoder().write_tag(Tag::GdalNodata, "-9999").unwrap();
+        image
+            .write_data(&[100.0, 200.0, 300.0, 400.0, 500.0, -9999.0])
+            .unwrap();
+
+        data.set_position(0);
+        let dem = Dem::from_geotiff(data).unwrap();
+        assert_eq!(dem.column_count(), 3);
+        assert_eq!(dem.north_latitude(), 35.875);
+        assert_eq!(dem.west_longitude(), -97.75);
+        assert_eq!(dem.height_at(35.875, -97.75), Some(100.0));
+        assert_eq!(dem.height_at(35.625, -97.25), Some(500.0));
+        assert!(dem.heights_meters()[5].is_nan());
+    }
+}
@@ -0,0 +1,292 @@
+use crate::result::{Error, Result};
+use std::fmt::Debug;
+use std::path::Path;
+
+/// The height value marking voids in SRTM HGT files.
+const HGT_VOID: i16 = -32768;
+
+/// A digital elevation model (DEM) of terrain heights in meters above sea level on a regular grid
+/// of latitude and longitude. Rows are ordered from north to south and columns from west to east,
+/// with voids represented as NaN.
+#[derive(Clone, PartialEq)]
+pub struct Dem {
+    north_latitude: f64,
+    west_longitude: f64,
+    latitude_spacing_degrees: f64,
+    longitude_spacing_degrees: f64,
+    column_count: usize,
+    heights_meters: Vec<f32>,
+}
+
+impl Dem {
+    /// Create a new DEM whose northwestern sample is at the given latitude and longitude, with
+    /// heights ordered by row from north to south and then by column from west to east.
+    pub fn new(
+        north_latitude: f64,
+        west_longitude: f64,
+        latitude_spacing_degrees: f64,
+        longitude_spacing_degrees: f64,
+        column_count: usize,
+        heights_meters: Vec<f32>,
+    ) -> Result<Self> {
+        if latitude_spacing_degrees <= 0.0 || longitude_spacing_degrees <= 0.0 {
+            return Err(Error::InvalidDem("grid spacing must be positive"));
+        }
+
+        if column_count < 2
+            || !heights_meters.len().is_multiple_of(column_count)
+            || heights_meters.len() / column_count < 2
+        {
+            return Err(Error::InvalidDem(
+                "grid must have at least two rows and columns",
+            ));
+        }
+
+        Ok(Self {
+            north_latitude,
+            west_longitude,
+            latitude_spacing_degrees,
+            longitude_spacing_degrees,
+            column_count,
+            heights_meters,
+        })
+    }
+
+    /// Loads a DEM from an SRTM HGT file. The file's name must identify the latitude and longitude
+    /// of its southwestern corner, as in `N35W098.hgt`.
+    pub fn from_hgt_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .ok_or(Error::InvalidDem("HGT file name is not valid"))?;
+
+        let (south_latitude, west_longitude) =
+            parse_hgt_name(name).ok_or(Error::InvalidDem("HGT file name has no corner"))?;
+
+        Self::from_hgt(&std::fs::read(path)?, south_latitude, west_longitude)
+    }
+
+    /// Decodes a DEM from the contents of an SRTM HGT file covering the one degree square whose
+    /// southwestern corner is at the given latitude and longitude. Files contain a square grid of
+    /// big-endian 16-bit heights, such as 1201 samples across for 3 arc-second data or 3601 for 1
+    /// arc-second data.
+    pub fn from_hgt(data: &[u8], south_latitude: i32, west_longitude: i32) -> Result<Self> {
+        let size = ((data.len() / 2) as f64).sqrt() as usize;
+        if size < 2 || size * size * 2 != data.len() {
+            return Err(Error::InvalidDem("HGT data is not a square grid"));
+        }
+
+        let heights = data
+            .chunks_exact(2)
+            .map(|bytes| match i16::from_be_bytes([bytes[0], bytes[1]]) {
+                HGT_VOID => f32::NAN,
+                height => height as f32,
+            })
+            .collect();
+
+        let spacing = 1.0 / (size - 1) as f64;
+        Self::new(
+            south_latitude as f64 + 1.0,
+            west_longitude as f64,
+            spacing,
+            spacing,
+            size,
+            heights,
+        )
+    }
+
+    /// Loads a DEM from a GeoTIFF file in geographic coordinates.
+    #[cfg(feature = "tiff")]
+    pub fn from_geotiff_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_geotiff(std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+
+    /// Decodes a DEM from a single-band GeoTIFF in geographic coordinates, with heights in meters
+    /// above sea level. The raster is located by its pixel scale and tie point tags, and any GDAL
+    /// no-data value is treated as a void.
+    #[cfg(feature = "tiff")]
+    pub fn from_geotiff<R: std::io::Read + std::io::Seek>(reader: R) -> Result<Self> {
+        use tiff::decoder::{Decoder, DecodingResult};
+        use tiff::tags::Tag;
+
+        /// The GeoTIFF key identifying whether raster coordinates refer to pixel corners or
+        /// centers, and its value for centers.
+        const RASTER_TYPE_KEY: u16 = 1025;
+        const RASTER_PIXEL_IS_POINT: u16 = 2;
+
+        let mut decoder = Decoder::new(reader)?;
+        let (width, _) = decoder.dimensions()?;
+
+        let samples_per_pixel = decoder
+            .find_tag_unsigned::<u16>(Tag::SamplesPerPixel)?
+            .unwrap_or(1);
+        if samples_per_pixel != 1 {
+            return Err(Error::InvalidDem("GeoTIFF must have a single band"));
+        }
+
+        let scale = decoder.get_tag_f64_vec(Tag::ModelPixelScaleTag)?;
+        let tie_point = decoder.get_tag_f64_vec(Tag::ModelTiepointTag)?;
+        if scale.len() < 2 || tie_point.len() < 6 {
+            return Err(Error::InvalidDem("GeoTIFF is missing georeferencing"));
+        }
+
+        let pixel_is_point = decoder
+            .find_tag_unsigned_vec::<u16>(Tag::GeoKeyDirectoryTag)?
+            .is_some_and(|keys| {
+                keys.chunks_exact(4).skip(1).any(|key| {
+                    key[0] == RASTER_TYPE_KEY && key[1] == 0 && key[3] == RASTER_PIXEL_IS_POINT
+                })
+            });
+
+        let no_data = match decoder.find_tag(Tag::GdalNodata)? {
+            Some(value) => value.into_string()?.trim().parse::<f64>().ok(),
+            None => None,
+        };
+
+        let heights: Vec<f64> = match decoder.read_image()? {
+            DecodingResult::U8(values) => values.into_iter().map(f64::from).collect(),
+            DecodingResult::U16(values) => values.into_iter().map(f64::from).collect(),
+            DecodingResult::U32(values) => values.into_iter().map(f64::from).collect(),
+            DecodingResult::U64(values) => values.into_iter().map(|value| value as f64).collect(),
+            DecodingResult::F32(values) => values.into_iter().map(f64::from).collect(),
+            DecodingResult::F64(values) => values,
+            DecodingResult::I8(values) => values.into_iter().map(f64::from).collect(),
+            DecodingResult::I16(values) => values.into_iter().map(f64::from).collect(),
+            DecodingResult::I32(values) => values.into_iter().map(f64::from).collect(),
+            DecodingResult::I64(values) => values.into_iter().map(|value| value as f64).collect(),
+        };
+
+        // Tie points for area rasters locate the corner of a pixel rather than its center.
+        let half_pixel = if pixel_is_point { 0.0 } else { 0.5 };
+        let (column, row) = (tie_point[0] - half_pixel, tie_point[1] - half_pixel);
+
+        Self::new(
+            tie_point[4] + row * scale[1],
+            tie_point[3] - column * scale[0],
+            scale[1],
+            scale[0],
+            width as usize,
+            heights
+                .into_iter()
+                .map(|height| {
+                    if no_data == Some(height) {
+                        f32::NAN
+                    } else {
+                        height as f32
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// The number of rows of samples from north to south.
+    pub fn row_count(&self) -> usize {
+        self.heights_meters.len() / self.column_count
+    }
+
+    /// The number of columns of samples from west to east.
+    pub fn column_count(&self) -> usize {
+        self.column_count
+    }
+
+    /// The latitude of the northernmost row of samples.
+    pub fn north_latitude(&self) -> f64 {
+        self.north_latitude
+    }
+
+    /// The longitude of the westernmost column of samples.
+    pub fn west_longitude(&self) -> f64 {
+        self.west_longitude
+    }
+
+    /// The distance in degrees of latitude between rows.
+    pub fn latitude_spacing_degrees(&self) -> f64 {
+        self.latitude_spacing_degrees
+    }
+
+    /// The distance in degrees of longitude between columns.
+    pub fn longitude_spacing_degrees(&self) -> f64 {
+        self.longitude_spacing_degrees
+    }
+
+    /// All of this model's heights in meters ordered by row and then column.
+    pub fn heights_meters(&self) -> &Vec<f32> {
+        self.heights_meters.as_ref()
+    }
+
+    /// The terrain height in meters above sea level at the given latitude and longitude,
+    /// interpolated bilinearly from the surrounding samples which are not voids. Returns [None]
+    /// outside of the model or where all surrounding samples are voids.
+    pub fn height_at(&self, latitude: f32, longitude: f32) -> Option<f32> {
+        let row = (self.north_latitude - latitude as f64) / self.latitude_spacing_degrees;
+        let column = (longitude as f64 - self.west_longitude) / self.longitude_spacing_degrees;
+        if row < 0.0
+            || column < 0.0
+            || row > (self.row_count() - 1) as f64
+            || column > (self.column_count - 1) as f64
+        {
+            return None;
+        }
+
+        let (top, left) = (
+            (row.floor() as usize).min(self.row_count() - 2),
+            (column.floor() as usize).min(self.column_count - 2),
+        );
+        let (row_fraction, column_fraction) = (row - top as f64, column - left as f64);
+
+        let mut weight = 0.0;
+        let mut total = 0.0;
+        for (row_offset, row_weight) in [(0, 1.0 - row_fraction), (1, row_fraction)] {
+            for (column_offset, column_weight) in [(0, 1.0 - column_fraction), (1, column_fraction)]
+            {
+                let height = self.heights_meters
+                    [(top + row_offset) * self.column_count + left + column_offset];
+                if !height.is_nan() {
+                    weight += row_weight * column_weight;
+                    total += row_weight * column_weight * height as f64;
+                }
+            }
+        }
+
+        (weight > 0.0).then(|| (total / weight) as f32)
+    }
+}
+
+/// Parses the southwestern corner latitude and longitude from an HGT file name such as `N35W098`.
+fn parse_hgt_name(name: &str) -> Option<(i32, i32)> {
+    let name = name.to_ascii_uppercase();
+    let longitude_start = name.find(['E', 'W'])?;
+    let (latitude, longitude) = name.split_at(longitude_start);
+
+    let parse = |value: &str, negative: char| -> Option<i32> {
+        let magnitude: i32 = value.get(1..)?.parse().ok()?;
+        Some(if value.starts_with(negative) {
+            -magnitude
+        } else {
+            magnitude
+        })
+    };
+
+    if !latitude.starts_with(['N', 'S']) {
+        return None;
+    }
+
+    Some((parse(latitude, 'S')?, parse(longitude.get(..4)?, 'W')?))
+}
+
+impl Debug for Dem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dem")
+            .field("north_latitude", &self.north_latitude())
+            .field("west_longitude", &self.west_longitude())
+            .field("latitude_spacing_degrees", &self.latitude_spacing_degrees())
+            .field(
+                "longitude_spacing_degrees",
+                &self.longitude_spacing_degrees(),
+            )
+            .field("row_count", &self.row_count())
+            .field("column_count", &self.column_count())
+            .finish()
+    }
+}
@@ -0,0 +1,59 @@
+use crate::result::{Error, Result};
+
+/// Options controlling beam blockage computation and hybrid scan selection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockageOptions {
+    /// The antenna's half-power beam width in degrees.
+    pub beam_width_degrees: f32,
+
+    /// The azimuthal spacing in degrees of blockage fields.
+    pub azimuth_spacing_degrees: f32,
+
+    /// The distance in meters between gates of blockage fields.
+    pub gate_interval_meters: f32,
+
+    /// The maximum range in meters of blockage fields.
+    pub max_range_meters: f32,
+
+    /// The largest blockage fraction, from 0 to 1, of an elevation which may be selected for the
+    /// hybrid scan.
+    pub max_hybrid_blockage_fraction: f32,
+
+    /// The minimum height in meters of the bottom of the beam above terrain for an elevation to be
+    /// selected for the hybrid scan, avoiding ground clutter.
+    pub min_hybrid_clearance_meters: f32,
+}
+
+impl BlockageOptions {
+    /// Checks that these options are usable for a blockage computation.
+    pub fn validate(&self) -> Result<()> {
+        if self.beam_width_degrees <= 0.0 {
+            return Err(Error::InvalidOptions("beam width must be positive"));
+        }
+
+        if self.azimuth_spacing_degrees <= 0.0 || self.gate_interval_meters <= 0.0 {
+            return Err(Error::InvalidOptions("field spacing must be positive"));
+        }
+
+        if !(0.0..=1.0).contains(&self.max_hybrid_blockage_fraction) {
+            return Err(Error::InvalidOptions(
+                "maximum hybrid blockage fraction must be between 0 and 1",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BlockageOptions {
+    fn default() -> Self {
+        Self {
+            beam_width_degrees: 0.95,
+            azimuth_spacing_degrees: 1.0,
+            gate_interval_meters: 250.0,
+            max_range_meters: 230_000.0,
+            max_hybrid_blockage_fraction: 0.5,
+            min_hybrid_clearance_meters: 150.0,
+        }
+    }
+}
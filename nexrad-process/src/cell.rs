@@ -0,0 +1,387 @@
+//!
+//! Storm cell identification and tracking in the spirit of the NWS Storm Cell Identification and
+//! Tracking (SCIT) algorithm. Within each sweep, connected regions of reflectivity at or above a
+//! series of thresholds form components, with the strongest cores taking precedence over the
+//! weaker echo surrounding them. Components from successive sweeps which are vertically aligned
+//! are stacked into three-dimensional cells with a centroid, base, top, maximum reflectivity and
+//! vertically integrated liquid (VIL).
+//!
+//! A [CellTracker] matches cells across successive scans to estimate their motion and forecast
+//! their future positions.
+//!
+
+mod options;
+pub use options::*;
+
+mod storm;
+pub use storm::*;
+
+mod tracker;
+pub use tracker::*;
+
+use crate::association::{east_north, stack_levels};
+use crate::field::PolarField;
+use crate::regions::connected_regions;
+use crate::result::Result;
+use nexrad_model::data::{Radial, Scan, Sweep, ELEVATION_TOLERANCE_DEGREES};
+use nexrad_model::geometry::{beam_height_meters, destination, ground_range_meters};
+use nexrad_model::meta::Site;
+use std::f32::consts::PI;
+
+/// Identifies storm cells in a scan, locating them relative to the given radar site.
+pub fn identify_cells(scan: &Scan, site: &Site, options: &CellOptions) -> Result<Vec<StormCell>> {
+    options.validate()?;
+
+    // Only the first sweep with reflectivity at each elevation contributes components.
+    let mut levels: Vec<Vec<CellComponent>> = Vec::new();
+    for sweeps in scan.sweeps_by_elevation_angle(ELEVATION_TOLERANCE_DEGREES) {
+        let sweep = sweeps.into_iter().find(|sweep| {
+            sweep
+                .radials()
+                .iter()
+                .any(|radial| radial.reflectivity().is_some())
+        });
+
+        if let Some(sweep) = sweep {
+            levels.push(sweep_components(sweep, options)?);
+        }
+    }
+
+    Ok(associate(levels, site, options))
+}
+
+/// Finds the components of storm cells in a single sweep's reflectivity data.
+pub fn sweep_components(sweep: &Sweep, options: &CellOptions) -> Result<Vec<CellComponent>> {
+    options.validate()?;
+
+    let reflectivity = match PolarField::from_sweep(sweep, Radial::reflectivity) {
+        Some(reflectivity) => reflectivity,
+        None => return Ok(Vec::new()),
+    };
+
+    let collection_timestamp = sweep
+        .radials()
+        .iter()
+        .map(|radial| radial.collection_timestamp())
+        .min()
+        .unwrap_or_default();
+
+    let elevation_angle = reflectivity.elevation_angle_degrees().unwrap_or_default();
+    let gate_count = reflectivity.gate_count();
+    let azimuth_spacing = 2.0 * PI / reflectivity.radial_count().max(1) as f32;
+
+    let mut thresholds = options.reflectivity_thresholds_dbz.clone();
+    thresholds.sort_by(|a, b| b.total_cmp(a));
+
+    let mut claimed = vec![false; reflectivity.values().len()];
+    let mut components = Vec::new();
+
+    for threshold in thresholds {
+        let flagged: Vec<bool> = reflectivity
+            .values()
+            .iter()
+            .map(|value| *value >= threshold)
+            .collect();
+
+        for region in connected_regions(reflectivity.radial_count(), gate_count, &flagged) {
+            if region
+                .iter()
+                .any(|(radial_index, gate_index)| claimed[radial_index * gate_count + gate_index])
+            {
+                continue;
+            }
+
+            let mut area = 0.0;
+            let mut weight = 0.0;
+            let mut east = 0.0;
+            let mut north = 0.0;
+            let mut slant_range = 0.0;
+            let mut max_reflectivity = f32::MIN;
+
+            for (radial_index, gate_index) in &region {
+                let value = match reflectivity.value(*radial_index, *gate_index) {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                let range = reflectivity.gate_range_meters(*gate_index);
+                let gate_area = range * azimuth_spacing * reflectivity.gate_interval_meters();
+                let gate_weight = liquid_water(value) * gate_area as f64;
+
+                let (sin, cos) = reflectivity.azimuths_degrees()[*radial_index]
+                    .to_radians()
+                    .sin_cos();
+                let ground_range = ground_range_meters(range, elevation_angle) as f64;
+
+                area += gate_area;
+                weight += gate_weight;
+                east += gate_weight * ground_range * sin as f64;
+                north += gate_weight * ground_range * cos as f64;
+                slant_range += gate_weight * range as f64;
+                max_reflectivity = max_reflectivity.max(value);
+            }
+
+            if area < options.min_component_area_square_meters || weight <= 0.0 {
+                continue;
+            }
+
+            for (radial_index, gate_index) in &region {
+                claimed[radial_index * gate_count + gate_index] = true;
+            }
+
+            let (east, north) = (east / weight, north / weight);
+            let slant_range = (slant_range / weight) as f32;
+            components.push(CellComponent::new(
+                sweep.elevation_number(),
+                elevation_angle,
+                collection_timestamp,
+                threshold,
+                east.atan2(north).to_degrees().rem_euclid(360.0) as f32,
+                east.hypot(north) as f32,
+                beam_height_meters(slant_range, elevation_angle),
+                area,
+                max_reflectivity,
+            ));
+        }
+    }
+
+    Ok(components)
+}
+
+/// Stacks components from successive levels, ordered by ascending elevation, into cells.
+fn associate(
+    levels: Vec<Vec<CellComponent>>,
+    site: &Site,
+    options: &CellOptions,
+) -> Vec<StormCell> {
+    let levels = levels.into_iter().map(|mut components| {
+        components.sort_by(|a, b| b.area_square_meters().total_cmp(&a.area_square_meters()));
+        components
+    });
+
+    stack_levels(
+        levels,
+        &options.association_distances_meters,
+        true,
+        position,
+    )
+    .into_iter()
+    .filter(|stack| stack.len() >= options.min_components)
+    .map(|stack| {
+        let area = stack
+            .iter()
+            .map(|component| component.area_square_meters())
+            .sum::<f32>();
+        let (east, north) = stack.iter().fold((0.0, 0.0), |(east, north), component| {
+            let (component_east, component_north) = position(component);
+            let weight = component.area_square_meters() / area;
+            (
+                east + weight * component_east,
+                north + weight * component_north,
+            )
+        });
+
+        let azimuth = east.atan2(north).to_degrees().rem_euclid(360.0);
+        let ground_range = east.hypot(north);
+        let (latitude, longitude) =
+            destination(site.latitude(), site.longitude(), azimuth, ground_range);
+
+        let vil = vertically_integrated_liquid(&stack, options.max_liquid_reflectivity_dbz);
+        StormCell::new(latitude, longitude, azimuth, ground_range, vil, stack)
+    })
+    .collect()
+}
+
+/// Computes vertically integrated liquid in kilograms per square meter from the largest
+/// reflectivity in each of a cell's components.
+fn vertically_integrated_liquid(components: &[CellComponent], max_reflectivity_dbz: f32) -> f32 {
+    let mut profile: Vec<(f32, f32)> = components
+        .iter()
+        .map(|component| {
+            let reflectivity = component.max_reflectivity_dbz().min(max_reflectivity_dbz);
+            (component.height_meters(), 10.0f32.powf(reflectivity / 10.0))
+        })
+        .collect();
+    profile.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    profile
+        .windows(2)
+        .map(|layer| {
+            let ((bottom, bottom_reflectivity), (top, top_reflectivity)) = (layer[0], layer[1]);
+            3.44e-6
+                * ((bottom_reflectivity + top_reflectivity) / 2.0).powf(4.0 / 7.0)
+                * (top - bottom)
+        })
+        .sum()
+}
+
+/// A relative liquid water content for the given reflectivity in dBZ, used to weight centroids.
+fn liquid_water(reflectivity_dbz: f32) -> f64 {
+    10.0f64.powf(reflectivity_dbz as f64 / 10.0).powf(4.0 / 7.0)
+}
+
+/// The eastward and northward distance in meters from the radar to a component's centroid.
+fn position(component: &CellComponent) -> (f32, f32) {
+    east_north(component.azimuth_degrees(), component.ground_range_meters())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::Product;
+
+    /// A scan taken at the given time in seconds containing cylindrical storms, each given by its
+    /// eastward and northward position in meters, radius in meters and reflectivity in dBZ. Each
+    /// storm extends from the ground to 10 km.
+    fn storm_scan(time_seconds: i64, storms: &[((f32, f32), f32, f32)]) -> Scan {
+        storm_scan_at(
+            time_seconds,
+            &[0.5, 1.5, 2.4, 3.4, 4.3, 6.0, 8.0, 10.0],
+            storms,
+        )
+    }
+
+    /// A scan as [storm_scan] produces with sweeps at the given elevation angles in degrees.
+    fn storm_scan_at(
+        time_seconds: i64,
+        elevations: &[f32],
+        storms: &[((f32, f32), f32, f32)],
+    ) -> Scan {
+        let sweeps = elevations
+            .iter()
+            .enumerate()
+            .map(|(elevation_index, elevation_angle)| {
+                let radials = (0..720)
+                    .map(|azimuth_number| {
+                        let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                        let (sin, cos) = azimuth.to_radians().sin_cos();
+
+                        let values = (0..400)
+                            .map(|gate| {
+                                let slant_range = 2_000.0 + gate as f32 * 250.0;
+                                let ground_range =
+                                    ground_range_meters(slant_range, *elevation_angle);
+                                if beam_height_meters(slant_range, *elevation_angle) > 10_000.0 {
+                                    return 0;
+                                }
+
+                                storms
+                                    .iter()
+                                    .filter(|((east, north), radius, _)| {
+                                        (ground_range * sin - east)
+                                            .hypot(ground_range * cos - north)
+                                            < *radius
+                                    })
+                                    .map(|(_, _, reflectivity)| {
+                                        (reflectivity * 2.0 + 66.0).round() as u8
+                                    })
+                                    .max()
+                                    .unwrap_or(0)
+                            })
+                            .collect();
+
+                        radial(
+                            time_seconds * 1000 + elevation_index as i64 * 20_000,
+                            azimuth_number,
+                            0.5,
+                            elevation_index as u8 + 1,
+                            *elevation_angle,
+                        )
+                        .with_moment(
+                            Product::Reflectivity,
+                            moment(&Product::Reflectivity, values),
+                        )
+                    })
+                    .collect();
+
+                Sweep::new(elevation_index as u8 + 1, radials)
+            })
+            .collect();
+
+        Scan::new(212, sweeps)
+    }
+
+    fn site() -> Site {
+        Site::new(*b"KTLX", 35.33, -97.28, 370, 20)
+    }
+
+    #[test]
+    fn identifies_cells() {
+        let scan = storm_scan(
+            0,
+            &[
+                ((30_000.0, 0.0), 5_000.0, 55.0),
+                ((0.0, -40_000.0), 4_000.0, 45.0),
+            ],
+        );
+
+        let mut cells = identify_cells(&scan, &site(), &CellOptions::default()).unwrap();
+        cells.sort_by(|a, b| a.azimuth_degrees().total_cmp(&b.azimuth_degrees()));
+        assert_eq!(cells.len(), 2);
+
+        let east = &cells[0];
+        assert!((east.azimuth_degrees() - 90.0).abs() < 1.0);
+        assert!((east.ground_range_meters() - 30_000.0).abs() < 500.0);
+        assert_eq!(east.max_reflectivity_dbz(), Some(55.0));
+        assert_eq!(east.components().len(), 8);
+        assert!(east.top_height_meters().unwrap() > 5_000.0);
+
+        let south = &cells[1];
+        assert!((south.azimuth_degrees() - 180.0).abs() < 1.0);
+        assert!(
+            east.vertically_integrated_liquid_kilograms_per_square_meter()
+                > south.vertically_integrated_liquid_kilograms_per_square_meter()
+        );
+    }
+
+    #[test]
+    fn uses_one_sweep_per_elevation() {
+        // The two lowest sweeps are within the model's elevation tolerance of each other.
+        let scan = storm_scan_at(0, &[0.5, 0.72, 1.5], &[((30_000.0, 0.0), 5_000.0, 55.0)]);
+
+        let cells = identify_cells(&scan, &site(), &CellOptions::default()).unwrap();
+        assert_eq!(cells.len(), 1);
+
+        let elevations: Vec<f32> = cells[0]
+            .components()
+            .iter()
+            .map(CellComponent::elevation_angle_degrees)
+            .collect();
+        assert_eq!(elevations, vec![0.5, 1.5]);
+    }
+
+    #[test]
+    fn tracks_cells() {
+        let mut tracker = CellTracker::new(
+            site(),
+            CellOptions::default(),
+            CellTrackerOptions::default(),
+        )
+        .unwrap();
+
+        // A storm moving east at 10 meters per second, observed every five minutes.
+        for scan_number in 0..3 {
+            let time = scan_number * 300;
+            let east = 30_000.0 + 10.0 * time as f32;
+            let scan = storm_scan(time, &[((east, 20_000.0), 5_000.0, 55.0)]);
+            tracker.update(&scan).unwrap();
+        }
+
+        let tracks = tracker.tracks();
+        assert_eq!(tracks.len(), 1);
+
+        let track = &tracks[0];
+        assert_eq!(track.cells().len(), 3);
+        assert!((track.motion_direction_degrees().unwrap() - 90.0).abs() < 5.0);
+        assert!((track.motion_speed_meters_per_second().unwrap() - 10.0).abs() < 1.5);
+
+        let latest = track.latest().unwrap();
+        let forecast = track.forecast_positions().last().unwrap();
+        assert_eq!(forecast.lead_time_seconds(), 3_600.0);
+        assert!(forecast.longitude_degrees() > latest.longitude_degrees() + 0.3);
+
+        let earlier = storm_scan(0, &[((30_000.0, 20_000.0), 5_000.0, 55.0)]);
+        assert!(tracker.update(&earlier).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::result::{Error, Result};
+
+/// Options controlling storm cell identification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellOptions {
+    /// The reflectivity thresholds in dBZ used to find components within each sweep. Components
+    /// found at higher thresholds take precedence over the lower-threshold regions containing them,
+    /// which allows neighboring cores within a larger echo to be identified as separate cells.
+    pub reflectivity_thresholds_dbz: Vec<f32>,
+
+    /// The minimum area in square meters of a component.
+    pub min_component_area_square_meters: f32,
+
+    /// The search distances in meters, in the order tried, within which a component is associated
+    /// with a cell's component from the next lower sweep.
+    pub association_distances_meters: Vec<f32>,
+
+    /// The minimum number of components, each from a different sweep, comprising a cell.
+    pub min_components: usize,
+
+    /// The reflectivity in dBZ at which reflectivity is capped when computing vertically integrated
+    /// liquid (VIL), limiting the contribution of hail.
+    pub max_liquid_reflectivity_dbz: f32,
+}
+
+impl CellOptions {
+    /// Checks that these options are usable for identification.
+    pub fn validate(&self) -> Result<()> {
+        if self.reflectivity_thresholds_dbz.is_empty() {
+            return Err(Error::InvalidOptions(
+                "at least one reflectivity threshold is required",
+            ));
+        }
+
+        if self.association_distances_meters.is_empty()
+            || self
+                .association_distances_meters
+                .iter()
+                .any(|distance| *distance <= 0.0)
+        {
+            return Err(Error::InvalidOptions(
+                "association distances must be positive",
+            ));
+        }
+
+        if self.min_components == 0 {
+            return Err(Error::InvalidOptions("at least one component is required"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for CellOptions {
+    fn default() -> Self {
+        Self {
+            reflectivity_thresholds_dbz: vec![60.0, 55.0, 50.0, 45.0, 40.0, 35.0, 30.0],
+            min_component_area_square_meters: 10_000_000.0,
+            association_distances_meters: vec![5_000.0, 7_500.0, 10_000.0],
+            min_components: 2,
+            max_liquid_reflectivity_dbz: 56.0,
+        }
+    }
+}
+
+/// Options controlling how cells are tracked across scans.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellTrackerOptions {
+    /// The fastest speed in meters per second a cell is expected to move. A cell is only matched to
+    /// a track if it lies within the distance this speed would cover of the track's predicted
+    /// position.
+    pub max_speed_meters_per_second: f32,
+
+    /// The longest time in seconds between scans for tracks to be continued. Tracks are ended if a
+    /// longer gap occurs.
+    pub max_time_gap_seconds: f32,
+
+    /// The largest number of recent positions used to estimate a track's motion.
+    pub motion_history: usize,
+
+    /// The lead times in seconds at which forecast positions are produced for each track.
+    pub forecast_lead_times_seconds: Vec<f32>,
+}
+
+impl CellTrackerOptions {
+    /// Checks that these options are usable for tracking.
+    pub fn validate(&self) -> Result<()> {
+        if self.max_speed_meters_per_second <= 0.0 || self.max_time_gap_seconds <= 0.0 {
+            return Err(Error::InvalidOptions(
+                "maximum speed and time gap must be positive",
+            ));
+        }
+
+        if self.motion_history < 2 {
+            return Err(Error::InvalidOptions(
+                "at least two positions are required to estimate motion",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for CellTrackerOptions {
+    fn default() -> Self {
+        Self {
+            max_speed_meters_per_second: 35.0,
+            max_time_gap_seconds: 1_200.0,
+            motion_history: 10,
+            forecast_lead_times_seconds: vec![900.0, 1_800.0, 2_700.0, 3_600.0],
+        }
+    }
+}
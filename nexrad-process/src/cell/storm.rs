@@ -0,0 +1,246 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A region of reflectivity at or above a threshold within a single sweep, forming a horizontal
+/// slice of a storm cell.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellComponent {
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+    collection_timestamp: i64,
+    threshold_dbz: f32,
+    azimuth_degrees: f32,
+    ground_range_meters: f32,
+    height_meters: f32,
+    area_square_meters: f32,
+    max_reflectivity_dbz: f32,
+}
+
+impl CellComponent {
+    /// Create a new cell component with the given properties.
+    pub fn new(
+        elevation_number: u8,
+        elevation_angle_degrees: f32,
+        collection_timestamp: i64,
+        threshold_dbz: f32,
+        azimuth_degrees: f32,
+        ground_range_meters: f32,
+        height_meters: f32,
+        area_square_meters: f32,
+        max_reflectivity_dbz: f32,
+    ) -> Self {
+        Self {
+            elevation_number,
+            elevation_angle_degrees,
+            collection_timestamp,
+            threshold_dbz,
+            azimuth_degrees,
+            ground_range_meters,
+            height_meters,
+            area_square_meters,
+            max_reflectivity_dbz,
+        }
+    }
+
+    /// The elevation number of the sweep this component was found in.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// The mean elevation angle in degrees of the sweep this component was found in.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// The collection timestamp of the sweep this component was found in, in milliseconds since
+    /// the UNIX epoch.
+    pub fn collection_timestamp(&self) -> i64 {
+        self.collection_timestamp
+    }
+
+    /// The reflectivity threshold in dBZ this component was found at.
+    pub fn threshold_dbz(&self) -> f32 {
+        self.threshold_dbz
+    }
+
+    /// The azimuth in degrees of this component's mass-weighted centroid.
+    pub fn azimuth_degrees(&self) -> f32 {
+        self.azimuth_degrees
+    }
+
+    /// The distance along the ground in meters from the radar to this component's mass-weighted
+    /// centroid.
+    pub fn ground_range_meters(&self) -> f32 {
+        self.ground_range_meters
+    }
+
+    /// The height in meters above the radar of this component's mass-weighted centroid.
+    pub fn height_meters(&self) -> f32 {
+        self.height_meters
+    }
+
+    /// The area covered by this component in square meters.
+    pub fn area_square_meters(&self) -> f32 {
+        self.area_square_meters
+    }
+
+    /// The largest reflectivity in dBZ within this component.
+    pub fn max_reflectivity_dbz(&self) -> f32 {
+        self.max_reflectivity_dbz
+    }
+}
+
+impl Debug for CellComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CellComponent")
+            .field("elevation_number", &self.elevation_number())
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("collection_timestamp", &self.collection_timestamp())
+            .field("threshold_dbz", &self.threshold_dbz())
+            .field("azimuth_degrees", &self.azimuth_degrees())
+            .field("ground_range_meters", &self.ground_range_meters())
+            .field("height_meters", &self.height_meters())
+            .field("area_square_meters", &self.area_square_meters())
+            .field("max_reflectivity_dbz", &self.max_reflectivity_dbz())
+            .finish()
+    }
+}
+
+/// A storm cell composed of vertically stacked components from successive sweeps of a scan.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StormCell {
+    latitude_degrees: f32,
+    longitude_degrees: f32,
+    azimuth_degrees: f32,
+    ground_range_meters: f32,
+    vertically_integrated_liquid_kilograms_per_square_meter: f32,
+    components: Vec<CellComponent>,
+}
+
+impl StormCell {
+    /// Create a new storm cell from its components, which should be ordered by ascending
+    /// elevation.
+    pub fn new(
+        latitude_degrees: f32,
+        longitude_degrees: f32,
+        azimuth_degrees: f32,
+        ground_range_meters: f32,
+        vertically_integrated_liquid_kilograms_per_square_meter: f32,
+        components: Vec<CellComponent>,
+    ) -> Self {
+        Self {
+            latitude_degrees,
+            longitude_degrees,
+            azimuth_degrees,
+            ground_range_meters,
+            vertically_integrated_liquid_kilograms_per_square_meter,
+            components,
+        }
+    }
+
+    /// The latitude in degrees of this cell's centroid.
+    pub fn latitude_degrees(&self) -> f32 {
+        self.latitude_degrees
+    }
+
+    /// The longitude in degrees of this cell's centroid.
+    pub fn longitude_degrees(&self) -> f32 {
+        self.longitude_degrees
+    }
+
+    /// The azimuth in degrees from the radar to this cell's centroid.
+    pub fn azimuth_degrees(&self) -> f32 {
+        self.azimuth_degrees
+    }
+
+    /// The distance along the ground in meters from the radar to this cell's centroid.
+    pub fn ground_range_meters(&self) -> f32 {
+        self.ground_range_meters
+    }
+
+    /// The cell's vertically integrated liquid (VIL) in kilograms per square meter, computed from
+    /// the largest reflectivity in each of its components.
+    pub fn vertically_integrated_liquid_kilograms_per_square_meter(&self) -> f32 {
+        self.vertically_integrated_liquid_kilograms_per_square_meter
+    }
+
+    /// The per-sweep components comprising this cell ordered by ascending elevation.
+    pub fn components(&self) -> &Vec<CellComponent> {
+        self.components.as_ref()
+    }
+
+    /// The collection timestamp of this cell's base in milliseconds since the UNIX epoch.
+    pub fn collection_timestamp(&self) -> Option<i64> {
+        self.components
+            .first()
+            .map(|component| component.collection_timestamp())
+    }
+
+    /// The height in meters above the radar of this cell's lowest component.
+    pub fn base_height_meters(&self) -> Option<f32> {
+        self.components
+            .iter()
+            .map(|component| component.height_meters())
+            .reduce(f32::min)
+    }
+
+    /// The height in meters above the radar of this cell's highest component.
+    pub fn top_height_meters(&self) -> Option<f32> {
+        self.components
+            .iter()
+            .map(|component| component.height_meters())
+            .reduce(f32::max)
+    }
+
+    /// The largest reflectivity in dBZ within any of this cell's components.
+    pub fn max_reflectivity_dbz(&self) -> Option<f32> {
+        self.strongest_component()
+            .map(|component| component.max_reflectivity_dbz())
+    }
+
+    /// The height in meters above the radar of the component containing this cell's largest
+    /// reflectivity.
+    pub fn max_reflectivity_height_meters(&self) -> Option<f32> {
+        self.strongest_component()
+            .map(|component| component.height_meters())
+    }
+
+    /// The largest reflectivity in dBZ in each of this cell's components paired with the
+    /// component's height in meters above the radar, suitable for [crate::hail::hail_estimate].
+    pub fn reflectivity_profile(&self) -> Vec<(f32, f32)> {
+        self.components
+            .iter()
+            .map(|component| (component.height_meters(), component.max_reflectivity_dbz()))
+            .collect()
+    }
+
+    fn strongest_component(&self) -> Option<&CellComponent> {
+        self.components.iter().max_by(|a, b| {
+            a.max_reflectivity_dbz()
+                .total_cmp(&b.max_reflectivity_dbz())
+        })
+    }
+}
+
+impl Debug for StormCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StormCell")
+            .field("latitude_degrees", &self.latitude_degrees())
+            .field("longitude_degrees", &self.longitude_degrees())
+            .field("azimuth_degrees", &self.azimuth_degrees())
+            .field("ground_range_meters", &self.ground_range_meters())
+            .field(
+                "vertically_integrated_liquid_kilograms_per_square_meter",
+                &self.vertically_integrated_liquid_kilograms_per_square_meter(),
+            )
+            .field("base_height_meters", &self.base_height_meters())
+            .field("top_height_meters", &self.top_height_meters())
+            .field("max_reflectivity_dbz", &self.max_reflectivity_dbz())
+            .field("components", &self.components())
+            .finish()
+    }
+}
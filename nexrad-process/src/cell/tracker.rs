@@ -0,0 +1,322 @@
+use crate::association::east_north;
+use crate::cell::{identify_cells, CellOptions, CellTrackerOptions, StormCell};
+use crate::result::{Error, Result};
+use nexrad_model::data::Scan;
+use nexrad_model::geometry::destination;
+use nexrad_model::meta::Site;
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A storm cell's forecast position at some lead time after its latest observation.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ForecastPosition {
+    lead_time_seconds: f32,
+    latitude_degrees: f32,
+    longitude_degrees: f32,
+}
+
+impl ForecastPosition {
+    /// Create a new forecast position with the given properties.
+    pub fn new(lead_time_seconds: f32, latitude_degrees: f32, longitude_degrees: f32) -> Self {
+        Self {
+            lead_time_seconds,
+            latitude_degrees,
+            longitude_degrees,
+        }
+    }
+
+    /// The time in seconds after the cell's latest observation this position is forecast for.
+    pub fn lead_time_seconds(&self) -> f32 {
+        self.lead_time_seconds
+    }
+
+    /// The forecast latitude in degrees of the cell's centroid.
+    pub fn latitude_degrees(&self) -> f32 {
+        self.latitude_degrees
+    }
+
+    /// The forecast longitude in degrees of the cell's centroid.
+    pub fn longitude_degrees(&self) -> f32 {
+        self.longitude_degrees
+    }
+}
+
+impl Debug for ForecastPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForecastPosition")
+            .field("lead_time_seconds", &self.lead_time_seconds())
+            .field("latitude_degrees", &self.latitude_degrees())
+            .field("longitude_degrees", &self.longitude_degrees())
+            .finish()
+    }
+}
+
+/// A storm cell followed across successive scans.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellTrack {
+    id: u32,
+    cells: Vec<StormCell>,
+    motion_meters_per_second: Option<(f32, f32)>,
+    forecast_positions: Vec<ForecastPosition>,
+}
+
+impl CellTrack {
+    /// This track's identifier, unique among the tracks of a [CellTracker].
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The cell observed in each scan this track spans, ordered from oldest to latest.
+    pub fn cells(&self) -> &Vec<StormCell> {
+        self.cells.as_ref()
+    }
+
+    /// The cell observed in the latest scan this track spans.
+    pub fn latest(&self) -> Option<&StormCell> {
+        self.cells.last()
+    }
+
+    /// The eastward and northward components of this track's motion in meters per second, if it
+    /// spans more than one scan.
+    pub fn motion_meters_per_second(&self) -> Option<(f32, f32)> {
+        self.motion_meters_per_second
+    }
+
+    /// The direction in degrees this track is moving towards, if it spans more than one scan.
+    pub fn motion_direction_degrees(&self) -> Option<f32> {
+        self.motion_meters_per_second
+            .map(|(east, north)| east.atan2(north).to_degrees().rem_euclid(360.0))
+    }
+
+    /// The speed in meters per second this track is moving at, if it spans more than one scan.
+    pub fn motion_speed_meters_per_second(&self) -> Option<f32> {
+        self.motion_meters_per_second
+            .map(|(east, north)| east.hypot(north))
+    }
+
+    /// Forecast positions extrapolated from the latest cell along this track's motion. Empty if the
+    /// track spans only one scan.
+    pub fn forecast_positions(&self) -> &Vec<ForecastPosition> {
+        self.forecast_positions.as_ref()
+    }
+
+    /// Extrapolates the latest cell's position along this track's motion by the given lead time
+    /// in seconds, returning a latitude and longitude in degrees.
+    pub fn forecast_position(&self, lead_time_seconds: f32) -> Option<(f32, f32)> {
+        let latest = self.latest()?;
+        Some(destination(
+            latest.latitude_degrees(),
+            latest.longitude_degrees(),
+            self.motion_direction_degrees()?,
+            self.motion_speed_meters_per_second()? * lead_time_seconds,
+        ))
+    }
+
+    /// Fits this track's motion to its recent positions and updates its forecast positions.
+    fn update_motion(&mut self, options: &CellTrackerOptions) {
+        let recent = &self.cells[self.cells.len().saturating_sub(options.motion_history)..];
+        self.motion_meters_per_second = fit_motion(recent);
+
+        self.forecast_positions = options
+            .forecast_lead_times_seconds
+            .iter()
+            .filter_map(|lead_time| {
+                let (latitude, longitude) = self.forecast_position(*lead_time)?;
+                Some(ForecastPosition::new(*lead_time, latitude, longitude))
+            })
+            .collect();
+    }
+}
+
+impl Debug for CellTrack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CellTrack")
+            .field("id", &self.id())
+            .field("motion_direction_degrees", &self.motion_direction_degrees())
+            .field(
+                "motion_speed_meters_per_second",
+                &self.motion_speed_meters_per_second(),
+            )
+            .field("forecast_positions", &self.forecast_positions())
+            .field("cells", &self.cells())
+            .finish()
+    }
+}
+
+/// Tracks storm cells across successive scans from a single radar. Scans must be provided in time
+/// order. Each cell identified in a new scan continues the track whose predicted position is
+/// nearest, within the distance the fastest expected motion would cover, or otherwise begins a new
+/// track. Tracks without a cell in the new scan are ended.
+#[derive(Debug, Clone)]
+pub struct CellTracker {
+    site: Site,
+    cell_options: CellOptions,
+    options: CellTrackerOptions,
+    tracks: Vec<CellTrack>,
+    latest_timestamp: Option<i64>,
+    next_id: u32,
+}
+
+impl CellTracker {
+    /// Create a new tracker for scans from the given radar site.
+    pub fn new(site: Site, cell_options: CellOptions, options: CellTrackerOptions) -> Result<Self> {
+        cell_options.validate()?;
+        options.validate()?;
+
+        Ok(Self {
+            site,
+            cell_options,
+            options,
+            tracks: Vec::new(),
+            latest_timestamp: None,
+            next_id: 1,
+        })
+    }
+
+    /// The tracks continued or begun by the latest scan.
+    pub fn tracks(&self) -> &Vec<CellTrack> {
+        self.tracks.as_ref()
+    }
+
+    /// Identifies cells in the next scan and matches them with existing tracks, returning the
+    /// updated tracks.
+    pub fn update(&mut self, scan: &Scan) -> Result<&Vec<CellTrack>> {
+        let cells = identify_cells(scan, &self.site, &self.cell_options)?;
+        self.update_cells(cells)
+    }
+
+    /// Matches cells already identified in the next scan with existing tracks, returning the
+    /// updated tracks.
+    pub fn update_cells(&mut self, cells: Vec<StormCell>) -> Result<&Vec<CellTrack>> {
+        let timestamp = cells
+            .iter()
+            .filter_map(|cell| cell.collection_timestamp())
+            .min();
+        if let (Some(timestamp), Some(latest_timestamp)) = (timestamp, self.latest_timestamp) {
+            if timestamp <= latest_timestamp {
+                return Err(Error::OutOfOrderScan);
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for (track_index, track) in self.tracks.iter().enumerate() {
+            let latest = match track.latest() {
+                Some(latest) => latest,
+                None => continue,
+            };
+
+            for (cell_index, cell) in cells.iter().enumerate() {
+                let elapsed = match (cell.collection_timestamp(), latest.collection_timestamp()) {
+                    (Some(timestamp), Some(latest_timestamp)) => {
+                        (timestamp - latest_timestamp) as f32 / 1000.0
+                    }
+                    _ => continue,
+                };
+
+                if elapsed <= 0.0 || elapsed > self.options.max_time_gap_seconds {
+                    continue;
+                }
+
+                let (motion_east, motion_north) =
+                    track.motion_meters_per_second().unwrap_or_default();
+                let (east, north) = position(latest);
+                let predicted = (east + motion_east * elapsed, north + motion_north * elapsed);
+
+                let (cell_east, cell_north) = position(cell);
+                let distance = (cell_east - predicted.0).hypot(cell_north - predicted.1);
+                if distance <= self.options.max_speed_meters_per_second * elapsed {
+                    candidates.push((distance, track_index, cell_index));
+                }
+            }
+        }
+
+        candidates.sort_by(|(a, _, _), (b, _, _)| a.total_cmp(b));
+
+        let mut track_matches = vec![None; self.tracks.len()];
+        let mut cell_matched = vec![false; cells.len()];
+        for (_, track_index, cell_index) in candidates {
+            if track_matches[track_index].is_none() && !cell_matched[cell_index] {
+                track_matches[track_index] = Some(cell_index);
+                cell_matched[cell_index] = true;
+            }
+        }
+
+        let mut cells: Vec<Option<StormCell>> = cells.into_iter().map(Some).collect();
+        let mut tracks = Vec::new();
+        for (mut track, cell_index) in self.tracks.drain(..).zip(track_matches) {
+            if let Some(cell) = cell_index.and_then(|index| cells[index].take()) {
+                track.cells.push(cell);
+                track.update_motion(&self.options);
+                tracks.push(track);
+            }
+        }
+
+        for cell in cells.into_iter().flatten() {
+            tracks.push(CellTrack {
+                id: self.next_id,
+                cells: vec![cell],
+                motion_meters_per_second: None,
+                forecast_positions: Vec::new(),
+            });
+            self.next_id += 1;
+        }
+
+        self.tracks = tracks;
+        self.latest_timestamp = timestamp.or(self.latest_timestamp);
+
+        Ok(self.tracks.as_ref())
+    }
+}
+
+/// The eastward and northward distance in meters from the radar to a cell's centroid.
+fn position(cell: &StormCell) -> (f32, f32) {
+    east_north(cell.azimuth_degrees(), cell.ground_range_meters())
+}
+
+/// Fits a constant velocity to a series of cells' positions over time using least squares.
+fn fit_motion(cells: &[StormCell]) -> Option<(f32, f32)> {
+    let samples: Vec<(f64, (f32, f32))> = cells
+        .iter()
+        .filter_map(|cell| Some((cell.collection_timestamp()? as f64 / 1000.0, position(cell))))
+        .collect();
+
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let count = samples.len() as f64;
+    let mean_time = samples.iter().map(|(time, _)| time).sum::<f64>() / count;
+    let mean_east = samples
+        .iter()
+        .map(|(_, (east, _))| *east as f64)
+        .sum::<f64>()
+        / count;
+    let mean_north = samples
+        .iter()
+        .map(|(_, (_, north))| *north as f64)
+        .sum::<f64>()
+        / count;
+
+    let mut variance = 0.0;
+    let mut east_covariance = 0.0;
+    let mut north_covariance = 0.0;
+    for (time, (east, north)) in &samples {
+        let time = time - mean_time;
+        variance += time * time;
+        east_covariance += time * (*east as f64 - mean_east);
+        north_covariance += time * (*north as f64 - mean_north);
+    }
+
+    if variance <= 0.0 {
+        return None;
+    }
+
+    Some((
+        (east_covariance / variance) as f32,
+        (north_covariance / variance) as f32,
+    ))
+}
@@ -0,0 +1,272 @@
+//!
+//! Tornado debris signature (TDS) detection. Debris lofted by a tornado produces a distinctive
+//! dual-polarization signature: moderate to high reflectivity with a markedly low correlation
+//! coefficient and near-zero differential reflectivity, co-located with a strong velocity couplet.
+//! Gates matching the signature are grouped into regions within each sweep, kept where a velocity
+//! couplet is found nearby at a similar elevation, and associated vertically across sweeps.
+//!
+
+mod options;
+pub use options::*;
+
+mod signature;
+pub use signature::*;
+
+use crate::association::{distance, east_north, elevation_levels, stack_levels};
+use crate::field::PolarField;
+use crate::regions::connected_regions;
+use crate::result::Result;
+use crate::vortex::{sweep_vortex_features, VortexFeature, VortexKind};
+use nexrad_model::data::{Radial, Scan, Sweep};
+use nexrad_model::geometry::{beam_height_meters, destination, ground_range_meters};
+use nexrad_model::meta::Site;
+
+/// Detects tornado debris signatures in a scan, locating them relative to the given radar site.
+pub fn detect_debris(
+    scan: &Scan,
+    site: &Site,
+    options: &DebrisOptions,
+) -> Result<Vec<DebrisSignature>> {
+    options.validate()?;
+
+    let mut couplets = Vec::new();
+    for sweep in scan.sweeps() {
+        for kind in [VortexKind::Mesocyclone, VortexKind::TornadoVortexSignature] {
+            couplets.extend(
+                sweep_vortex_features(sweep, kind, &options.vortex_options)?
+                    .into_iter()
+                    .filter(|couplet| {
+                        couplet.delta_velocity_meters_per_second()
+                            >= options.min_couplet_delta_velocity_meters_per_second
+                    }),
+            );
+        }
+    }
+
+    let mut features = Vec::new();
+    for sweep in scan.sweeps() {
+        features.extend(sweep_debris_features(sweep, &couplets, options));
+    }
+
+    Ok(associate(features, site, options))
+}
+
+/// Finds debris features in a single sweep which are co-located with one of the given velocity
+/// couplets.
+pub fn sweep_debris_features(
+    sweep: &Sweep,
+    couplets: &[VortexFeature],
+    options: &DebrisOptions,
+) -> Vec<DebrisFeature> {
+    let (reflectivity, correlation, differential_reflectivity) = match (
+        PolarField::from_sweep(sweep, Radial::reflectivity),
+        PolarField::from_sweep(sweep, Radial::correlation_coefficient),
+        PolarField::from_sweep(sweep, Radial::differential_reflectivity),
+    ) {
+        (Some(reflectivity), Some(correlation), Some(differential_reflectivity)) => {
+            (reflectivity, correlation, differential_reflectivity)
+        }
+        _ => return Vec::new(),
+    };
+
+    let elevation_angle = reflectivity.elevation_angle_degrees().unwrap_or_default();
+    let gate_count = reflectivity.gate_count();
+
+    let mut flagged = vec![false; reflectivity.values().len()];
+    let mut polarimetric = vec![(f32::NAN, f32::NAN); reflectivity.values().len()];
+    for (radial_index, azimuth) in reflectivity.azimuths_degrees().iter().enumerate() {
+        for gate_index in 0..gate_count {
+            match reflectivity.value(radial_index, gate_index) {
+                Some(value) if value >= options.min_reflectivity_dbz => {}
+                _ => continue,
+            }
+
+            let range = reflectivity.gate_range_meters(gate_index);
+            let (correlation_value, differential_reflectivity_value) = match (
+                correlation.value_at(*azimuth, range),
+                differential_reflectivity.value_at(*azimuth, range),
+            ) {
+                (Some(correlation), Some(differential_reflectivity)) => {
+                    (correlation, differential_reflectivity)
+                }
+                _ => continue,
+            };
+
+            if correlation_value <= options.max_correlation_coefficient
+                && differential_reflectivity_value.abs()
+                    <= options.max_abs_differential_reflectivity_db
+            {
+                let index = radial_index * gate_count + gate_index;
+                flagged[index] = true;
+                polarimetric[index] = (correlation_value, differential_reflectivity_value);
+            }
+        }
+    }
+
+    connected_regions(reflectivity.radial_count(), gate_count, &flagged)
+        .into_iter()
+        .filter(|region| region.len() >= options.min_gates)
+        .filter_map(|region| {
+            let mut east = 0.0;
+            let mut north = 0.0;
+            let mut slant_range = 0.0;
+            let mut max_reflectivity = f32::MIN;
+            let mut min_correlation = f32::MAX;
+            let mut differential_reflectivity_sum = 0.0;
+
+            for (radial_index, gate_index) in &region {
+                let azimuth = reflectivity.azimuths_degrees()[*radial_index].to_radians();
+                east += azimuth.sin();
+                north += azimuth.cos();
+                slant_range += reflectivity.gate_range_meters(*gate_index);
+
+                if let Some(value) = reflectivity.value(*radial_index, *gate_index) {
+                    max_reflectivity = max_reflectivity.max(value);
+                }
+
+                let (correlation, differential_reflectivity) =
+                    polarimetric[radial_index * gate_count + gate_index];
+                min_correlation = min_correlation.min(correlation);
+                differential_reflectivity_sum += differential_reflectivity;
+            }
+
+            let azimuth = east.atan2(north).to_degrees().rem_euclid(360.0);
+            let slant_range = slant_range / region.len() as f32;
+            let ground_range = ground_range_meters(slant_range, elevation_angle);
+
+            let couplet = couplets
+                .iter()
+                .filter(|couplet| {
+                    (couplet.elevation_angle_degrees() - elevation_angle).abs()
+                        <= options.max_elevation_difference_degrees
+                        && distance(
+                            east_north(azimuth, ground_range),
+                            east_north(couplet.azimuth_degrees(), couplet.ground_range_meters()),
+                        ) <= options.couplet_distance_meters
+                })
+                .map(|couplet| couplet.delta_velocity_meters_per_second())
+                .reduce(f32::max)?;
+
+            Some(DebrisFeature::new(
+                sweep.elevation_number(),
+                elevation_angle,
+                azimuth,
+                ground_range,
+                beam_height_meters(slant_range, elevation_angle),
+                region.len(),
+                max_reflectivity,
+                min_correlation,
+                differential_reflectivity_sum / region.len() as f32,
+                couplet,
+            ))
+        })
+        .collect()
+}
+
+/// Associates debris features from successive sweeps which are vertically aligned into signatures.
+fn associate(
+    features: Vec<DebrisFeature>,
+    site: &Site,
+    options: &DebrisOptions,
+) -> Vec<DebrisSignature> {
+    let levels = elevation_levels(features, DebrisFeature::elevation_angle_degrees);
+    stack_levels(
+        levels,
+        &[options.association_distance_meters],
+        false,
+        |feature: &DebrisFeature| {
+            east_north(feature.azimuth_degrees(), feature.ground_range_meters())
+        },
+    )
+    .into_iter()
+    .filter(|stack| stack.len() >= options.min_sweeps)
+    .filter_map(|stack| {
+        let base = stack.first()?;
+        let (latitude, longitude) = destination(
+            site.latitude(),
+            site.longitude(),
+            base.azimuth_degrees(),
+            base.ground_range_meters(),
+        );
+
+        Some(DebrisSignature::new(latitude, longitude, stack))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::Product;
+
+    /// A sweep containing a tornado 20 km east of the radar, with debris within 600 meters of its
+    /// center.
+    fn tornado_sweep() -> Sweep {
+        let center = (20_000.0f32, 0.0f32);
+        let (radius, rotation) = (600.0, 30.0);
+
+        let radials = (0..720)
+            .map(|azimuth_number| {
+                let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                let (sin, cos) = azimuth.to_radians().sin_cos();
+
+                let mut velocity = Vec::new();
+                let mut correlation = Vec::new();
+                let mut differential_reflectivity = Vec::new();
+                for gate in 0..200 {
+                    let range = 2_000.0 + gate as f32 * 250.0;
+                    let (x, y) = (range * sin - center.0, range * cos - center.1);
+                    let distance = x.hypot(y).max(1.0);
+                    let speed = if distance < radius {
+                        rotation * distance / radius
+                    } else {
+                        rotation * radius / distance
+                    };
+
+                    let radial_velocity = -speed * y / distance * sin + speed * x / distance * cos;
+                    velocity.push((radial_velocity * 2.0 + 129.0).round().clamp(2.0, 255.0) as u8);
+
+                    let debris = distance < 600.0;
+                    correlation.push(if debris { 120 } else { 233 });
+                    differential_reflectivity.push(if debris { 128 } else { 160 });
+                }
+
+                radial(0, azimuth_number, 0.5, 1, 0.5)
+                    .with_moment(
+                        Product::Reflectivity,
+                        moment(&Product::Reflectivity, vec![166; 200]),
+                    )
+                    .with_moment(Product::Velocity, moment(&Product::Velocity, velocity))
+                    .with_moment(
+                        Product::DifferentialReflectivity,
+                        moment(
+                            &Product::DifferentialReflectivity,
+                            differential_reflectivity,
+                        ),
+                    )
+                    .with_moment(
+                        Product::CorrelationCoefficient,
+                        moment(&Product::CorrelationCoefficient, correlation),
+                    )
+            })
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    #[test]
+    fn detects_debris() {
+        let scan = Scan::new(212, vec![tornado_sweep()]);
+        let site = Site::new(*b"KTLX", 35.33, -97.28, 370, 20);
+
+        let signatures = detect_debris(&scan, &site, &DebrisOptions::default()).unwrap();
+        assert_eq!(signatures.len(), 1);
+
+        let feature = &signatures[0].features()[0];
+        assert!((feature.azimuth_degrees() - 90.0).abs() < 2.0);
+        assert!((feature.ground_range_meters() - 20_000.0).abs() < 500.0);
+        assert!(feature.min_correlation_coefficient() < 0.65);
+        assert!(feature.couplet_delta_velocity_meters_per_second() >= 25.0);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::result::{Error, Result};
+use crate::vortex::VortexOptions;
+
+/// Options controlling tornado debris signature detection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebrisOptions {
+    /// The minimum reflectivity in dBZ of a debris gate.
+    pub min_reflectivity_dbz: f32,
+
+    /// The maximum correlation coefficient of a debris gate. Lofted debris is irregularly shaped
+    /// and diverse, lowering the correlation between the horizontal and vertical channels.
+    pub max_correlation_coefficient: f32,
+
+    /// The maximum magnitude of differential reflectivity in dB of a debris gate. Tumbling debris
+    /// has no preferred orientation and so has near-zero differential reflectivity.
+    pub max_abs_differential_reflectivity_db: f32,
+
+    /// The minimum number of connected debris gates forming a feature in a sweep.
+    pub min_gates: usize,
+
+    /// The minimum velocity difference in meters per second of a couplet co-located with debris.
+    pub min_couplet_delta_velocity_meters_per_second: f32,
+
+    /// The largest distance in meters between a debris feature and its velocity couplet.
+    pub couplet_distance_meters: f32,
+
+    /// The largest difference in degrees between the elevation angles of a debris feature and its
+    /// velocity couplet. Debris and velocity may be observed in separate sweeps at the same
+    /// elevation when a split cut is used.
+    pub max_elevation_difference_degrees: f32,
+
+    /// The largest distance in meters between debris features in successive sweeps for them to be
+    /// associated into the same signature.
+    pub association_distance_meters: f32,
+
+    /// The minimum number of sweeps a signature must span.
+    pub min_sweeps: usize,
+
+    /// Options for finding velocity couplets.
+    pub vortex_options: VortexOptions,
+}
+
+impl DebrisOptions {
+    /// Checks that these options are usable for detection.
+    pub fn validate(&self) -> Result<()> {
+        if self.min_gates == 0 || self.min_sweeps == 0 {
+            return Err(Error::InvalidOptions(
+                "at least one gate and sweep are required",
+            ));
+        }
+
+        if self.couplet_distance_meters <= 0.0 || self.association_distance_meters <= 0.0 {
+            return Err(Error::InvalidOptions("distances must be positive"));
+        }
+
+        self.vortex_options.validate()
+    }
+}
+
+impl Default for DebrisOptions {
+    fn default() -> Self {
+        Self {
+            min_reflectivity_dbz: 30.0,
+            max_correlation_coefficient: 0.80,
+            max_abs_differential_reflectivity_db: 1.0,
+            min_gates: 4,
+            min_couplet_delta_velocity_meters_per_second: 25.0,
+            couplet_distance_meters: 3_000.0,
+            max_elevation_difference_degrees: 0.3,
+            association_distance_meters: 3_000.0,
+            min_sweeps: 1,
+            vortex_options: VortexOptions {
+                min_sweeps: 1,
+                min_segments: 1,
+                ..VortexOptions::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A region of debris-like dual-polarization returns within a single sweep which is co-located
+/// with a velocity couplet.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DebrisFeature {
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+    azimuth_degrees: f32,
+    ground_range_meters: f32,
+    height_meters: f32,
+    gate_count: usize,
+    max_reflectivity_dbz: f32,
+    min_correlation_coefficient: f32,
+    mean_differential_reflectivity_db: f32,
+    couplet_delta_velocity_meters_per_second: f32,
+}
+
+impl DebrisFeature {
+    /// Create a new debris feature with the given properties.
+    pub fn new(
+        elevation_number: u8,
+        elevation_angle_degrees: f32,
+        azimuth_degrees: f32,
+        ground_range_meters: f32,
+        height_meters: f32,
+        gate_count: usize,
+        max_reflectivity_dbz: f32,
+        min_correlation_coefficient: f32,
+        mean_differential_reflectivity_db: f32,
+        couplet_delta_velocity_meters_per_second: f32,
+    ) -> Self {
+        Self {
+            elevation_number,
+            elevation_angle_degrees,
+            azimuth_degrees,
+            ground_range_meters,
+            height_meters,
+            gate_count,
+            max_reflectivity_dbz,
+            min_correlation_coefficient,
+            mean_differential_reflectivity_db,
+            couplet_delta_velocity_meters_per_second,
+        }
+    }
+
+    /// The elevation number of the sweep this feature was found in.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// The mean elevation angle in degrees of the sweep this feature was found in.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// The azimuth in degrees of this feature's centroid.
+    pub fn azimuth_degrees(&self) -> f32 {
+        self.azimuth_degrees
+    }
+
+    /// The distance along the ground in meters from the radar to this feature's centroid.
+    pub fn ground_range_meters(&self) -> f32 {
+        self.ground_range_meters
+    }
+
+    /// The height in meters above the radar of this feature's centroid.
+    pub fn height_meters(&self) -> f32 {
+        self.height_meters
+    }
+
+    /// The number of gates comprising this feature.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+
+    /// The largest reflectivity in dBZ within this feature.
+    pub fn max_reflectivity_dbz(&self) -> f32 {
+        self.max_reflectivity_dbz
+    }
+
+    /// The smallest correlation coefficient within this feature.
+    pub fn min_correlation_coefficient(&self) -> f32 {
+        self.min_correlation_coefficient
+    }
+
+    /// The mean differential reflectivity in dB within this feature.
+    pub fn mean_differential_reflectivity_db(&self) -> f32 {
+        self.mean_differential_reflectivity_db
+    }
+
+    /// The velocity difference in meters per second of the couplet co-located with this feature.
+    pub fn couplet_delta_velocity_meters_per_second(&self) -> f32 {
+        self.couplet_delta_velocity_meters_per_second
+    }
+}
+
+impl Debug for DebrisFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebrisFeature")
+            .field("elevation_number", &self.elevation_number())
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("azimuth_degrees", &self.azimuth_degrees())
+            .field("ground_range_meters", &self.ground_range_meters())
+            .field("height_meters", &self.height_meters())
+            .field("gate_count", &self.gate_count())
+            .field("max_reflectivity_dbz", &self.max_reflectivity_dbz())
+            .field(
+                "min_correlation_coefficient",
+                &self.min_correlation_coefficient(),
+            )
+            .field(
+                "mean_differential_reflectivity_db",
+                &self.mean_differential_reflectivity_db(),
+            )
+            .field(
+                "couplet_delta_velocity_meters_per_second",
+                &self.couplet_delta_velocity_meters_per_second(),
+            )
+            .finish()
+    }
+}
+
+/// A tornado debris signature extending vertically through one or more sweeps of a scan.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DebrisSignature {
+    latitude_degrees: f32,
+    longitude_degrees: f32,
+    features: Vec<DebrisFeature>,
+}
+
+impl DebrisSignature {
+    /// Create a new debris signature from its features, which should be ordered by ascending
+    /// elevation.
+    pub fn new(
+        latitude_degrees: f32,
+        longitude_degrees: f32,
+        features: Vec<DebrisFeature>,
+    ) -> Self {
+        Self {
+            latitude_degrees,
+            longitude_degrees,
+            features,
+        }
+    }
+
+    /// The latitude in degrees of this signature's base.
+    pub fn latitude_degrees(&self) -> f32 {
+        self.latitude_degrees
+    }
+
+    /// The longitude in degrees of this signature's base.
+    pub fn longitude_degrees(&self) -> f32 {
+        self.longitude_degrees
+    }
+
+    /// The per-sweep features comprising this signature ordered by ascending elevation.
+    pub fn features(&self) -> &Vec<DebrisFeature> {
+        self.features.as_ref()
+    }
+
+    /// The height in meters above the radar of this signature's lowest feature.
+    pub fn base_height_meters(&self) -> Option<f32> {
+        self.features
+            .iter()
+            .map(|feature| feature.height_meters())
+            .reduce(f32::min)
+    }
+
+    /// The height in meters above the radar of this signature's highest feature.
+    pub fn top_height_meters(&self) -> Option<f32> {
+        self.features
+            .iter()
+            .map(|feature| feature.height_meters())
+            .reduce(f32::max)
+    }
+
+    /// The vertical extent of this signature in meters.
+    pub fn depth_meters(&self) -> Option<f32> {
+        Some(self.top_height_meters()? - self.base_height_meters()?)
+    }
+}
+
+impl Debug for DebrisSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebrisSignature")
+            .field("latitude_degrees", &self.latitude_degrees())
+            .field("longitude_degrees", &self.longitude_degrees())
+            .field("base_height_meters", &self.base_height_meters())
+            .field("top_height_meters", &self.top_height_meters())
+            .field("features", &self.features())
+            .finish()
+    }
+}
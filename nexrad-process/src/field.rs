@@ -0,0 +1,289 @@
+//!
+//! A gridded field of floating-point values in radar (polar) coordinates. Algorithms use fields to
+//! hold moment data decoded from a sweep as well as the derived quantities they produce, with
+//! missing or invalid gates represented as NaN.
+//!
+
+use nexrad_model::data::{MomentData, MomentValue, Product, Radial, Sweep};
+use nexrad_model::geometry::azimuth_difference_degrees;
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A field of values indexed by radial and gate. Radials are ordered by ascending azimuth and gates
+/// by ascending range. For fields derived from a single sweep, ranges are slant ranges along the
+/// beam. For fields composited across several sweeps, ranges are distances along the ground and
+/// the field has no elevation angle.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PolarField {
+    elevation_angle_degrees: Option<f32>,
+    azimuths_degrees: Vec<f32>,
+    first_gate_range_meters: f32,
+    gate_interval_meters: f32,
+    gate_count: usize,
+    values: Vec<f32>,
+}
+
+impl PolarField {
+    /// Create a new field with every gate set to NaN. Azimuths should be in ascending order within
+    /// 0 to 360 degrees.
+    pub fn empty(
+        elevation_angle_degrees: Option<f32>,
+        azimuths_degrees: Vec<f32>,
+        first_gate_range_meters: f32,
+        gate_interval_meters: f32,
+        gate_count: usize,
+    ) -> Self {
+        let values = vec![f32::NAN; azimuths_degrees.len() * gate_count];
+        Self {
+            elevation_angle_degrees,
+            azimuths_degrees,
+            first_gate_range_meters,
+            gate_interval_meters,
+            gate_count,
+            values,
+        }
+    }
+
+    /// Create a new field with the same geometry as this one and every gate set to NaN.
+    pub fn empty_like(&self) -> Self {
+        Self::empty(
+            self.elevation_angle_degrees,
+            self.azimuths_degrees.clone(),
+            self.first_gate_range_meters,
+            self.gate_interval_meters,
+            self.gate_count,
+        )
+    }
+
+    /// Create a field from one of a sweep's data moments, selected from each radial by the provided
+    /// function. Gates which are below threshold or range folded are NaN. Returns [None] if no
+    /// radial in the sweep has the selected moment.
+    pub fn from_sweep<F>(sweep: &Sweep, moment: F) -> Option<Self>
+    where
+        F: Fn(&Radial) -> Option<&MomentData>,
+    {
+        let mut radials: Vec<(&Radial, &MomentData)> = sweep
+            .radials()
+            .iter()
+            .filter_map(|radial| moment(radial).map(|data| (radial, data)))
+            .collect();
+
+        radials.sort_by(|(a, _), (b, _)| {
+            a.azimuth_angle_degrees()
+                .total_cmp(&b.azimuth_angle_degrees())
+        });
+
+        let (_, first_moment) = radials.first()?;
+        let first_gate_range = first_moment.first_gate_range_meters() as f32;
+        let gate_interval = first_moment.gate_interval_meters() as f32;
+        let gate_count = radials
+            .iter()
+            .map(|(_, data)| data.gate_count())
+            .max()
+            .unwrap_or_default();
+
+        let elevation_angle = radials
+            .iter()
+            .map(|(radial, _)| radial.elevation_angle_degrees())
+            .sum::<f32>()
+            / radials.len() as f32;
+
+        let mut field = Self::empty(
+            Some(elevation_angle),
+            radials
+                .iter()
+                .map(|(radial, _)| radial.azimuth_angle_degrees())
+                .collect(),
+            first_gate_range,
+            gate_interval,
+            gate_count,
+        );
+
+        for (radial_index, (_, data)) in radials.iter().enumerate() {
+            let same_geometry = data.first_gate_range_meters() as f32 == first_gate_range
+                && data.gate_interval_meters() as f32 == gate_interval;
+
+            for (gate_index, value) in data.values().into_iter().enumerate() {
+                let value = match value {
+                    MomentValue::Value(value) => value,
+                    _ => continue,
+                };
+
+                let field_gate_index = if same_geometry {
+                    Some(gate_index)
+                } else {
+                    field.gate_index(data.gate_range_meters(gate_index))
+                };
+
+                if let Some(field_gate_index) = field_gate_index {
+                    field.set(radial_index, field_gate_index, value);
+                }
+            }
+        }
+
+        Some(field)
+    }
+
+    /// Create a field from a sweep's data for the given product. Returns [None] if no radial in the
+    /// sweep has the product.
+    pub fn from_product(sweep: &Sweep, product: &Product) -> Option<Self> {
+        Self::from_sweep(sweep, |radial| radial.moment(product))
+    }
+
+    /// The mean elevation angle in degrees of the sweep this field was derived from, if any.
+    pub fn elevation_angle_degrees(&self) -> Option<f32> {
+        self.elevation_angle_degrees
+    }
+
+    /// The azimuth angle in degrees of each radial in this field.
+    pub fn azimuths_degrees(&self) -> &Vec<f32> {
+        self.azimuths_degrees.as_ref()
+    }
+
+    /// The number of radials in this field.
+    pub fn radial_count(&self) -> usize {
+        self.azimuths_degrees.len()
+    }
+
+    /// The number of gates in each of this field's radials.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+
+    /// Range to the center of the first gate in meters.
+    pub fn first_gate_range_meters(&self) -> f32 {
+        self.first_gate_range_meters
+    }
+
+    /// Distance between the centers of adjacent gates in meters.
+    pub fn gate_interval_meters(&self) -> f32 {
+        self.gate_interval_meters
+    }
+
+    /// Range to the center of the gate at the given index in meters.
+    pub fn gate_range_meters(&self, gate_index: usize) -> f32 {
+        self.first_gate_range_meters + gate_index as f32 * self.gate_interval_meters
+    }
+
+    /// The index of the gate whose center is nearest the given range in meters, if any.
+    pub fn gate_index(&self, range_meters: f32) -> Option<usize> {
+        if self.gate_interval_meters <= 0.0 {
+            return None;
+        }
+
+        let index =
+            ((range_meters - self.first_gate_range_meters) / self.gate_interval_meters).round();
+        if index < 0.0 || index as usize >= self.gate_count {
+            return None;
+        }
+
+        Some(index as usize)
+    }
+
+    /// The index of the radial whose azimuth is nearest the given azimuth in degrees, if any.
+    pub fn radial_index(&self, azimuth_degrees: f32) -> Option<usize> {
+        nearest_radial(&self.azimuths_degrees, azimuth_degrees)
+    }
+
+    /// The value of the gate nearest the given azimuth in degrees and range in meters. Returns
+    /// [None] if the nearest radial is more than a nominal radial spacing away, the range is out of
+    /// bounds or the gate has no valid value.
+    pub fn value_at(&self, azimuth_degrees: f32, range_meters: f32) -> Option<f32> {
+        let radial_index = self.radial_index(azimuth_degrees)?;
+        if !within_spacing(&self.azimuths_degrees, radial_index, azimuth_degrees) {
+            return None;
+        }
+
+        self.value(radial_index, self.gate_index(range_meters)?)
+    }
+
+    /// All of this field's values ordered by radial and then gate.
+    pub fn values(&self) -> &Vec<f32> {
+        self.values.as_ref()
+    }
+
+    /// The values for the radial at the given index.
+    pub fn radial_values(&self, radial_index: usize) -> &[f32] {
+        let start = radial_index * self.gate_count;
+        &self.values[start..start + self.gate_count]
+    }
+
+    /// The value at the given radial and gate, or [None] if the gate is out of bounds or has no
+    /// valid value.
+    pub fn value(&self, radial_index: usize, gate_index: usize) -> Option<f32> {
+        if radial_index >= self.radial_count() || gate_index >= self.gate_count {
+            return None;
+        }
+
+        let value = self.values[radial_index * self.gate_count + gate_index];
+        if value.is_nan() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Sets the value at the given radial and gate. Out-of-bounds gates are ignored.
+    pub fn set(&mut self, radial_index: usize, gate_index: usize, value: f32) {
+        if radial_index < self.radial_count() && gate_index < self.gate_count {
+            self.values[radial_index * self.gate_count + gate_index] = value;
+        }
+    }
+
+    /// The largest valid value in this field, if any.
+    pub fn max(&self) -> Option<f32> {
+        self.values
+            .iter()
+            .copied()
+            .filter(|value| !value.is_nan())
+            .reduce(f32::max)
+    }
+}
+
+/// The index of the radial whose azimuth is nearest the given azimuth in degrees, given radial
+/// azimuths in ascending order.
+pub(crate) fn nearest_radial(azimuths_degrees: &[f32], azimuth_degrees: f32) -> Option<usize> {
+    let radial_count = azimuths_degrees.len();
+    if radial_count == 0 {
+        return None;
+    }
+
+    let azimuth = azimuth_degrees.rem_euclid(360.0);
+    let following = azimuths_degrees.partition_point(|radial_azimuth| *radial_azimuth < azimuth);
+
+    [
+        following % radial_count,
+        (following + radial_count - 1) % radial_count,
+    ]
+    .into_iter()
+    .min_by(|a, b| {
+        azimuth_difference_degrees(azimuths_degrees[*a], azimuth)
+            .total_cmp(&azimuth_difference_degrees(azimuths_degrees[*b], azimuth))
+    })
+}
+
+/// Whether the radial at the given index is no more than a nominal radial spacing from the given
+/// azimuth in degrees.
+pub(crate) fn within_spacing(
+    azimuths_degrees: &[f32],
+    radial_index: usize,
+    azimuth_degrees: f32,
+) -> bool {
+    let nominal_spacing = 360.0 / azimuths_degrees.len() as f32;
+    azimuth_difference_degrees(azimuths_degrees[radial_index], azimuth_degrees) <= nominal_spacing
+}
+
+impl Debug for PolarField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolarField")
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("radial_count", &self.radial_count())
+            .field("gate_count", &self.gate_count())
+            .field("first_gate_range_meters", &self.first_gate_range_meters())
+            .field("gate_interval_meters", &self.gate_interval_meters())
+            .finish()
+    }
+}
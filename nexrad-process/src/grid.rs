@@ -0,0 +1,211 @@
+//!
+//! A gridded field of floating-point values in Cartesian coordinates centered on the radar.
+//! Algorithms which compare or advect echoes between scans use Cartesian fields, since their
+//! cells have a uniform size regardless of range. Missing values are represented as NaN.
+//!
+
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A field of values on a square grid indexed by column (eastward) and row (northward). Positions
+/// are distances along the ground in meters east and north of the radar.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CartesianField {
+    first_east_meters: f32,
+    first_north_meters: f32,
+    spacing_meters: f32,
+    column_count: usize,
+    row_count: usize,
+    values: Vec<f32>,
+}
+
+impl CartesianField {
+    /// Create a new field with every cell set to NaN, given the position of the center of its
+    /// south-western cell and the distance between adjacent cells' centers.
+    pub fn empty(
+        first_east_meters: f32,
+        first_north_meters: f32,
+        spacing_meters: f32,
+        column_count: usize,
+        row_count: usize,
+    ) -> Self {
+        Self {
+            first_east_meters,
+            first_north_meters,
+            spacing_meters,
+            column_count,
+            row_count,
+            values: vec![f32::NAN; column_count * row_count],
+        }
+    }
+
+    /// Create a new field with every cell set to NaN whose center cell is over the radar and which
+    /// extends at least the given distance in meters from the radar in each direction.
+    pub fn centered(spacing_meters: f32, radius_meters: f32) -> Self {
+        let half_count = (radius_meters / spacing_meters).ceil().max(0.0) as usize;
+        let first = -(half_count as f32) * spacing_meters;
+        let count = 2 * half_count + 1;
+
+        Self::empty(first, first, spacing_meters, count, count)
+    }
+
+    /// Create a new field with the same geometry as this one and every cell set to NaN.
+    pub fn empty_like(&self) -> Self {
+        Self::empty(
+            self.first_east_meters,
+            self.first_north_meters,
+            self.spacing_meters,
+            self.column_count,
+            self.row_count,
+        )
+    }
+
+    /// Distance between the centers of adjacent cells in meters.
+    pub fn spacing_meters(&self) -> f32 {
+        self.spacing_meters
+    }
+
+    /// The number of columns in this field.
+    pub fn column_count(&self) -> usize {
+        self.column_count
+    }
+
+    /// The number of rows in this field.
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    /// Distance in meters east of the radar to the center of the given column.
+    pub fn east_meters(&self, column: usize) -> f32 {
+        self.first_east_meters + column as f32 * self.spacing_meters
+    }
+
+    /// Distance in meters north of the radar to the center of the given row.
+    pub fn north_meters(&self, row: usize) -> f32 {
+        self.first_north_meters + row as f32 * self.spacing_meters
+    }
+
+    /// The index of the column whose center is nearest the given distance in meters east of the
+    /// radar, if any.
+    pub fn column_index(&self, east_meters: f32) -> Option<usize> {
+        Self::index(
+            east_meters,
+            self.first_east_meters,
+            self.spacing_meters,
+            self.column_count,
+        )
+    }
+
+    /// The index of the row whose center is nearest the given distance in meters north of the
+    /// radar, if any.
+    pub fn row_index(&self, north_meters: f32) -> Option<usize> {
+        Self::index(
+            north_meters,
+            self.first_north_meters,
+            self.spacing_meters,
+            self.row_count,
+        )
+    }
+
+    /// All of this field's values ordered by row and then column.
+    pub fn values(&self) -> &Vec<f32> {
+        self.values.as_ref()
+    }
+
+    /// The value at the given column and row, or [None] if the cell is out of bounds or has no
+    /// valid value.
+    pub fn value(&self, column: usize, row: usize) -> Option<f32> {
+        if column >= self.column_count || row >= self.row_count {
+            return None;
+        }
+
+        let value = self.values[row * self.column_count + column];
+        if value.is_nan() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// The value of the cell nearest the given position in meters east and north of the radar.
+    pub fn value_at(&self, east_meters: f32, north_meters: f32) -> Option<f32> {
+        self.value(
+            self.column_index(east_meters)?,
+            self.row_index(north_meters)?,
+        )
+    }
+
+    /// The value at the given position in meters east and north of the radar, bilinearly
+    /// interpolated between the surrounding cells' centers. Missing cells are ignored, and
+    /// positions within half a cell of the field's edge take the value of the nearest cells.
+    pub fn interpolate(&self, east_meters: f32, north_meters: f32) -> Option<f32> {
+        if self.spacing_meters <= 0.0 || self.column_count == 0 || self.row_count == 0 {
+            return None;
+        }
+
+        let column = ((east_meters - self.first_east_meters) / self.spacing_meters)
+            .clamp(0.0, (self.column_count - 1) as f32);
+        let row = ((north_meters - self.first_north_meters) / self.spacing_meters)
+            .clamp(0.0, (self.row_count - 1) as f32);
+
+        let (left, bottom) = (column.floor() as usize, row.floor() as usize);
+        let (column_fraction, row_fraction) = (column.fract(), row.fract());
+
+        let mut total = 0.0;
+        let mut weight = 0.0;
+        for (column_offset, column_weight) in [(0, 1.0 - column_fraction), (1, column_fraction)] {
+            for (row_offset, row_weight) in [(0, 1.0 - row_fraction), (1, row_fraction)] {
+                if let Some(value) = self.value(left + column_offset, bottom + row_offset) {
+                    total += column_weight * row_weight * value;
+                    weight += column_weight * row_weight;
+                }
+            }
+        }
+
+        (weight > 0.0).then(|| total / weight)
+    }
+
+    /// Sets the value at the given column and row. Out-of-bounds cells are ignored.
+    pub fn set(&mut self, column: usize, row: usize, value: f32) {
+        if column < self.column_count && row < self.row_count {
+            self.values[row * self.column_count + column] = value;
+        }
+    }
+
+    /// The largest valid value in this field, if any.
+    pub fn max(&self) -> Option<f32> {
+        self.values
+            .iter()
+            .copied()
+            .filter(|value| !value.is_nan())
+            .reduce(f32::max)
+    }
+
+    fn index(position: f32, first: f32, spacing: f32, count: usize) -> Option<usize> {
+        if spacing <= 0.0 {
+            return None;
+        }
+
+        let index = ((position - first) / spacing).round();
+        if index < 0.0 || index as usize >= count {
+            return None;
+        }
+
+        Some(index as usize)
+    }
+}
+
+impl Debug for CartesianField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CartesianField")
+            .field("first_east_meters", &self.first_east_meters)
+            .field("first_north_meters", &self.first_north_meters)
+            .field("spacing_meters", &self.spacing_meters())
+            .field("column_count", &self.column_count())
+            .field("row_count", &self.row_count())
+            .finish()
+    }
+}
@@ -0,0 +1,350 @@
+//!
+//! Hail detection following the NWS Hail Detection Algorithm (HDA). Reflectivity aloft is weighted
+//! by its height relative to the freezing level and the -20 degree Celsius level, both of which are
+//! supplied by the caller from a nearby sounding or model analysis, and integrated vertically into
+//! a severe hail index (SHI). The probability of severe hail (POSH) and maximum expected size of
+//! hail (MESH) are derived from the SHI, and the probability of hail (POH) from the height of the
+//! 45 dBZ echo top above the freezing level.
+//!
+//! Estimates can be made for a storm cell, using the largest reflectivity near the cell in each
+//! sweep, or as gridded fields composited across a scan's sweeps.
+//!
+
+mod estimate;
+pub use estimate::*;
+
+mod options;
+pub use options::*;
+
+use crate::association::{distance, east_north};
+use crate::field::PolarField;
+use crate::result::Result;
+use nexrad_model::data::{Radial, Scan};
+use nexrad_model::geometry::{
+    azimuth_difference_degrees, beam_height_meters, ground_range_meters, slant_range_meters,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The reflectivity in dBZ at and below which echoes are assumed to contain no hail.
+const LOWER_HAIL_REFLECTIVITY_DBZ: f32 = 40.0;
+
+/// The reflectivity in dBZ at and above which echoes are assumed to be entirely hail.
+const UPPER_HAIL_REFLECTIVITY_DBZ: f32 = 50.0;
+
+/// The reflectivity in dBZ whose echo top height determines the probability of hail.
+const PROBABILITY_OF_HAIL_REFLECTIVITY_DBZ: f32 = 45.0;
+
+/// The heights in kilometers of the 45 dBZ echo top above the freezing level at which the
+/// probability of hail reaches 10%, 20% and so on up to 100%.
+const PROBABILITY_OF_HAIL_HEIGHTS_KILOMETERS: [f32; 10] = [
+    1.625, 1.875, 2.125, 2.375, 2.625, 2.925, 3.300, 3.750, 4.500, 5.500,
+];
+
+/// Gridded hail fields for a scan. These fields are composited across sweeps and indexed by ground
+/// range.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HailFields {
+    /// The probability of hail in percent.
+    pub probability_of_hail: PolarField,
+
+    /// The probability of severe hail in percent.
+    pub probability_of_severe_hail: PolarField,
+
+    /// The maximum expected size of hail in millimeters.
+    pub max_expected_size: PolarField,
+}
+
+/// Estimates hail for a storm cell centered at the given azimuth in degrees and ground range in
+/// meters. The largest reflectivity within the given radius of the center is used from each sweep.
+pub fn cell_hail(
+    scan: &Scan,
+    center_azimuth_degrees: f32,
+    center_ground_range_meters: f32,
+    radius_meters: f32,
+    options: &HailOptions,
+) -> Result<HailEstimate> {
+    options.validate()?;
+
+    let center = east_north(center_azimuth_degrees, center_ground_range_meters);
+
+    let mut samples = Vec::new();
+    for sweep in scan.sweeps() {
+        let reflectivity = match PolarField::from_sweep(sweep, Radial::reflectivity) {
+            Some(reflectivity) => reflectivity,
+            None => continue,
+        };
+
+        let elevation_angle = reflectivity.elevation_angle_degrees().unwrap_or_default();
+        let mut strongest: Option<(f32, f32)> = None;
+
+        for (radial_index, azimuth) in reflectivity.azimuths_degrees().iter().enumerate() {
+            let angle = azimuth_difference_degrees(*azimuth, center_azimuth_degrees).to_radians();
+            let closest_approach = if angle.cos() > 0.0 {
+                center_ground_range_meters * angle.sin()
+            } else {
+                center_ground_range_meters
+            };
+            if closest_approach > radius_meters {
+                continue;
+            }
+
+            for gate_index in 0..reflectivity.gate_count() {
+                let value = match reflectivity.value(radial_index, gate_index) {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                let slant_range = reflectivity.gate_range_meters(gate_index);
+                let ground_range = ground_range_meters(slant_range, elevation_angle);
+                if distance(east_north(*azimuth, ground_range), center) > radius_meters {
+                    continue;
+                }
+
+                if strongest.is_none_or(|(_, strongest)| value > strongest) {
+                    strongest = Some((beam_height_meters(slant_range, elevation_angle), value));
+                }
+            }
+        }
+
+        samples.extend(strongest);
+    }
+
+    Ok(hail_estimate(&samples, options))
+}
+
+/// Computes gridded hail fields from the vertical column of reflectivity above each grid gate.
+pub fn hail_fields(scan: &Scan, options: &HailOptions) -> Result<HailFields> {
+    options.validate()?;
+
+    let radial_count = (360.0 / options.grid_azimuth_spacing_degrees).round() as usize;
+    let azimuths: Vec<f32> = (0..radial_count)
+        .map(|index| (index as f32 + 0.5) * options.grid_azimuth_spacing_degrees)
+        .collect();
+    let gate_count =
+        (options.grid_max_range_meters / options.grid_gate_interval_meters).ceil() as usize;
+
+    let probability_of_hail = PolarField::empty(
+        None,
+        azimuths.clone(),
+        0.0,
+        options.grid_gate_interval_meters,
+        gate_count,
+    );
+    let mut fields = HailFields {
+        probability_of_severe_hail: probability_of_hail.empty_like(),
+        max_expected_size: probability_of_hail.empty_like(),
+        probability_of_hail,
+    };
+
+    let sweeps: Vec<PolarField> = scan
+        .sweeps()
+        .iter()
+        .filter_map(|sweep| PolarField::from_sweep(sweep, Radial::reflectivity))
+        .collect();
+
+    let mut samples = Vec::with_capacity(sweeps.len());
+    for gate_index in 0..gate_count {
+        let ground_range = gate_index as f32 * options.grid_gate_interval_meters;
+
+        let columns: Vec<(&PolarField, f32, f32)> = sweeps
+            .iter()
+            .filter_map(|sweep| {
+                let elevation_angle = sweep.elevation_angle_degrees().unwrap_or_default();
+                let slant_range = slant_range_meters(ground_range, elevation_angle)?;
+                Some((
+                    sweep,
+                    slant_range,
+                    beam_height_meters(slant_range, elevation_angle),
+                ))
+            })
+            .collect();
+
+        for (radial_index, azimuth) in azimuths.iter().enumerate() {
+            samples.clear();
+            samples.extend(columns.iter().filter_map(|(sweep, slant_range, height)| {
+                Some((*height, sweep.value_at(*azimuth, *slant_range)?))
+            }));
+
+            if samples.is_empty() {
+                continue;
+            }
+
+            let estimate = hail_estimate(&samples, options);
+            fields.probability_of_hail.set(
+                radial_index,
+                gate_index,
+                estimate.probability_of_hail_percent(),
+            );
+            fields.probability_of_severe_hail.set(
+                radial_index,
+                gate_index,
+                estimate.probability_of_severe_hail_percent(),
+            );
+            fields.max_expected_size.set(
+                radial_index,
+                gate_index,
+                estimate.max_expected_size_millimeters(),
+            );
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Estimates hail from a vertical profile of reflectivity given as pairs of height in meters above
+/// the radar and reflectivity in dBZ, typically one per sweep.
+pub fn hail_estimate(samples: &[(f32, f32)], options: &HailOptions) -> HailEstimate {
+    let mut samples = samples.to_vec();
+    samples.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let freezing_level = options.freezing_level_height_meters;
+    let minus_20_level = options.minus_20_celsius_height_meters;
+
+    let mut severe_hail_index = 0.0;
+    for (index, (height, reflectivity)) in samples.iter().enumerate() {
+        let below = index
+            .checked_sub(1)
+            .map_or(*height, |below| samples[below].0);
+        let above = samples.get(index + 1).map_or(*height, |(above, _)| *above);
+        let depth = (above - below) / 2.0;
+
+        let temperature_weight =
+            ((height - freezing_level) / (minus_20_level - freezing_level)).clamp(0.0, 1.0);
+        let reflectivity_weight = ((reflectivity - LOWER_HAIL_REFLECTIVITY_DBZ)
+            / (UPPER_HAIL_REFLECTIVITY_DBZ - LOWER_HAIL_REFLECTIVITY_DBZ))
+            .clamp(0.0, 1.0);
+        let kinetic_energy_flux = 5.0e-6 * 10.0f32.powf(0.084 * reflectivity) * reflectivity_weight;
+
+        severe_hail_index += 0.1 * temperature_weight * kinetic_energy_flux * depth;
+    }
+
+    let warning_threshold = (57.5 * freezing_level / 1000.0 - 121.0).max(20.0);
+    let probability_of_severe_hail = if severe_hail_index > 0.0 {
+        (29.0 * (severe_hail_index / warning_threshold).ln() + 50.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    };
+
+    let probability_of_hail = samples
+        .iter()
+        .filter(|(_, reflectivity)| *reflectivity >= PROBABILITY_OF_HAIL_REFLECTIVITY_DBZ)
+        .map(|(height, _)| (height - freezing_level) / 1000.0)
+        .reduce(f32::max)
+        .map_or(0, |echo_top| {
+            PROBABILITY_OF_HAIL_HEIGHTS_KILOMETERS
+                .iter()
+                .filter(|threshold| echo_top >= **threshold)
+                .count()
+        });
+
+    HailEstimate::new(
+        probability_of_hail as f32 * 10.0,
+        probability_of_severe_hail,
+        2.54 * severe_hail_index.sqrt(),
+        severe_hail_index,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::{Product, Sweep};
+
+    /// A scan through a storm 30 km east of the radar with 60 dBZ reflectivity extending from the
+    /// ground to 12 km within 5 km of its center.
+    fn hailstorm_scan() -> Scan {
+        let elevations = [0.5, 1.5, 2.4, 3.4, 4.3, 6.0, 8.0, 10.0, 12.5, 15.6, 19.5];
+
+        let sweeps = elevations
+            .iter()
+            .enumerate()
+            .map(|(elevation_index, elevation_angle)| {
+                let radials = (0..720)
+                    .map(|azimuth_number| {
+                        let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                        let (sin, cos) = azimuth.to_radians().sin_cos();
+
+                        let values = (0..400)
+                            .map(|gate| {
+                                let slant_range = 2_000.0 + gate as f32 * 250.0;
+                                let ground_range =
+                                    ground_range_meters(slant_range, *elevation_angle);
+                                let height = beam_height_meters(slant_range, *elevation_angle);
+                                let distance =
+                                    (ground_range * sin - 30_000.0).hypot(ground_range * cos);
+
+                                if distance < 5_000.0 && height < 12_000.0 {
+                                    186
+                                } else {
+                                    0
+                                }
+                            })
+                            .collect();
+
+                        radial(
+                            0,
+                            azimuth_number,
+                            0.5,
+                            elevation_index as u8 + 1,
+                            *elevation_angle,
+                        )
+                        .with_moment(
+                            Product::Reflectivity,
+                            moment(&Product::Reflectivity, values),
+                        )
+                    })
+                    .collect();
+
+                Sweep::new(elevation_index as u8 + 1, radials)
+            })
+            .collect();
+
+        Scan::new(212, sweeps)
+    }
+
+    #[test]
+    fn estimates_column() {
+        let options = HailOptions::new(4_000.0, 7_000.0);
+
+        let samples: Vec<(f32, f32)> = (0..=12).map(|km| (km as f32 * 1_000.0, 60.0)).collect();
+        let estimate = hail_estimate(&samples, &options);
+
+        // Hail kinetic energy flux at 60 dBZ, weighted by 1.5 km of the transition layer and 5 km
+        // above the -20 C level.
+        let expected = 0.1 * 5.0e-6 * 10.0f32.powf(0.084 * 60.0) * 6_500.0;
+        assert!((estimate.severe_hail_index() - expected).abs() < 1.0);
+        assert_eq!(estimate.probability_of_hail_percent(), 100.0);
+        assert!(estimate.probability_of_severe_hail_percent() > 80.0);
+
+        let weak: Vec<(f32, f32)> = (0..=12).map(|km| (km as f32 * 1_000.0, 35.0)).collect();
+        let estimate = hail_estimate(&weak, &options);
+        assert_eq!(estimate.probability_of_hail_percent(), 0.0);
+        assert_eq!(estimate.max_expected_size_millimeters(), 0.0);
+    }
+
+    #[test]
+    fn estimates_cell_and_grid() {
+        let scan = hailstorm_scan();
+        let options = HailOptions::new(4_000.0, 7_000.0);
+
+        let estimate = cell_hail(&scan, 90.0, 30_000.0, 5_000.0, &options).unwrap();
+        assert_eq!(estimate.probability_of_hail_percent(), 100.0);
+        assert!(estimate.max_expected_size_millimeters() > 25.0);
+
+        let fields = hail_fields(&scan, &options).unwrap();
+        let mesh = &fields.max_expected_size;
+        let radial_index = mesh.radial_index(90.0).unwrap();
+
+        let storm = mesh
+            .value(radial_index, mesh.gate_index(30_000.0).unwrap())
+            .unwrap();
+        assert!(storm > 25.0, "mesh {storm}");
+
+        let clear = mesh.value(radial_index, mesh.gate_index(60_000.0).unwrap());
+        assert_eq!(clear, None);
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Hail estimates for a storm cell or a vertical column of reflectivity.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HailEstimate {
+    probability_of_hail_percent: f32,
+    probability_of_severe_hail_percent: f32,
+    max_expected_size_millimeters: f32,
+    severe_hail_index: f32,
+}
+
+impl HailEstimate {
+    /// Create a new hail estimate with the given properties.
+    pub fn new(
+        probability_of_hail_percent: f32,
+        probability_of_severe_hail_percent: f32,
+        max_expected_size_millimeters: f32,
+        severe_hail_index: f32,
+    ) -> Self {
+        Self {
+            probability_of_hail_percent,
+            probability_of_severe_hail_percent,
+            max_expected_size_millimeters,
+            severe_hail_index,
+        }
+    }
+
+    /// The probability of hail of any size reaching the ground, from 0 to 100 percent.
+    pub fn probability_of_hail_percent(&self) -> f32 {
+        self.probability_of_hail_percent
+    }
+
+    /// The probability of severe hail, at least 19 millimeters in diameter, from 0 to 100 percent.
+    pub fn probability_of_severe_hail_percent(&self) -> f32 {
+        self.probability_of_severe_hail_percent
+    }
+
+    /// The maximum expected size of hail (MESH) in millimeters.
+    pub fn max_expected_size_millimeters(&self) -> f32 {
+        self.max_expected_size_millimeters
+    }
+
+    /// The severe hail index (SHI) in joules per meter per second, a temperature-weighted vertical
+    /// integral of hail kinetic energy flux.
+    pub fn severe_hail_index(&self) -> f32 {
+        self.severe_hail_index
+    }
+}
+
+impl Debug for HailEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HailEstimate")
+            .field(
+                "probability_of_hail_percent",
+                &self.probability_of_hail_percent(),
+            )
+            .field(
+                "probability_of_severe_hail_percent",
+                &self.probability_of_severe_hail_percent(),
+            )
+            .field(
+                "max_expected_size_millimeters",
+                &self.max_expected_size_millimeters(),
+            )
+            .field("severe_hail_index", &self.severe_hail_index())
+            .finish()
+    }
+}
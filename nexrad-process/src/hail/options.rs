@@ -0,0 +1,53 @@
+use crate::result::{Error, Result};
+
+/// Options controlling the hail detection algorithm, including the environmental heights it
+/// depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HailOptions {
+    /// The height of the 0 degree Celsius (melting/freezing) level in meters above the radar.
+    pub freezing_level_height_meters: f32,
+
+    /// The height of the -20 degree Celsius level in meters above the radar.
+    pub minus_20_celsius_height_meters: f32,
+
+    /// The azimuthal spacing in degrees of gridded hail fields.
+    pub grid_azimuth_spacing_degrees: f32,
+
+    /// The ground distance in meters between gates of gridded hail fields.
+    pub grid_gate_interval_meters: f32,
+
+    /// The maximum ground range in meters of gridded hail fields.
+    pub grid_max_range_meters: f32,
+}
+
+impl HailOptions {
+    /// Create new hail options with the given environmental heights in meters above the radar and
+    /// default grid dimensions.
+    pub fn new(freezing_level_height_meters: f32, minus_20_celsius_height_meters: f32) -> Self {
+        Self {
+            freezing_level_height_meters,
+            minus_20_celsius_height_meters,
+            grid_azimuth_spacing_degrees: 1.0,
+            grid_gate_interval_meters: 1_000.0,
+            grid_max_range_meters: 230_000.0,
+        }
+    }
+
+    /// Checks that these options are usable for hail detection.
+    pub fn validate(&self) -> Result<()> {
+        if self.minus_20_celsius_height_meters <= self.freezing_level_height_meters {
+            return Err(Error::InvalidOptions(
+                "the -20 C level must be above the freezing level",
+            ));
+        }
+
+        if self.grid_azimuth_spacing_degrees <= 0.0
+            || self.grid_gate_interval_meters <= 0.0
+            || self.grid_max_range_meters <= 0.0
+        {
+            return Err(Error::InvalidOptions("grid dimensions must be positive"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//!
+//! # nexrad-process
+//! Processing algorithms for NEXRAD weather radar data. Algorithms operate on the common model
+//! provided by `nexrad-model` and derive meteorological products from Level II base data.
+//!
+//! A number of optional features are available:
+//! - `serde`: Implement `serde::Serialize` and `serde::Deserialize` for all algorithm outputs.
+//! - `tiff`: Load digital elevation models for beam blockage from GeoTIFF files.
+//!
+
+#![forbid(unsafe_code)]
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![warn(clippy::correctness)]
+#![allow(clippy::too_many_arguments)]
+
+pub mod biology;
+pub mod blockage;
+pub mod cell;
+pub mod debris;
+pub mod field;
+pub mod grid;
+pub mod hail;
+pub mod nowcast;
+pub mod qc;
+pub mod resample;
+pub mod result;
+pub mod section;
+pub mod shear;
+pub mod sun;
+pub mod vad;
+pub mod vortex;
+pub mod zdr;
+
+mod association;
+mod math;
+mod regions;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,54 @@
+/// Folds a velocity difference into the interval +/- half the given interval.
+pub(crate) fn fold(velocity: f32, interval: f32) -> f32 {
+    velocity - interval * (velocity / interval).round()
+}
+
+/// Solves a 3x3 linear system using Cramer's rule.
+pub(crate) fn solve_3x3(matrix: [[f64; 3]; 3], target: [f64; 3]) -> Option<[f64; 3]> {
+    let base = determinant(matrix);
+    if base.abs() < 1e-9 {
+        return None;
+    }
+
+    let mut solution = [0.0; 3];
+    for (column, value) in solution.iter_mut().enumerate() {
+        let mut substituted = matrix;
+        for row in 0..3 {
+            substituted[row][column] = target[row];
+        }
+        *value = determinant(substituted) / base;
+    }
+
+    Some(solution)
+}
+
+/// The standard deviation of the valid values, if there are at least two.
+pub(crate) fn standard_deviation(values: &[f32]) -> Option<f32> {
+    let valid: Vec<f32> = values
+        .iter()
+        .copied()
+        .filter(|value| !value.is_nan())
+        .collect();
+    if valid.len() < 2 {
+        return None;
+    }
+
+    let mean = valid.iter().sum::<f32>() / valid.len() as f32;
+    let variance = valid
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f32>()
+        / valid.len() as f32;
+    Some(variance.sqrt())
+}
+
+/// A membership increasing linearly from 0 at the lower value to 1 at the upper value.
+pub(crate) fn ramp(value: f32, lower: f32, upper: f32) -> f32 {
+    ((value - lower) / (upper - lower)).clamp(0.0, 1.0)
+}
+
+fn determinant(m: [[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
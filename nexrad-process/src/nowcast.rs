@@ -0,0 +1,378 @@
+//!
+//! Motion estimation and extrapolation nowcasting of reflectivity. Each scan's reflectivity is
+//! composited onto a Cartesian grid, and boxes of the grid are matched between consecutive scans by
+//! cross-correlation in the manner of Tracking Radar Echoes by Correlation (TREC). The resulting
+//! motion vectors are quality controlled and smoothed into a motion field, along which the latest
+//! composite is advected to produce forecasts.
+//!
+//! Scan times are taken from the earliest radial collection timestamp in each scan.
+//!
+
+mod options;
+pub use options::*;
+
+mod motion;
+
+use crate::field::PolarField;
+use crate::grid::CartesianField;
+use crate::result::{Error, Result};
+use motion::match_boxes;
+use nexrad_model::data::{Radial, Scan};
+use nexrad_model::geometry::slant_range_meters;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The longest time in seconds positions are advected along the motion field in a single step.
+const ADVECTION_STEP_SECONDS: f32 = 60.0;
+
+/// A field of echo motion. Vectors are given at the centers of the boxes matched between scans.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MotionField {
+    /// The eastward component of motion in meters per second.
+    pub east: CartesianField,
+
+    /// The northward component of motion in meters per second.
+    pub north: CartesianField,
+}
+
+impl MotionField {
+    /// The motion in meters per second east and north at the given position in meters east and
+    /// north of the radar, interpolated between the surrounding vectors.
+    pub fn motion_at(&self, east_meters: f32, north_meters: f32) -> Option<(f32, f32)> {
+        Some((
+            self.east.interpolate(east_meters, north_meters)?,
+            self.north.interpolate(east_meters, north_meters)?,
+        ))
+    }
+}
+
+/// A forecast of composite reflectivity.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Forecast {
+    /// The time in seconds after the latest scan this forecast is for.
+    pub lead_time_seconds: f32,
+
+    /// The time this forecast is valid for in milliseconds since the UNIX epoch.
+    pub valid_timestamp: i64,
+
+    /// The forecast composite reflectivity in dBZ.
+    pub reflectivity: CartesianField,
+}
+
+/// The earliest radial collection timestamp in a scan in milliseconds since the UNIX epoch.
+pub fn scan_timestamp(scan: &Scan) -> Option<i64> {
+    scan.sweeps()
+        .iter()
+        .flat_map(|sweep| sweep.radials())
+        .map(|radial| radial.collection_timestamp())
+        .min()
+}
+
+/// Composites the largest reflectivity in dBZ above each cell of a Cartesian grid across all of a
+/// scan's sweeps.
+pub fn composite_reflectivity(scan: &Scan, options: &NowcastOptions) -> Result<CartesianField> {
+    options.validate()?;
+
+    let sweeps: Vec<(PolarField, f32)> = scan
+        .sweeps()
+        .iter()
+        .filter_map(|sweep| {
+            let field = PolarField::from_sweep(sweep, Radial::reflectivity)?;
+            let elevation_angle = field.elevation_angle_degrees().unwrap_or_default();
+            Some((field, elevation_angle))
+        })
+        .collect();
+
+    let mut composite =
+        CartesianField::centered(options.grid_spacing_meters, options.grid_radius_meters);
+    for row in 0..composite.row_count() {
+        let north = composite.north_meters(row);
+        for column in 0..composite.column_count() {
+            let east = composite.east_meters(column);
+            let azimuth = east.atan2(north).to_degrees().rem_euclid(360.0);
+            let ground_range = east.hypot(north);
+
+            let value = sweeps
+                .iter()
+                .filter_map(|(field, elevation_angle)| {
+                    field.value_at(azimuth, slant_range_meters(ground_range, *elevation_angle)?)
+                })
+                .reduce(f32::max);
+
+            if let Some(value) = value {
+                composite.set(column, row, value);
+            }
+        }
+    }
+
+    Ok(composite)
+}
+
+/// Estimates the motion of reflectivity from two or more scans ordered by time. Motion is matched
+/// between each consecutive pair of scans and averaged.
+pub fn estimate_motion(scans: &[Scan], options: &NowcastOptions) -> Result<MotionField> {
+    options.validate()?;
+
+    let composites = timed_composites(scans, options)?;
+    Ok(motion_field(&composites, options))
+}
+
+/// Extrapolates composite reflectivity along a motion field by the given lead time in seconds.
+/// Each cell takes the value found by tracing its position backwards along the motion.
+pub fn extrapolate(
+    reflectivity: &CartesianField,
+    motion: &MotionField,
+    lead_time_seconds: f32,
+) -> CartesianField {
+    let mut forecast = reflectivity.empty_like();
+
+    let steps = (lead_time_seconds / ADVECTION_STEP_SECONDS).ceil().max(1.0) as usize;
+    let step_seconds = lead_time_seconds / steps as f32;
+
+    for row in 0..forecast.row_count() {
+        for column in 0..forecast.column_count() {
+            let mut position = (forecast.east_meters(column), forecast.north_meters(row));
+            for _ in 0..steps {
+                let (east, north) = motion.motion_at(position.0, position.1).unwrap_or_default();
+                position = (
+                    position.0 - east * step_seconds,
+                    position.1 - north * step_seconds,
+                );
+            }
+
+            if let Some(value) = reflectivity.value_at(position.0, position.1) {
+                forecast.set(column, row, value);
+            }
+        }
+    }
+
+    forecast
+}
+
+/// Produces reflectivity forecasts from two or more scans ordered by time, at each forecast
+/// interval from the latest scan up to the maximum lead time. The first forecast has no lead time
+/// and is the latest scan's composite.
+pub fn nowcast(scans: &[Scan], options: &NowcastOptions) -> Result<Vec<Forecast>> {
+    options.validate()?;
+
+    let composites = timed_composites(scans, options)?;
+    let motion = motion_field(&composites, options);
+    let (latest_timestamp, latest) = match composites.last() {
+        Some(latest) => latest,
+        None => return Err(Error::InsufficientScans),
+    };
+
+    let forecast_count =
+        (options.max_lead_time_seconds / options.forecast_interval_seconds).floor() as usize;
+
+    Ok((0..=forecast_count)
+        .map(|index| {
+            let lead_time_seconds = index as f32 * options.forecast_interval_seconds;
+            Forecast {
+                lead_time_seconds,
+                valid_timestamp: latest_timestamp + (lead_time_seconds * 1000.0) as i64,
+                reflectivity: extrapolate(latest, &motion, lead_time_seconds),
+            }
+        })
+        .collect())
+}
+
+/// Composites each scan, checking that there are at least two with increasing timestamps.
+fn timed_composites(
+    scans: &[Scan],
+    options: &NowcastOptions,
+) -> Result<Vec<(i64, CartesianField)>> {
+    if scans.len() < 2 {
+        return Err(Error::InsufficientScans);
+    }
+
+    let mut composites: Vec<(i64, CartesianField)> = Vec::with_capacity(scans.len());
+    for scan in scans {
+        let timestamp = scan_timestamp(scan).ok_or(Error::InsufficientScans)?;
+        if composites
+            .last()
+            .is_some_and(|(previous, _)| timestamp <= *previous)
+        {
+            return Err(Error::OutOfOrderScan);
+        }
+
+        composites.push((timestamp, composite_reflectivity(scan, options)?));
+    }
+
+    Ok(composites)
+}
+
+/// Matches boxes between each consecutive pair of composites, then averages, fills and smooths the
+/// vectors into a motion field.
+fn motion_field(composites: &[(i64, CartesianField)], options: &NowcastOptions) -> MotionField {
+    let box_cells =
+        ((options.box_size_meters / options.grid_spacing_meters).round() as usize).max(1);
+
+    let mut sums: Vec<(f32, f32, usize)> = Vec::new();
+    let mut grid: Option<CartesianField> = None;
+
+    for pair in composites.windows(2) {
+        let ((earlier_timestamp, earlier), (later_timestamp, later)) = (&pair[0], &pair[1]);
+        let elapsed_seconds = (later_timestamp - earlier_timestamp) as f32 / 1000.0;
+
+        let vectors = match_boxes(earlier, later, box_cells, elapsed_seconds, options);
+        if sums.is_empty() {
+            sums = vec![(0.0, 0.0, 0); vectors.len()];
+        }
+
+        for (sum, vector) in sums.iter_mut().zip(vectors) {
+            if let Some((east, north)) = vector {
+                *sum = (sum.0 + east, sum.1 + north, sum.2 + 1);
+            }
+        }
+
+        grid.get_or_insert_with(|| {
+            let offset = (box_cells - 1) as f32 / 2.0 * earlier.spacing_meters();
+            CartesianField::empty(
+                earlier.east_meters(0) + offset,
+                earlier.north_meters(0) + offset,
+                earlier.spacing_meters() * box_cells as f32,
+                earlier.column_count() / box_cells,
+                earlier.row_count() / box_cells,
+            )
+        });
+    }
+
+    let grid = grid.unwrap_or_else(|| CartesianField::empty(0.0, 0.0, 1.0, 0, 0));
+    let (columns, rows) = (grid.column_count(), grid.row_count());
+
+    let vectors: Vec<Option<(f32, f32)>> = sums
+        .iter()
+        .map(|(east, north, count)| {
+            (*count > 0).then(|| (east / *count as f32, north / *count as f32))
+        })
+        .collect();
+
+    let valid: Vec<(f32, f32)> = vectors.iter().flatten().copied().collect();
+    let mean = if valid.is_empty() {
+        (0.0, 0.0)
+    } else {
+        let count = valid.len() as f32;
+        let (east, north) = valid.iter().fold((0.0, 0.0), |(east, north), vector| {
+            (east + vector.0, north + vector.1)
+        });
+        (east / count, north / count)
+    };
+
+    let mut field = MotionField {
+        east: grid.empty_like(),
+        north: grid,
+    };
+
+    for row in 0..rows {
+        for column in 0..columns {
+            let mut total = (0.0, 0.0);
+            let mut count = 0;
+            for neighbor_row in row.saturating_sub(1)..=(row + 1).min(rows - 1) {
+                for neighbor_column in column.saturating_sub(1)..=(column + 1).min(columns - 1) {
+                    if let Some((east, north)) = vectors[neighbor_row * columns + neighbor_column] {
+                        total = (total.0 + east, total.1 + north);
+                        count += 1;
+                    }
+                }
+            }
+
+            let (east, north) = if count > 0 {
+                (total.0 / count as f32, total.1 / count as f32)
+            } else {
+                mean
+            };
+
+            field.east.set(column, row, east);
+            field.north.set(column, row, north);
+        }
+    }
+
+    field
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::{Product, Sweep};
+
+    /// A single-sweep scan taken at the given time in seconds containing a storm whose reflectivity
+    /// decreases from 55 dBZ at its center, at the given position in meters east and north of the
+    /// radar, to 20 dBZ 12 km away.
+    fn storm_scan(time_seconds: i64, (center_east, center_north): (f32, f32)) -> Scan {
+        let radials = (0..720)
+            .map(|azimuth_number| {
+                let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                let (sin, cos) = azimuth.to_radians().sin_cos();
+
+                let values = (0..320)
+                    .map(|gate| {
+                        let range = 2_000.0 + gate as f32 * 250.0;
+                        let distance =
+                            (range * sin - center_east).hypot(range * cos - center_north);
+                        if distance > 12_000.0 {
+                            return 0;
+                        }
+
+                        let reflectivity = 55.0 - 35.0 * distance / 12_000.0;
+                        (reflectivity * 2.0 + 66.0).round() as u8
+                    })
+                    .collect();
+
+                radial(time_seconds * 1000, azimuth_number, 0.5, 1, 0.5).with_moment(
+                    Product::Reflectivity,
+                    moment(&Product::Reflectivity, values),
+                )
+            })
+            .collect();
+
+        Scan::new(212, vec![Sweep::new(1, radials)])
+    }
+
+    fn options() -> NowcastOptions {
+        NowcastOptions {
+            grid_radius_meters: 70_000.0,
+            max_lead_time_seconds: 1_200.0,
+            ..NowcastOptions::default()
+        }
+    }
+
+    #[test]
+    fn estimates_motion_and_extrapolates() {
+        // A storm moving east at 15 meters per second, observed every five minutes.
+        let scans = [
+            storm_scan(0, (20_000.0, 30_000.0)),
+            storm_scan(300, (24_500.0, 30_000.0)),
+        ];
+
+        let motion = estimate_motion(&scans, &options()).unwrap();
+        let (east, north) = motion.motion_at(24_500.0, 30_000.0).unwrap();
+        assert!((east - 15.0).abs() < 3.0, "east {east}");
+        assert!(north.abs() < 3.0, "north {north}");
+
+        let forecasts = nowcast(&scans, &options()).unwrap();
+        assert_eq!(forecasts.len(), 5);
+
+        let forecast = &forecasts[4];
+        assert_eq!(forecast.lead_time_seconds, 1_200.0);
+        assert_eq!(forecast.valid_timestamp, 1_500_000);
+
+        // After 20 minutes the storm's center should have moved a further 18 km east.
+        let center = forecast.reflectivity.value_at(42_500.0, 30_000.0).unwrap();
+        assert!(center > 50.0, "center {center}");
+        assert!(forecast.reflectivity.value_at(24_500.0, 30_000.0).is_none());
+    }
+
+    #[test]
+    fn requires_ordered_scans() {
+        let earlier = storm_scan(0, (20_000.0, 30_000.0));
+        let later = storm_scan(300, (24_500.0, 30_000.0));
+
+        assert!(nowcast(std::slice::from_ref(&later), &options()).is_err());
+        assert!(nowcast(&[later, earlier], &options()).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::grid::CartesianField;
+use crate::nowcast::NowcastOptions;
+
+/// Matches boxes of the earlier field against the later field, returning each box's motion in
+/// meters per second east and north, ordered by box row and then column. Boxes without enough echo
+/// or a good enough match have no motion.
+pub(crate) fn match_boxes(
+    earlier: &CartesianField,
+    later: &CartesianField,
+    box_cells: usize,
+    elapsed_seconds: f32,
+    options: &NowcastOptions,
+) -> Vec<Option<(f32, f32)>> {
+    let box_columns = earlier.column_count() / box_cells;
+    let box_rows = earlier.row_count() / box_cells;
+    let search = (options.max_speed_meters_per_second * elapsed_seconds / earlier.spacing_meters())
+        .ceil() as isize;
+
+    let clipped = |field: &CartesianField, column: isize, row: isize| -> f32 {
+        if column < 0 || row < 0 {
+            return options.min_reflectivity_dbz;
+        }
+
+        field
+            .value(column as usize, row as usize)
+            .map_or(options.min_reflectivity_dbz, |value| {
+                value.max(options.min_reflectivity_dbz)
+            })
+    };
+
+    let mut motion = Vec::with_capacity(box_columns * box_rows);
+    for box_row in 0..box_rows {
+        for box_column in 0..box_columns {
+            let first_column = (box_column * box_cells) as isize;
+            let first_row = (box_row * box_cells) as isize;
+
+            let mut template = Vec::with_capacity(box_cells * box_cells);
+            for row in first_row..first_row + box_cells as isize {
+                for column in first_column..first_column + box_cells as isize {
+                    template.push(clipped(earlier, column, row));
+                }
+            }
+
+            let echo_count = template
+                .iter()
+                .filter(|value| **value > options.min_reflectivity_dbz)
+                .count();
+            if (echo_count as f32) < options.min_echo_fraction * template.len() as f32 {
+                motion.push(None);
+                continue;
+            }
+
+            let width = (2 * search + 1) as usize;
+            let mut correlations = vec![f32::NAN; width * width];
+            let mut best: Option<(f32, isize, isize)> = None;
+
+            for row_offset in -search..=search {
+                for column_offset in -search..=search {
+                    let mut candidate = Vec::with_capacity(template.len());
+                    for row in first_row..first_row + box_cells as isize {
+                        for column in first_column..first_column + box_cells as isize {
+                            candidate.push(clipped(
+                                later,
+                                column + column_offset,
+                                row + row_offset,
+                            ));
+                        }
+                    }
+
+                    let correlation = match correlation(&template, &candidate) {
+                        Some(correlation) => correlation,
+                        None => continue,
+                    };
+
+                    correlations[((row_offset + search) as usize) * width
+                        + (column_offset + search) as usize] = correlation;
+
+                    if best.is_none_or(|(best, _, _)| correlation > best) {
+                        best = Some((correlation, column_offset, row_offset));
+                    }
+                }
+            }
+
+            motion.push(
+                best.filter(|(correlation, _, _)| *correlation >= options.min_correlation)
+                    .map(|(_, column_offset, row_offset)| {
+                        let at = |column_offset: isize, row_offset: isize| {
+                            if column_offset.abs() > search || row_offset.abs() > search {
+                                return f32::NAN;
+                            }
+
+                            correlations[((row_offset + search) as usize) * width
+                                + (column_offset + search) as usize]
+                        };
+
+                        let column_shift = column_offset as f32
+                            + peak_offset(
+                                at(column_offset - 1, row_offset),
+                                at(column_offset, row_offset),
+                                at(column_offset + 1, row_offset),
+                            );
+                        let row_shift = row_offset as f32
+                            + peak_offset(
+                                at(column_offset, row_offset - 1),
+                                at(column_offset, row_offset),
+                                at(column_offset, row_offset + 1),
+                            );
+
+                        let scale = earlier.spacing_meters() / elapsed_seconds;
+                        (column_shift * scale, row_shift * scale)
+                    }),
+            );
+        }
+    }
+
+    motion
+}
+
+/// The Pearson correlation coefficient between two equally sized samples, if both vary.
+fn correlation(first: &[f32], second: &[f32]) -> Option<f32> {
+    let count = first.len() as f32;
+    let first_mean = first.iter().sum::<f32>() / count;
+    let second_mean = second.iter().sum::<f32>() / count;
+
+    let mut covariance = 0.0;
+    let mut first_variance = 0.0;
+    let mut second_variance = 0.0;
+    for (first, second) in first.iter().zip(second) {
+        let (first, second) = (first - first_mean, second - second_mean);
+        covariance += first * second;
+        first_variance += first * first;
+        second_variance += second * second;
+    }
+
+    if first_variance <= 0.0 || second_variance <= 0.0 {
+        return None;
+    }
+
+    Some(covariance / (first_variance * second_variance).sqrt())
+}
+
+/// The offset from the center sample, within half a sample, of the peak of a parabola fit through
+/// three equally spaced samples.
+fn peak_offset(before: f32, center: f32, after: f32) -> f32 {
+    let curvature = before - 2.0 * center + after;
+    if before.is_nan() || after.is_nan() || curvature >= 0.0 {
+        return 0.0;
+    }
+
+    (0.5 * (before - after) / curvature).clamp(-0.5, 0.5)
+}
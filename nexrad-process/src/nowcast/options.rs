@@ -0,0 +1,80 @@
+use crate::result::{Error, Result};
+
+/// Options controlling motion estimation and extrapolation nowcasting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NowcastOptions {
+    /// The distance in meters between the centers of adjacent cells of composite reflectivity
+    /// grids.
+    pub grid_spacing_meters: f32,
+
+    /// The distance in meters from the radar to which composite reflectivity grids extend in each
+    /// direction.
+    pub grid_radius_meters: f32,
+
+    /// The width in meters of the square boxes of reflectivity matched between scans, each of
+    /// which yields one motion vector.
+    pub box_size_meters: f32,
+
+    /// The fastest speed in meters per second echoes are expected to move. This bounds the search
+    /// for each box's displacement between scans.
+    pub max_speed_meters_per_second: f32,
+
+    /// Reflectivity in dBZ below which echoes are treated as clear air when matching boxes.
+    pub min_reflectivity_dbz: f32,
+
+    /// The minimum fraction of a box's cells which must contain echo for a motion vector to be
+    /// estimated from it.
+    pub min_echo_fraction: f32,
+
+    /// The minimum correlation between a box and its displaced match for the motion vector to be
+    /// kept. Boxes without a kept vector take the motion of their neighbors.
+    pub min_correlation: f32,
+
+    /// The interval in seconds between successive forecasts.
+    pub forecast_interval_seconds: f32,
+
+    /// The lead time in seconds of the last forecast.
+    pub max_lead_time_seconds: f32,
+}
+
+impl NowcastOptions {
+    /// Checks that these options are usable for nowcasting.
+    pub fn validate(&self) -> Result<()> {
+        if self.grid_spacing_meters <= 0.0
+            || self.grid_radius_meters <= 0.0
+            || self.box_size_meters < self.grid_spacing_meters
+        {
+            return Err(Error::InvalidOptions(
+                "grid dimensions must be positive and boxes at least one cell wide",
+            ));
+        }
+
+        if self.max_speed_meters_per_second <= 0.0 {
+            return Err(Error::InvalidOptions("maximum speed must be positive"));
+        }
+
+        if self.forecast_interval_seconds <= 0.0 || self.max_lead_time_seconds < 0.0 {
+            return Err(Error::InvalidOptions(
+                "forecast interval must be positive and lead time non-negative",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for NowcastOptions {
+    fn default() -> Self {
+        Self {
+            grid_spacing_meters: 2_000.0,
+            grid_radius_meters: 230_000.0,
+            box_size_meters: 16_000.0,
+            max_speed_meters_per_second: 40.0,
+            min_reflectivity_dbz: 15.0,
+            min_echo_fraction: 0.1,
+            min_correlation: 0.5,
+            forecast_interval_seconds: 300.0,
+            max_lead_time_seconds: 3_600.0,
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//!
+//! Gate-level quality control of sweep data. Gates are flagged as speckle when they belong to
+//! small, isolated regions of echo; as clutter when a fuzzy combination of reflectivity texture,
+//! near-zero velocity, low velocity texture and low correlation coefficient indicates ground
+//! clutter or anomalous propagation; and as second-trip echo when they are range folded or have the
+//! low correlation, broad spectrum width and weak reflectivity typical of echoes from beyond the
+//! unambiguous range. Gates may also be flagged where the RDA's clutter filter map forces
+//! filtering.
+//!
+//! Flagged gates are masked as below threshold in every moment of the output sweep, alongside a
+//! field of the flags for each gate.
+//!
+
+mod flags;
+pub use flags::*;
+
+mod options;
+pub use options::*;
+
+use crate::field::PolarField;
+use crate::math::{ramp, standard_deviation};
+use crate::regions::connected_regions;
+use crate::result::Result;
+use nexrad_model::data::{MomentData, MomentValue, Product, Radial, Sweep};
+use nexrad_model::meta::{ClutterFilterAction, ClutterFilterMap};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A sweep with flagged gates masked, along with the flags for each gate.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QualityControlled {
+    /// The sweep with gates whose flags intersect [QcOptions::mask] set below threshold.
+    pub sweep: Sweep,
+
+    /// The flags for each gate, in the geometry of the sweep's reflectivity, or its velocity if it
+    /// has no reflectivity.
+    pub flags: QcFlagField,
+}
+
+/// Flags and masks a sweep's gates, optionally applying the site's clutter filter map.
+pub fn quality_control(
+    sweep: &Sweep,
+    clutter_map: Option<&ClutterFilterMap>,
+    options: &QcOptions,
+) -> Result<QualityControlled> {
+    let flags = sweep_flags(sweep, clutter_map, options)?;
+    Ok(QualityControlled {
+        sweep: mask_sweep(sweep, &flags, options.mask),
+        flags,
+    })
+}
+
+/// Flags a sweep's gates, optionally applying the site's clutter filter map. The flags are in the
+/// geometry of the sweep's reflectivity, or its velocity if it has no reflectivity. A sweep with
+/// neither has an empty flag field.
+pub fn sweep_flags(
+    sweep: &Sweep,
+    clutter_map: Option<&ClutterFilterMap>,
+    options: &QcOptions,
+) -> Result<QcFlagField> {
+    options.validate()?;
+
+    let reflectivity = PolarField::from_sweep(sweep, Radial::reflectivity);
+    let velocity = PolarField::from_sweep(sweep, Radial::velocity);
+    let spectrum_width = PolarField::from_sweep(sweep, Radial::spectrum_width);
+    let correlation = PolarField::from_sweep(sweep, Radial::correlation_coefficient);
+
+    let reference = match reflectivity.as_ref().or(velocity.as_ref()) {
+        Some(reference) => reference,
+        None => return Ok(QcFlagField::empty(Vec::new(), 0.0, 0.0, 0)),
+    };
+
+    let mut flags = QcFlagField::empty_like(reference);
+    let (radial_count, gate_count) = (reference.radial_count(), reference.gate_count());
+
+    let echo: Vec<bool> = reference
+        .values()
+        .iter()
+        .map(|value| !value.is_nan())
+        .collect();
+    for region in connected_regions(radial_count, gate_count, &echo) {
+        if region.len() < options.min_region_gates {
+            for (radial_index, gate_index) in region {
+                flags.insert(radial_index, gate_index, QcFlags::SPECKLE);
+            }
+        }
+    }
+
+    let sample = |field: &Option<PolarField>, radial_index: usize| -> Vec<f32> {
+        let azimuth = reference.azimuths_degrees()[radial_index];
+        (0..gate_count)
+            .map(|gate_index| {
+                field
+                    .as_ref()
+                    .and_then(|field| {
+                        field.value_at(azimuth, reference.gate_range_meters(gate_index))
+                    })
+                    .unwrap_or(f32::NAN)
+            })
+            .collect()
+    };
+
+    let half_window = options.texture_window_gates / 2;
+    for radial_index in 0..radial_count {
+        let reflectivity_values = match &reflectivity {
+            Some(_) => reference.radial_values(radial_index).to_vec(),
+            None => vec![f32::NAN; gate_count],
+        };
+        let velocity_values = sample(&velocity, radial_index);
+        let spectrum_width_values = sample(&spectrum_width, radial_index);
+        let correlation_values = sample(&correlation, radial_index);
+
+        for gate_index in 0..gate_count {
+            let window = gate_index.saturating_sub(half_window)
+                ..(gate_index + half_window + 1).min(gate_count);
+
+            let reflectivity_value = reflectivity_values[gate_index];
+            let velocity_value = velocity_values[gate_index];
+            let correlation_value = correlation_values[gate_index];
+
+            let texture = reflectivity_texture(&reflectivity_values[window.clone()]);
+            if !reflectivity_value.is_nan()
+                && reference.gate_range_meters(gate_index) <= options.clutter_max_range_meters
+            {
+                if let Some(texture) = texture {
+                    let mut memberships = vec![ramp(texture, 20.0, 60.0)];
+                    if !velocity_value.is_nan() {
+                        memberships.push(1.0 - ramp(velocity_value.abs(), 0.5, 2.0));
+                    }
+                    if let Some(deviation) = standard_deviation(&velocity_values[window]) {
+                        memberships.push(1.0 - ramp(deviation, 0.7, 2.0));
+                    }
+                    if !correlation_value.is_nan() {
+                        memberships.push(1.0 - ramp(correlation_value, 0.85, 0.95));
+                    }
+
+                    let likelihood = memberships.iter().sum::<f32>() / memberships.len() as f32;
+                    if likelihood >= options.clutter_threshold {
+                        flags.insert(radial_index, gate_index, QcFlags::CLUTTER);
+                    }
+                }
+            }
+
+            let spectrum_width_value = spectrum_width_values[gate_index];
+            if correlation_value <= options.second_trip_max_correlation
+                && spectrum_width_value >= options.second_trip_min_spectrum_width_meters_per_second
+                && (reflectivity_value.is_nan()
+                    || reflectivity_value <= options.second_trip_max_reflectivity_dbz)
+            {
+                flags.insert(radial_index, gate_index, QcFlags::SECOND_TRIP);
+            }
+        }
+    }
+
+    for radial in sweep.radials() {
+        for moment in [radial.velocity(), radial.spectrum_width()]
+            .into_iter()
+            .flatten()
+        {
+            for (gate_index, value) in moment.values().into_iter().enumerate() {
+                if value != MomentValue::RangeFolded {
+                    continue;
+                }
+
+                let range = moment.gate_range_meters(gate_index);
+                if let (Some(radial_index), Some(flag_gate_index)) = (
+                    flags.radial_index(radial.azimuth_angle_degrees()),
+                    flags.gate_index(range),
+                ) {
+                    flags.insert(radial_index, flag_gate_index, QcFlags::SECOND_TRIP);
+                }
+            }
+        }
+    }
+
+    if let Some(clutter_map) = clutter_map {
+        let elevation_angle = reference.elevation_angle_degrees().unwrap_or_default();
+        let segment = options
+            .clutter_map_segment_tops_degrees
+            .iter()
+            .filter(|top| elevation_angle > **top)
+            .count()
+            .min(clutter_map.elevation_segment_count().saturating_sub(1));
+
+        for (radial_index, azimuth) in reference.azimuths_degrees().iter().enumerate() {
+            for gate_index in 0..gate_count {
+                let range = reference.gate_range_meters(gate_index);
+                if clutter_map.action(segment, *azimuth, range) == Some(ClutterFilterAction::Force)
+                {
+                    flags.insert(radial_index, gate_index, QcFlags::CLUTTER_MAP);
+                }
+            }
+        }
+    }
+
+    Ok(flags)
+}
+
+/// Masks gates whose flags intersect the given mask as below threshold in every moment of a
+/// sweep's radials.
+pub fn mask_sweep(sweep: &Sweep, flags: &QcFlagField, mask: QcFlags) -> Sweep {
+    let radials = sweep
+        .radials()
+        .iter()
+        .map(|radial| {
+            let azimuth = radial.azimuth_angle_degrees();
+            let masked: Vec<(Product, MomentData)> = radial
+                .moments()
+                .map(|(product, moment)| {
+                    let mut values = moment.raw_values().clone();
+                    let word_length = usize::from(moment.word_size() / 8).max(1);
+                    for (gate_index, word) in values.chunks_exact_mut(word_length).enumerate() {
+                        match flags.flags_at(azimuth, moment.gate_range_meters(gate_index)) {
+                            Some(gate_flags) if gate_flags.intersects(mask) => word.fill(0),
+                            _ => {}
+                        }
+                    }
+
+                    let moment =
+                        MomentData::from_fixed_point(moment.scale(), moment.offset(), values)
+                            .with_gate_geometry(
+                                moment.first_gate_range_meters(),
+                                moment.gate_interval_meters(),
+                            )
+                            .with_word_size(moment.word_size());
+                    (product, moment)
+                })
+                .collect();
+
+            let mut radial = radial.clone();
+            for (product, moment) in masked {
+                radial.set_moment(product, moment);
+            }
+            radial
+        })
+        .collect();
+
+    Sweep::new(sweep.elevation_number(), radials)
+}
+
+/// The mean squared difference in dB between adjacent valid reflectivity gates, if any.
+fn reflectivity_texture(values: &[f32]) -> Option<f32> {
+    let differences: Vec<f32> = values
+        .windows(2)
+        .filter(|pair| !pair[0].is_nan() && !pair[1].is_nan())
+        .map(|pair| (pair[1] - pair[0]).powi(2))
+        .collect();
+
+    (!differences.is_empty()).then(|| differences.iter().sum::<f32>() / differences.len() as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::Product;
+    use nexrad_model::meta::ClutterFilterZone;
+
+    /// A sweep of uniform 30 dBZ rain moving at 10 m/s beyond 40 km, with ground clutter of
+    /// alternating 20 and 50 dBZ and zero velocity within 10 km, a single isolated gate of echo at
+    /// 25 km on one radial, and range-folded velocities beyond 80 km.
+    fn noisy_sweep() -> Sweep {
+        let radials = (0..360)
+            .map(|azimuth_number| {
+                let mut reflectivity = Vec::new();
+                let mut velocity = Vec::new();
+                let mut correlation = Vec::new();
+
+                for gate in 0..400 {
+                    let range = 2_000.0 + gate as f32 * 250.0;
+                    let (z, v, cc) = if range < 10_000.0 {
+                        (if gate % 2 == 0 { 20.0 } else { 50.0 }, Some(0.0), 0.7)
+                    } else if range > 40_000.0 {
+                        (30.0, (range <= 80_000.0).then_some(10.0), 0.99)
+                    } else if azimuth_number == 100 && gate == 92 {
+                        (25.0, Some(5.0), 0.95)
+                    } else {
+                        reflectivity.push(0);
+                        velocity.push(0);
+                        correlation.push(0);
+                        continue;
+                    };
+
+                    reflectivity.push((z * 2.0 + 66.0) as u8);
+                    velocity.push(v.map_or(1, |v: f32| (v * 2.0 + 129.0) as u8));
+                    correlation.push((cc * 300.0f32 - 60.5).round() as u8);
+                }
+
+                radial(0, azimuth_number, 1.0, 1, 0.5)
+                    .with_moment(
+                        Product::Reflectivity,
+                        moment(&Product::Reflectivity, reflectivity),
+                    )
+                    .with_moment(Product::Velocity, moment(&Product::Velocity, velocity))
+                    .with_moment(
+                        Product::CorrelationCoefficient,
+                        moment(&Product::CorrelationCoefficient, correlation),
+                    )
+            })
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    #[test]
+    fn flags_and_masks_gates() {
+        let sweep = noisy_sweep();
+        let controlled = quality_control(&sweep, None, &QcOptions::default()).unwrap();
+        let flags = &controlled.flags;
+
+        let clutter = flags.flags_at(45.5, 6_000.0).unwrap();
+        assert!(clutter.contains(QcFlags::CLUTTER), "{clutter:?}");
+
+        let rain = flags.flags_at(45.5, 60_000.0).unwrap();
+        assert!(rain.is_empty(), "{rain:?}");
+
+        let speckle = flags.flags_at(100.5, 25_000.0).unwrap();
+        assert!(speckle.contains(QcFlags::SPECKLE), "{speckle:?}");
+
+        let folded = flags.flags_at(45.5, 90_000.0).unwrap();
+        assert!(folded.contains(QcFlags::SECOND_TRIP), "{folded:?}");
+
+        let radial = &controlled.sweep.radials()[45];
+        let reflectivity = radial.reflectivity().unwrap().values();
+        assert_eq!(reflectivity[16], MomentValue::BelowThreshold);
+        assert_eq!(reflectivity[232], MomentValue::Value(30.0));
+    }
+
+    #[test]
+    fn applies_clutter_map() {
+        let zones = vec![
+            ClutterFilterZone::new(50_000, ClutterFilterAction::Bypass),
+            ClutterFilterZone::new(55_000, ClutterFilterAction::Force),
+            ClutterFilterZone::new(511_000, ClutterFilterAction::Bypass),
+        ];
+        let clutter_map = ClutterFilterMap::new(vec![vec![zones; 360]]);
+
+        let options = QcOptions {
+            mask: QcFlags::CLUTTER_MAP,
+            ..QcOptions::default()
+        };
+        let controlled = quality_control(&noisy_sweep(), Some(&clutter_map), &options).unwrap();
+
+        let flags = controlled.flags.flags_at(200.5, 52_000.0).unwrap();
+        assert_eq!(flags, QcFlags::CLUTTER_MAP);
+
+        let reflectivity = controlled.sweep.radials()[200]
+            .reflectivity()
+            .unwrap()
+            .values();
+        assert_eq!(reflectivity[200], MomentValue::BelowThreshold);
+        assert_eq!(reflectivity[21], MomentValue::Value(50.0));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::field::{nearest_radial, within_spacing, PolarField};
+use std::fmt::Debug;
+use std::ops::BitOr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A set of quality control flags for a gate. Flags may be combined with `|`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QcFlags(u8);
+
+impl QcFlags {
+    /// The gate belongs to a small, isolated region of echo.
+    pub const SPECKLE: Self = Self(1);
+
+    /// The gate's texture, velocity and correlation are characteristic of ground clutter or
+    /// anomalous propagation.
+    pub const CLUTTER: Self = Self(1 << 1);
+
+    /// The gate is range folded or characteristic of second-trip echo.
+    pub const SECOND_TRIP: Self = Self(1 << 2);
+
+    /// The gate lies in a zone where the clutter filter map forces clutter filtering.
+    pub const CLUTTER_MAP: Self = Self(1 << 3);
+
+    /// No flags.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Every flag.
+    pub const fn all() -> Self {
+        Self(Self::SPECKLE.0 | Self::CLUTTER.0 | Self::SECOND_TRIP.0 | Self::CLUTTER_MAP.0)
+    }
+
+    /// The raw bits of this set of flags.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Whether no flags are set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every flag in the other set is also set in this one.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether any flag in the other set is also set in this one.
+    pub fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Sets every flag in the other set.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitOr for QcFlags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// Quality control flags for each gate of a sweep, indexed by radial and gate like a
+/// [PolarField]. Radials are ordered by ascending azimuth and gates by ascending slant range.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QcFlagField {
+    azimuths_degrees: Vec<f32>,
+    first_gate_range_meters: f32,
+    gate_interval_meters: f32,
+    gate_count: usize,
+    flags: Vec<QcFlags>,
+}
+
+impl QcFlagField {
+    /// Create a new flag field with no flags set for each radial and gate. Azimuths should be in
+    /// ascending order within 0 to 360 degrees.
+    pub fn empty(
+        azimuths_degrees: Vec<f32>,
+        first_gate_range_meters: f32,
+        gate_interval_meters: f32,
+        gate_count: usize,
+    ) -> Self {
+        let flags = vec![QcFlags::empty(); azimuths_degrees.len() * gate_count];
+        Self {
+            azimuths_degrees,
+            first_gate_range_meters,
+            gate_interval_meters,
+            gate_count,
+            flags,
+        }
+    }
+
+    /// Create a new flag field with the same geometry as the given field and no flags set.
+    pub fn empty_like(field: &PolarField) -> Self {
+        Self::empty(
+            field.azimuths_degrees().clone(),
+            field.first_gate_range_meters(),
+            field.gate_interval_meters(),
+            field.gate_count(),
+        )
+    }
+
+    /// The azimuth angle in degrees of each radial in this field.
+    pub fn azimuths_degrees(&self) -> &Vec<f32> {
+        self.azimuths_degrees.as_ref()
+    }
+
+    /// The number of radials in this field.
+    pub fn radial_count(&self) -> usize {
+        self.azimuths_degrees.len()
+    }
+
+    /// The number of gates in each of this field's radials.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+
+    /// Range to the center of the first gate in meters.
+    pub fn first_gate_range_meters(&self) -> f32 {
+        self.first_gate_range_meters
+    }
+
+    /// Distance between the centers of adjacent gates in meters.
+    pub fn gate_interval_meters(&self) -> f32 {
+        self.gate_interval_meters
+    }
+
+    /// Range to the center of the gate at the given index in meters.
+    pub fn gate_range_meters(&self, gate_index: usize) -> f32 {
+        self.first_gate_range_meters + gate_index as f32 * self.gate_interval_meters
+    }
+
+    /// The index of the gate whose center is nearest the given range in meters, if any.
+    pub fn gate_index(&self, range_meters: f32) -> Option<usize> {
+        if self.gate_interval_meters <= 0.0 {
+            return None;
+        }
+
+        let index =
+            ((range_meters - self.first_gate_range_meters) / self.gate_interval_meters).round();
+        if index < 0.0 || index as usize >= self.gate_count {
+            return None;
+        }
+
+        Some(index as usize)
+    }
+
+    /// The index of the radial whose azimuth is nearest the given azimuth in degrees, if any.
+    pub fn radial_index(&self, azimuth_degrees: f32) -> Option<usize> {
+        nearest_radial(&self.azimuths_degrees, azimuth_degrees)
+    }
+
+    /// All of this field's flags ordered by radial and then gate.
+    pub fn values(&self) -> &Vec<QcFlags> {
+        self.flags.as_ref()
+    }
+
+    /// The flags at the given radial and gate, or [None] if the gate is out of bounds.
+    pub fn flags(&self, radial_index: usize, gate_index: usize) -> Option<QcFlags> {
+        if radial_index >= self.radial_count() || gate_index >= self.gate_count {
+            return None;
+        }
+
+        Some(self.flags[radial_index * self.gate_count + gate_index])
+    }
+
+    /// The flags of the gate nearest the given azimuth in degrees and range in meters. Returns
+    /// [None] if the nearest radial is more than a nominal radial spacing away or the range is out
+    /// of bounds.
+    pub fn flags_at(&self, azimuth_degrees: f32, range_meters: f32) -> Option<QcFlags> {
+        let radial_index = self.radial_index(azimuth_degrees)?;
+        if !within_spacing(&self.azimuths_degrees, radial_index, azimuth_degrees) {
+            return None;
+        }
+
+        self.flags(radial_index, self.gate_index(range_meters)?)
+    }
+
+    /// Sets the given flags at the given radial and gate, in addition to any already set.
+    /// Out-of-bounds gates are ignored.
+    pub fn insert(&mut self, radial_index: usize, gate_index: usize, flags: QcFlags) {
+        if radial_index < self.radial_count() && gate_index < self.gate_count {
+            self.flags[radial_index * self.gate_count + gate_index].insert(flags);
+        }
+    }
+
+    /// The number of gates with any of the given flags set.
+    pub fn count(&self, flags: QcFlags) -> usize {
+        self.flags
+            .iter()
+            .filter(|gate_flags| gate_flags.intersects(flags))
+            .count()
+    }
+}
+
+impl Debug for QcFlagField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QcFlagField")
+            .field("radial_count", &self.radial_count())
+            .field("gate_count", &self.gate_count())
+            .field("first_gate_range_meters", &self.first_gate_range_meters())
+            .field("gate_interval_meters", &self.gate_interval_meters())
+            .field("speckle", &self.count(QcFlags::SPECKLE))
+            .field("clutter", &self.count(QcFlags::CLUTTER))
+            .field("second_trip", &self.count(QcFlags::SECOND_TRIP))
+            .field("clutter_map", &self.count(QcFlags::CLUTTER_MAP))
+            .finish()
+    }
+}
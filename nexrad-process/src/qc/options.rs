@@ -0,0 +1,75 @@
+use crate::qc::QcFlags;
+use crate::result::{Error, Result};
+
+/// Options controlling gate-level quality control.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QcOptions {
+    /// Connected regions of echo with fewer gates than this are flagged as speckle.
+    pub min_region_gates: usize,
+
+    /// The number of gates along each radial over which reflectivity and velocity texture are
+    /// computed.
+    pub texture_window_gates: usize,
+
+    /// Clutter is only identified within this slant range in meters of the radar.
+    pub clutter_max_range_meters: f32,
+
+    /// The clutter likelihood, from 0 to 1, at or above which a gate is flagged as clutter. The
+    /// likelihood combines high reflectivity texture with near-zero velocity, low velocity texture
+    /// and low correlation coefficient where those moments are available.
+    pub clutter_threshold: f32,
+
+    /// Gates with correlation coefficient at or below this value, spectrum width at or above
+    /// [QcOptions::second_trip_min_spectrum_width_meters_per_second] and reflectivity at or below
+    /// [QcOptions::second_trip_max_reflectivity_dbz] are flagged as second-trip echo.
+    pub second_trip_max_correlation: f32,
+
+    /// The minimum spectrum width in meters per second of second-trip echo.
+    pub second_trip_min_spectrum_width_meters_per_second: f32,
+
+    /// The maximum reflectivity in dBZ of second-trip echo.
+    pub second_trip_max_reflectivity_dbz: f32,
+
+    /// The elevation angles in degrees dividing the clutter filter map's elevation segments. Sweeps
+    /// at or below the first angle use the first segment, those above it and at or below the
+    /// second use the second segment, and so on.
+    pub clutter_map_segment_tops_degrees: Vec<f32>,
+
+    /// The flags whose gates are masked as below threshold in every moment of the output sweep.
+    pub mask: QcFlags,
+}
+
+impl QcOptions {
+    /// Checks that these options are usable for quality control.
+    pub fn validate(&self) -> Result<()> {
+        if self.texture_window_gates < 2 {
+            return Err(Error::InvalidOptions(
+                "texture window must span at least two gates",
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&self.clutter_threshold) {
+            return Err(Error::InvalidOptions(
+                "clutter threshold must be between 0 and 1",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for QcOptions {
+    fn default() -> Self {
+        Self {
+            min_region_gates: 5,
+            texture_window_gates: 5,
+            clutter_max_range_meters: 150_000.0,
+            clutter_threshold: 0.6,
+            second_trip_max_correlation: 0.7,
+            second_trip_min_spectrum_width_meters_per_second: 6.0,
+            second_trip_max_reflectivity_dbz: 20.0,
+            clutter_map_segment_tops_degrees: vec![1.65],
+            mask: QcFlags::all(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+/// Labels the connected regions of flagged gates in a polar grid of the given dimensions, where
+/// gates are ordered by radial and then gate. Gates are connected to their eight neighbors, and
+/// the first and last radials are adjacent. Returns each region as a list of (radial, gate)
+/// indices.
+pub(crate) fn connected_regions(
+    radial_count: usize,
+    gate_count: usize,
+    flagged: &[bool],
+) -> Vec<Vec<(usize, usize)>> {
+    let mut visited = vec![false; flagged.len()];
+    let mut regions = Vec::new();
+
+    for start in 0..flagged.len() {
+        if !flagged[start] || visited[start] {
+            continue;
+        }
+
+        visited[start] = true;
+        let mut region = Vec::new();
+        let mut pending = vec![start];
+
+        while let Some(index) = pending.pop() {
+            let (radial, gate) = (index / gate_count, index % gate_count);
+            region.push((radial, gate));
+
+            for radial_offset in [radial_count - 1, 0, 1] {
+                let neighbor_radial = (radial + radial_offset) % radial_count;
+                for neighbor_gate in gate.saturating_sub(1)..=(gate + 1).min(gate_count - 1) {
+                    let neighbor = neighbor_radial * gate_count + neighbor_gate;
+                    if flagged[neighbor] && !visited[neighbor] {
+                        visited[neighbor] = true;
+                        pending.push(neighbor);
+                    }
+                }
+            }
+        }
+
+        regions.push(region);
+    }
+
+    regions
+}
@@ -0,0 +1,550 @@
+//!
+//! Resampling of sweeps between super-resolution and legacy resolution, so that products built for
+//! legacy resolution can run on modern data. Coarsening recombines adjacent radials or gates as the
+//! RPG does when producing legacy resolution base data: reflectivity and differential reflectivity
+//! are averaged in linear units, velocity is averaged weighted by each gate's linear reflectivity
+//! or taken from the strongest gate where the velocities span more than the Nyquist velocity,
+//! spectrum width is the root mean square of the widths, differential phase is averaged circularly,
+//! and other products are averaged arithmetically. A recombined gate without any valid values is
+//! range folded if any of its gates were, and below threshold otherwise. Refining splits each
+//! radial or gate into equal copies.
+//!
+
+mod options;
+pub use options::*;
+
+use crate::result::{Error, Result};
+use nexrad_model::data::{MomentData, MomentValue, Product, Radial, RadialStatus, Sweep};
+
+/// Ratios between spacings within this tolerance of a whole number are treated as whole.
+const RATIO_TOLERANCE: f32 = 0.01;
+
+/// Resamples a sweep's radials to the azimuth spacing and its products to the gate intervals given
+/// by the options. Radials and gates keep their centers consistent with their new spacing.
+pub fn resample_sweep(sweep: &Sweep, options: &ResampleOptions) -> Result<Sweep> {
+    options.validate()?;
+
+    let mut radials = match options.azimuth_spacing_degrees {
+        Some(spacing) => resample_azimuths(sweep.radials(), spacing)?,
+        None => sweep.radials().clone(),
+    };
+
+    for radial in &mut radials {
+        let mut resampled = Vec::new();
+        for (product, interval) in &options.gate_intervals_meters {
+            if let Some(data) = radial.moment(product) {
+                resampled.push((
+                    product.clone(),
+                    resample_gates(radial, product, data, *interval)?,
+                ));
+            }
+        }
+
+        for (product, data) in resampled {
+            radial.set_moment(product, data);
+        }
+    }
+
+    Ok(Sweep::new(sweep.elevation_number(), radials))
+}
+
+/// How a target spacing relates to a source spacing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Ratio {
+    Same,
+    Coarsen(usize),
+    Refine(usize),
+}
+
+/// The whole ratio between the given source and target spacings, if there is one.
+fn ratio(source: f32, target: f32) -> Option<Ratio> {
+    if source <= 0.0 || target <= 0.0 {
+        return None;
+    }
+
+    let (factor, coarsen) = if target >= source {
+        (target / source, true)
+    } else {
+        (source / target, false)
+    };
+    if (factor - factor.round()).abs() > RATIO_TOLERANCE {
+        return None;
+    }
+
+    Some(match (factor.round() as usize, coarsen) {
+        (1, _) => Ratio::Same,
+        (factor, true) => Ratio::Coarsen(factor),
+        (factor, false) => Ratio::Refine(factor),
+    })
+}
+
+/// Resamples radials to the given azimuth spacing in degrees.
+fn resample_azimuths(radials: &[Radial], spacing: f32) -> Result<Vec<Radial>> {
+    let source_spacing = match radials.first() {
+        Some(radial) => radial.azimuth_spacing_degrees(),
+        None => return Ok(Vec::new()),
+    };
+
+    let ratio = ratio(source_spacing, spacing).ok_or(Error::InvalidOptions(
+        "azimuth spacing must be a whole multiple or divisor of the sweep's spacing",
+    ))?;
+
+    Ok(match ratio {
+        Ratio::Same => radials.to_vec(),
+        Ratio::Coarsen(_) => {
+            let bin_count = (360.0 / spacing).round().max(1.0) as usize;
+
+            // Radials are grouped into bins in the order the bins were first scanned.
+            let mut groups: Vec<(usize, Vec<&Radial>)> = Vec::new();
+            for radial in radials {
+                let bin = (radial.azimuth_angle_degrees().rem_euclid(360.0) / spacing) as usize
+                    % bin_count;
+                match groups.iter_mut().find(|(group_bin, _)| *group_bin == bin) {
+                    Some((_, group)) => group.push(radial),
+                    None => groups.push((bin, vec![radial])),
+                }
+            }
+
+            groups
+                .into_iter()
+                .map(|(bin, group)| combine_radials(&group, bin, spacing))
+                .collect()
+        }
+        Ratio::Refine(factor) => radials
+            .iter()
+            .flat_map(|radial| split_radial(radial, factor, spacing))
+            .collect(),
+    })
+}
+
+/// Recombines the radials within an azimuth bin into a single radial centered in the bin.
+fn combine_radials(radials: &[&Radial], bin: usize, spacing: f32) -> Radial {
+    let first = radials[0];
+    let elevation_angle = radials
+        .iter()
+        .map(|radial| radial.elevation_angle_degrees())
+        .sum::<f32>()
+        / radials.len() as f32;
+    let timestamp = radials
+        .iter()
+        .map(|radial| radial.collection_timestamp())
+        .min()
+        .unwrap_or_default();
+
+    let mut products: Vec<Product> = Vec::new();
+    for radial in radials {
+        for (product, _) in radial.moments() {
+            if !products.contains(&product) {
+                products.push(product);
+            }
+        }
+    }
+
+    let mut combined = empty_radial(
+        first,
+        timestamp,
+        bin as u16 + 1,
+        (bin as f32 + 0.5) * spacing,
+        spacing,
+        combined_status(radials.iter().map(|radial| radial.radial_status())),
+        elevation_angle,
+    );
+
+    for product in products {
+        let template = match radials.iter().find_map(|radial| radial.moment(&product)) {
+            Some(template) => template,
+            None => continue,
+        };
+
+        let raw_values = (0..template.gate_count())
+            .flat_map(|gate_index| {
+                let range = template.gate_range_meters(gate_index);
+                let samples: Vec<(MomentValue, Option<f32>)> = radials
+                    .iter()
+                    .filter_map(|radial| {
+                        let data = radial.moment(&product)?;
+                        let value = data.value(data.gate_index_meters(range)?)?;
+                        Some((value, power_weight(radial, range)))
+                    })
+                    .collect();
+
+                let value = combine(
+                    &product,
+                    &samples,
+                    first.nyquist_velocity_meters_per_second(),
+                );
+                encode(value, template)
+            })
+            .collect();
+
+        combined.set_moment(
+            product,
+            MomentData::from_fixed_point(template.scale(), template.offset(), raw_values)
+                .with_gate_geometry(
+                    template.first_gate_range_meters(),
+                    template.gate_interval_meters(),
+                )
+                .with_word_size(template.word_size()),
+        );
+    }
+
+    combined
+}
+
+/// Splits a radial into the given number of equal radials with the given azimuth spacing.
+fn split_radial(radial: &Radial, factor: usize, spacing: f32) -> Vec<Radial> {
+    let bin_count = (360.0 / spacing).round().max(1.0) as usize;
+
+    (0..factor)
+        .map(|index| {
+            let azimuth = (radial.azimuth_angle_degrees()
+                + (index as f32 - (factor - 1) as f32 / 2.0) * spacing)
+                .rem_euclid(360.0);
+            let bin = (azimuth / spacing) as usize % bin_count;
+
+            let status = match radial.radial_status() {
+                RadialStatus::ElevationEnd | RadialStatus::VolumeScanEnd if index + 1 < factor => {
+                    RadialStatus::IntermediateRadialData
+                }
+                RadialStatus::ElevationStart
+                | RadialStatus::VolumeScanStart
+                | RadialStatus::ElevationStartVCPFinal
+                    if index > 0 =>
+                {
+                    RadialStatus::IntermediateRadialData
+                }
+                status => status,
+            };
+
+            let mut split = empty_radial(
+                radial,
+                radial.collection_timestamp(),
+                bin as u16 + 1,
+                azimuth,
+                spacing,
+                status,
+                radial.elevation_angle_degrees(),
+            );
+            for (product, data) in radial.moments() {
+                split.set_moment(product, data.clone());
+            }
+            split
+        })
+        .collect()
+}
+
+/// A radial without moments, taking its elevation, cut and Nyquist velocity from the given radial.
+fn empty_radial(
+    radial: &Radial,
+    collection_timestamp: i64,
+    azimuth_number: u16,
+    azimuth_angle_degrees: f32,
+    azimuth_spacing_degrees: f32,
+    radial_status: RadialStatus,
+    elevation_angle_degrees: f32,
+) -> Radial {
+    Radial::new(
+        collection_timestamp,
+        azimuth_number,
+        azimuth_angle_degrees,
+        azimuth_spacing_degrees,
+        radial_status,
+        radial.elevation_number(),
+        elevation_angle_degrees,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .with_nyquist_velocity_meters_per_second(radial.nyquist_velocity_meters_per_second())
+    .with_cut_sector_number(radial.cut_sector_number())
+}
+
+/// The status of a radial recombined from radials with the given statuses, preferring the start
+/// and then the end of an elevation or volume.
+fn combined_status(statuses: impl Iterator<Item = RadialStatus>) -> RadialStatus {
+    let statuses: Vec<RadialStatus> = statuses.collect();
+    let starts = [
+        RadialStatus::VolumeScanStart,
+        RadialStatus::ElevationStartVCPFinal,
+        RadialStatus::ElevationStart,
+    ];
+    let ends = [RadialStatus::VolumeScanEnd, RadialStatus::ElevationEnd];
+
+    starts
+        .into_iter()
+        .chain(ends)
+        .find(|status| statuses.contains(status))
+        .unwrap_or(RadialStatus::IntermediateRadialData)
+}
+
+/// Resamples a radial's data for a product to the given gate interval in meters.
+fn resample_gates(
+    radial: &Radial,
+    product: &Product,
+    data: &MomentData,
+    interval: u16,
+) -> Result<MomentData> {
+    let source_interval = data.gate_interval_meters();
+    let ratio = ratio(source_interval as f32, interval as f32).ok_or(Error::InvalidOptions(
+        "gate interval must be a whole multiple or divisor of the product's interval",
+    ))?;
+
+    Ok(match ratio {
+        Ratio::Same => data.clone(),
+        Ratio::Coarsen(factor) => {
+            let raw_values = (0..data.gate_count())
+                .step_by(factor)
+                .flat_map(|start| {
+                    let samples: Vec<(MomentValue, Option<f32>)> = (start
+                        ..(start + factor).min(data.gate_count()))
+                        .filter_map(|gate_index| {
+                            let range = data.gate_range_meters(gate_index);
+                            Some((data.value(gate_index)?, power_weight(radial, range)))
+                        })
+                        .collect();
+
+                    let value = combine(
+                        product,
+                        &samples,
+                        radial.nyquist_velocity_meters_per_second(),
+                    );
+                    encode(value, data)
+                })
+                .collect();
+
+            let offset = (factor as u32 - 1) * source_interval as u32 / 2;
+            MomentData::from_fixed_point(data.scale(), data.offset(), raw_values)
+                .with_gate_geometry(
+                    (data.first_gate_range_meters() as u32 + offset).min(u16::MAX as u32) as u16,
+                    interval,
+                )
+                .with_word_size(data.word_size())
+        }
+        Ratio::Refine(factor) => {
+            let raw_values = data
+                .raw_values()
+                .chunks_exact(usize::from(data.word_size() / 8).max(1))
+                .flat_map(|word| std::iter::repeat_n(word, factor))
+                .flatten()
+                .copied()
+                .collect();
+
+            let offset = (factor as u32 - 1) * interval as u32 / 2;
+            MomentData::from_fixed_point(data.scale(), data.offset(), raw_values)
+                .with_gate_geometry(
+                    (data.first_gate_range_meters() as u32).saturating_sub(offset) as u16,
+                    interval,
+                )
+                .with_word_size(data.word_size())
+        }
+    })
+}
+
+/// The linear reflectivity of a radial at the given slant range in meters, if it is valid.
+fn power_weight(radial: &Radial, range: f32) -> Option<f32> {
+    let reflectivity = radial.reflectivity()?;
+    match reflectivity.value(reflectivity.gate_index_meters(range)?)? {
+        MomentValue::Value(dbz) => Some(10f32.powf(dbz / 10.0)),
+        _ => None,
+    }
+}
+
+/// Recombines gate values for a product, each with its linear reflectivity if known.
+fn combine(
+    product: &Product,
+    samples: &[(MomentValue, Option<f32>)],
+    nyquist_velocity: Option<f32>,
+) -> MomentValue {
+    let valid: Vec<(f32, Option<f32>)> = samples
+        .iter()
+        .filter_map(|(value, weight)| match value {
+            MomentValue::Value(value) => Some((*value, *weight)),
+            _ => None,
+        })
+        .collect();
+
+    if valid.is_empty() {
+        return if samples
+            .iter()
+            .any(|(value, _)| *value == MomentValue::RangeFolded)
+        {
+            MomentValue::RangeFolded
+        } else {
+            MomentValue::BelowThreshold
+        };
+    }
+
+    // Values are only weighted by reflectivity when every value has a weight.
+    let weighted = valid.iter().all(|(_, weight)| weight.is_some());
+    let weight = |sample: &(f32, Option<f32>)| match sample.1 {
+        Some(weight) if weighted => weight,
+        _ => 1.0,
+    };
+    let total_weight: f32 = valid.iter().map(weight).sum();
+    let mean = |map: &dyn Fn(f32) -> f32| {
+        valid
+            .iter()
+            .map(|sample| weight(sample) * map(sample.0))
+            .sum::<f32>()
+            / total_weight
+    };
+    let count = valid.len() as f32;
+
+    MomentValue::Value(match product {
+        Product::Reflectivity | Product::DifferentialReflectivity => {
+            let linear = valid
+                .iter()
+                .map(|(value, _)| 10f32.powf(value / 10.0))
+                .sum::<f32>()
+                / count;
+            10.0 * linear.log10()
+        }
+        Product::Velocity => {
+            let (min, max) = valid
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(min, max), (v, _)| {
+                    (min.min(*v), max.max(*v))
+                });
+            match nyquist_velocity {
+                Some(nyquist) if max - min > nyquist => valid
+                    .iter()
+                    .max_by(|a, b| weight(a).total_cmp(&weight(b)))
+                    .map_or(f32::NAN, |(value, _)| *value),
+                _ => mean(&|value| value),
+            }
+        }
+        Product::SpectrumWidth => mean(&|value| value * value).sqrt(),
+        Product::DifferentialPhase => {
+            let sin = valid
+                .iter()
+                .map(|(value, _)| value.to_radians().sin())
+                .sum::<f32>();
+            let cos = valid
+                .iter()
+                .map(|(value, _)| value.to_radians().cos())
+                .sum::<f32>();
+            sin.atan2(cos).to_degrees().rem_euclid(360.0)
+        }
+        _ => valid.iter().map(|(value, _)| value).sum::<f32>() / count,
+    })
+}
+
+/// Encodes a value into the raw bytes of a word in the fixed-point representation of the given data.
+fn encode(value: MomentValue, data: &MomentData) -> impl Iterator<Item = u8> {
+    let max = if data.word_size() == 16 {
+        u16::MAX
+    } else {
+        u8::MAX as u16
+    } as f32;
+
+    let word = match value {
+        MomentValue::BelowThreshold => 0,
+        MomentValue::RangeFolded => 1,
+        MomentValue::Value(value) if data.scale() == 0.0 => value.round().clamp(0.0, max) as u16,
+        MomentValue::Value(value) => (value * data.scale() + data.offset())
+            .round()
+            .clamp(2.0, max) as u16,
+    };
+
+    let bytes = word.to_be_bytes();
+    let skip = if data.word_size() == 16 { 0 } else { 1 };
+    bytes.into_iter().skip(skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment_at, radial};
+
+    /// A super-resolution sweep of 0.5 degree radials with eight 250 m gates, alternating between
+    /// 20 dBZ moving at 10 m/s and 30 dBZ moving at 20 m/s.
+    fn super_resolution_sweep() -> Sweep {
+        let radials = (0..720)
+            .map(|index| {
+                let (z, v) = if index % 2 == 0 {
+                    (20.0, 10.0)
+                } else {
+                    (30.0, 20.0)
+                };
+
+                radial(index as i64, index as u16, 0.5, 1, 0.5)
+                    .with_moment(
+                        Product::Reflectivity,
+                        moment_at(
+                            &Product::Reflectivity,
+                            2_125,
+                            250,
+                            vec![(z * 2.0 + 66.0) as u8; 8],
+                        ),
+                    )
+                    .with_moment(
+                        Product::Velocity,
+                        moment_at(
+                            &Product::Velocity,
+                            2_125,
+                            250,
+                            vec![(v * 2.0 + 129.0) as u8; 8],
+                        ),
+                    )
+            })
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    #[test]
+    fn recombines_to_legacy_resolution() {
+        let legacy = resample_sweep(&super_resolution_sweep(), &ResampleOptions::legacy()).unwrap();
+        assert_eq!(legacy.radials().len(), 360);
+
+        let radial = &legacy.radials()[10];
+        assert_eq!(radial.azimuth_number(), 11);
+        assert_eq!(radial.azimuth_angle_degrees(), 10.5);
+        assert_eq!(radial.azimuth_spacing_degrees(), 1.0);
+
+        let reflectivity = radial.reflectivity().unwrap();
+        assert_eq!(reflectivity.first_gate_range_meters(), 2_500);
+        assert_eq!(reflectivity.gate_interval_meters(), 1_000);
+        assert_eq!(reflectivity.gate_count(), 2);
+        assert_eq!(reflectivity.value(0), Some(MomentValue::Value(27.5)));
+
+        // Velocity keeps its gates and is weighted toward the stronger echo.
+        let velocity = radial.velocity().unwrap();
+        assert_eq!(velocity.first_gate_range_meters(), 2_125);
+        assert_eq!(velocity.gate_interval_meters(), 250);
+        assert_eq!(velocity.gate_count(), 8);
+        assert_eq!(velocity.value(0), Some(MomentValue::Value(19.0)));
+    }
+
+    #[test]
+    fn splits_to_super_resolution() {
+        let legacy = resample_sweep(&super_resolution_sweep(), &ResampleOptions::legacy()).unwrap();
+        let split = resample_sweep(&legacy, &ResampleOptions::super_resolution()).unwrap();
+        assert_eq!(split.radials().len(), 720);
+
+        let (first, second) = (&split.radials()[20], &split.radials()[21]);
+        assert_eq!(first.azimuth_angle_degrees(), 10.25);
+        assert_eq!(second.azimuth_angle_degrees(), 10.75);
+        assert_eq!(first.azimuth_number(), 21);
+        assert_eq!(second.azimuth_spacing_degrees(), 0.5);
+
+        let reflectivity = second.reflectivity().unwrap();
+        assert_eq!(reflectivity.first_gate_range_meters(), 2_125);
+        assert_eq!(reflectivity.gate_interval_meters(), 250);
+        assert_eq!(reflectivity.gate_count(), 8);
+        assert_eq!(reflectivity.value(7), Some(MomentValue::Value(27.5)));
+    }
+
+    #[test]
+    fn rejects_uneven_spacing() {
+        let options = ResampleOptions {
+            azimuth_spacing_degrees: Some(0.75),
+            gate_intervals_meters: Vec::new(),
+        };
+        assert!(resample_sweep(&super_resolution_sweep(), &options).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::result::{Error, Result};
+use nexrad_model::data::Product;
+
+/// Options controlling resampling of a sweep's azimuth spacing and gate intervals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResampleOptions {
+    /// The azimuth spacing in degrees of the resampled sweep's radials, or [None] to keep the
+    /// sweep's spacing. The spacing must be a whole multiple or divisor of the sweep's spacing.
+    pub azimuth_spacing_degrees: Option<f32>,
+
+    /// The gate interval in meters of each listed product in the resampled sweep. Each interval
+    /// must be a whole multiple or divisor of the product's interval, and products that are not
+    /// listed keep their gates.
+    pub gate_intervals_meters: Vec<(Product, u16)>,
+}
+
+impl ResampleOptions {
+    /// Options resampling super-resolution data to legacy resolution: 1 degree radials with 1 km
+    /// reflectivity gates, while the Doppler and dual-polarization products keep their 250 m
+    /// gates.
+    pub fn legacy() -> Self {
+        Self {
+            azimuth_spacing_degrees: Some(1.0),
+            gate_intervals_meters: vec![(Product::Reflectivity, 1_000)],
+        }
+    }
+
+    /// Options resampling legacy resolution data to super resolution: 0.5 degree radials with
+    /// 250 m reflectivity gates.
+    pub fn super_resolution() -> Self {
+        Self {
+            azimuth_spacing_degrees: Some(0.5),
+            gate_intervals_meters: vec![(Product::Reflectivity, 250)],
+        }
+    }
+
+    /// Checks that these options are usable for resampling.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(spacing) = self.azimuth_spacing_degrees {
+            if spacing <= 0.0 || spacing > 360.0 {
+                return Err(Error::InvalidOptions(
+                    "azimuth spacing must be positive and at most 360 degrees",
+                ));
+            }
+        }
+
+        if self
+            .gate_intervals_meters
+            .iter()
+            .any(|(_, interval)| *interval == 0)
+        {
+            return Err(Error::InvalidOptions("gate intervals must be positive"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ResampleOptions {
+    fn default() -> Self {
+        Self::legacy()
+    }
+}
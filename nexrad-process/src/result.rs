@@ -0,0 +1,24 @@
+//!
+//! Contains the Result and Error types for NEXRAD processing operations.
+//!
+
+use thiserror::Error as ThisError;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(ThisError, Debug)]
+pub enum Error {
+    #[error("invalid algorithm options: {0}")]
+    InvalidOptions(&'static str),
+    #[error("at least two scans with timestamps are required")]
+    InsufficientScans,
+    #[error("scans must be provided in time order")]
+    OutOfOrderScan,
+    #[error("invalid digital elevation model: {0}")]
+    InvalidDem(&'static str),
+    #[error("IO error")]
+    FileError(#[from] std::io::Error),
+    #[cfg(feature = "tiff")]
+    #[error("error decoding GeoTIFF")]
+    TiffError(#[from] tiff::TiffError),
+}
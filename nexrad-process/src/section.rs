@@ -0,0 +1,273 @@
+//!
+//! Vertical cross sections (pseudo-RHIs) through a scan, along a line between two points or along
+//! a single azimuth from the radar. Each column of the section samples every sweep at the column's
+//! location, and values are interpolated linearly in height between the beam centers of adjacent
+//! sweeps. Beyond the outermost sweeps, or where an adjacent sweep has no data, values are only
+//! filled within half a beam width of a sweep's beam center.
+//!
+
+mod cross_section;
+pub use cross_section::*;
+
+mod options;
+pub use options::*;
+
+use crate::field::PolarField;
+use crate::result::Result;
+use nexrad_model::data::{MomentData, Radial, Scan, ELEVATION_TOLERANCE_DEGREES};
+use nexrad_model::geometry::{
+    beam_height_meters, bearing_and_distance, destination, slant_range_meters,
+};
+use nexrad_model::meta::Site;
+
+/// Extracts a vertical cross section through a scan's moment, selected from each radial by the
+/// provided function, along the line between two points given by latitude and longitude.
+pub fn line_cross_section<F>(
+    scan: &Scan,
+    site: &Site,
+    moment: F,
+    start: (f32, f32),
+    end: (f32, f32),
+    options: &SectionOptions,
+) -> Result<CrossSection>
+where
+    F: Fn(&Radial) -> Option<&MomentData>,
+{
+    options.validate()?;
+
+    let (bearing, distance) = bearing_and_distance(start.0, start.1, end.0, end.1);
+    let column_count = (distance / options.distance_spacing_meters).floor() as usize + 1;
+
+    let locations = (0..column_count)
+        .map(|column| {
+            let (latitude, longitude) = destination(
+                start.0,
+                start.1,
+                bearing,
+                column as f32 * options.distance_spacing_meters,
+            );
+            bearing_and_distance(site.latitude(), site.longitude(), latitude, longitude)
+        })
+        .collect();
+
+    Ok(cross_section(scan, moment, locations, options))
+}
+
+/// Extracts a vertical cross section through a scan's moment, selected from each radial by the
+/// provided function, outward from the radar along the given azimuth in degrees.
+pub fn azimuth_cross_section<F>(
+    scan: &Scan,
+    moment: F,
+    azimuth_degrees: f32,
+    options: &SectionOptions,
+) -> Result<CrossSection>
+where
+    F: Fn(&Radial) -> Option<&MomentData>,
+{
+    options.validate()?;
+
+    let column_count =
+        (options.max_range_meters / options.distance_spacing_meters).floor() as usize + 1;
+    let locations = (0..column_count)
+        .map(|column| {
+            (
+                azimuth_degrees,
+                column as f32 * options.distance_spacing_meters,
+            )
+        })
+        .collect();
+
+    Ok(cross_section(scan, moment, locations, options))
+}
+
+/// Fills a cross section whose columns are at the given azimuths and ground ranges.
+fn cross_section<F>(
+    scan: &Scan,
+    moment: F,
+    locations: Vec<(f32, f32)>,
+    options: &SectionOptions,
+) -> CrossSection
+where
+    F: Fn(&Radial) -> Option<&MomentData>,
+{
+    // Each elevation's sweeps, such as the surveillance and Doppler halves of a split cut, in
+    // ascending elevation.
+    let levels: Vec<Vec<PolarField>> = scan
+        .sweeps_by_elevation_angle(ELEVATION_TOLERANCE_DEGREES)
+        .into_iter()
+        .map(|sweeps| {
+            sweeps
+                .into_iter()
+                .filter_map(|sweep| PolarField::from_sweep(sweep, &moment))
+                .collect::<Vec<_>>()
+        })
+        .filter(|fields| !fields.is_empty())
+        .collect();
+
+    let row_count =
+        (options.max_height_meters / options.height_spacing_meters).floor() as usize + 1;
+    let mut section = CrossSection::empty(
+        locations,
+        options.distance_spacing_meters,
+        options.height_spacing_meters,
+        row_count,
+    );
+
+    let half_beam_width = (options.beam_width_degrees / 2.0).to_radians().tan();
+    for column in 0..section.column_count() {
+        let (azimuth, ground_range) = section.locations()[column];
+
+        // The elevation angle, beam center height, half beam width and value of each distinct
+        // elevation at this column.
+        let mut samples: Vec<(f32, f32, f32, Option<f32>)> = Vec::new();
+        for fields in &levels {
+            let elevation = fields[0].elevation_angle_degrees().unwrap_or_default();
+            let range = match slant_range_meters(ground_range, elevation) {
+                Some(range) => range,
+                None => continue,
+            };
+
+            // A repeat of the elevation is only used where the elevation's first sweep lacks data.
+            let value = fields
+                .iter()
+                .find_map(|field| field.value_at(azimuth, range));
+
+            samples.push((
+                elevation,
+                beam_height_meters(range, elevation),
+                range * half_beam_width,
+                value,
+            ));
+        }
+
+        for row in 0..section.row_count() {
+            let height = section.height_meters(row);
+            if let Some(value) = interpolate(&samples, height) {
+                section.set(column, row, value);
+            }
+        }
+    }
+
+    section
+}
+
+/// The value at the given height in meters from samples of elevation angle, beam center height,
+/// half beam width and value in ascending order.
+fn interpolate(samples: &[(f32, f32, f32, Option<f32>)], height: f32) -> Option<f32> {
+    let above = samples.partition_point(|(_, center, _, _)| *center < height);
+
+    let below = above.checked_sub(1).and_then(|index| samples.get(index));
+    let above = samples.get(above);
+    match (below, above) {
+        (Some((_, low, _, Some(low_value))), Some((_, high, _, Some(high_value)))) => {
+            let fraction = if high > low {
+                (height - low) / (high - low)
+            } else {
+                0.0
+            };
+            Some(low_value + fraction * (high_value - low_value))
+        }
+        (below, above) => [below, above]
+            .into_iter()
+            .flatten()
+            .filter(|(_, center, half_width, _)| (height - center).abs() <= *half_width)
+            .find_map(|(_, _, _, value)| *value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment_at, radial};
+    use nexrad_model::data::{Product, Sweep};
+
+    /// A scan whose reflectivity decreases by 3 dBZ per kilometer of height from 50 dBZ at the
+    /// radar, out to 100 km.
+    fn layered_scan() -> Scan {
+        let elevations = [0.5, 0.5, 1.5, 2.4, 3.4, 4.3, 6.0, 9.9, 14.6, 19.5];
+        let sweeps = elevations
+            .iter()
+            .enumerate()
+            .map(|(index, elevation)| {
+                let radials = (0..360)
+                    .map(|azimuth_number| {
+                        let reflectivity = (0..400)
+                            .map(|gate| {
+                                let range = 250.0 + gate as f32 * 250.0;
+                                let height = beam_height_meters(range, *elevation) / 1000.0;
+                                ((50.0 - 3.0 * height) * 2.0 + 66.0).round() as u8
+                            })
+                            .collect();
+
+                        radial(0, azimuth_number, 1.0, index as u8 + 1, *elevation).with_moment(
+                            Product::Reflectivity,
+                            moment_at(&Product::Reflectivity, 250, 250, reflectivity),
+                        )
+                    })
+                    .collect();
+
+                Sweep::new(index as u8 + 1, radials)
+            })
+            .collect();
+
+        Scan::new(212, sweeps)
+    }
+
+    #[test]
+    fn extracts_azimuth_section() {
+        let scan = layered_scan();
+        let section = azimuth_cross_section(
+            &scan,
+            Radial::reflectivity,
+            45.0,
+            &SectionOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(section.column_count(), 461);
+        assert_eq!(section.row_count(), 61);
+        assert_eq!(section.locations()[10], (45.0, 5_000.0));
+
+        for (distance, height) in [
+            (50_000.0, 3_000.0),
+            (30_000.0, 5_000.0),
+            (80_000.0, 1_500.0),
+        ] {
+            let value = section.value_at(distance, height).unwrap();
+            let expected = 50.0 - 3.0 * height / 1000.0;
+            assert!(
+                (value - expected).abs() < 1.0,
+                "{value} at {distance}, {height}"
+            );
+        }
+
+        // Far above the highest sweep and beyond the data's range.
+        assert_eq!(section.value_at(20_000.0, 14_000.0), None);
+        assert_eq!(section.value_at(150_000.0, 3_000.0), None);
+    }
+
+    #[test]
+    fn extracts_line_section() {
+        let scan = layered_scan();
+        let site = Site::new(*b"KTLX", 35.33, -97.28, 370, 20);
+
+        let start = destination(site.latitude(), site.longitude(), 270.0, 40_000.0);
+        let end = destination(site.latitude(), site.longitude(), 90.0, 40_000.0);
+        let section = line_cross_section(
+            &scan,
+            &site,
+            Radial::reflectivity,
+            start,
+            end,
+            &SectionOptions::default(),
+        )
+        .unwrap();
+
+        assert!((159..=161).contains(&section.column_count()));
+        let (azimuth, range) = section.locations()[40];
+        assert!((azimuth - 270.0).abs() < 0.5 && (range - 20_000.0).abs() < 100.0);
+
+        let value = section.value_at(60_000.0, 2_000.0).unwrap();
+        assert!((value - 44.0).abs() < 1.0, "{value}");
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A vertical cross section of values indexed by column, along the section's path, and row,
+/// upward from the radar's height. Each column is located by its azimuth and ground range from
+/// the radar, and missing values are represented as NaN.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrossSection {
+    locations: Vec<(f32, f32)>,
+    distance_spacing_meters: f32,
+    height_spacing_meters: f32,
+    row_count: usize,
+    values: Vec<f32>,
+}
+
+impl CrossSection {
+    /// Create a new cross section with every cell set to NaN, given the azimuth in degrees and
+    /// ground range in meters from the radar of each column, the distance between adjacent columns
+    /// and rows, and the number of rows.
+    pub fn empty(
+        locations: Vec<(f32, f32)>,
+        distance_spacing_meters: f32,
+        height_spacing_meters: f32,
+        row_count: usize,
+    ) -> Self {
+        let values = vec![f32::NAN; locations.len() * row_count];
+        Self {
+            locations,
+            distance_spacing_meters,
+            height_spacing_meters,
+            row_count,
+            values,
+        }
+    }
+
+    /// The number of columns along this section's path.
+    pub fn column_count(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// The number of rows in this section.
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    /// Distance in meters along the ground between adjacent columns.
+    pub fn distance_spacing_meters(&self) -> f32 {
+        self.distance_spacing_meters
+    }
+
+    /// Vertical distance in meters between adjacent rows.
+    pub fn height_spacing_meters(&self) -> f32 {
+        self.height_spacing_meters
+    }
+
+    /// Distance in meters along the section's path to the given column.
+    pub fn distance_meters(&self, column: usize) -> f32 {
+        column as f32 * self.distance_spacing_meters
+    }
+
+    /// Height in meters above the radar of the given row.
+    pub fn height_meters(&self, row: usize) -> f32 {
+        row as f32 * self.height_spacing_meters
+    }
+
+    /// The azimuth in degrees and ground range in meters from the radar of each column.
+    pub fn locations(&self) -> &Vec<(f32, f32)> {
+        self.locations.as_ref()
+    }
+
+    /// All of this section's values ordered by row and then column.
+    pub fn values(&self) -> &Vec<f32> {
+        self.values.as_ref()
+    }
+
+    /// The value at the given column and row, or [None] if the cell is out of bounds or has no
+    /// valid value.
+    pub fn value(&self, column: usize, row: usize) -> Option<f32> {
+        if column >= self.column_count() || row >= self.row_count {
+            return None;
+        }
+
+        let value = self.values[row * self.column_count() + column];
+        if value.is_nan() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// The value of the cell nearest the given distance in meters along the section's path and
+    /// height in meters above the radar.
+    pub fn value_at(&self, distance_meters: f32, height_meters: f32) -> Option<f32> {
+        let column = (distance_meters / self.distance_spacing_meters).round();
+        let row = (height_meters / self.height_spacing_meters).round();
+        if column < 0.0 || row < 0.0 {
+            return None;
+        }
+
+        self.value(column as usize, row as usize)
+    }
+
+    /// Sets the value at the given column and row. Out-of-bounds cells are ignored.
+    pub fn set(&mut self, column: usize, row: usize, value: f32) {
+        if column < self.column_count() && row < self.row_count {
+            let column_count = self.column_count();
+            self.values[row * column_count + column] = value;
+        }
+    }
+
+    /// The largest valid value in this section, if any.
+    pub fn max(&self) -> Option<f32> {
+        self.values
+            .iter()
+            .copied()
+            .filter(|value| !value.is_nan())
+            .reduce(f32::max)
+    }
+}
+
+impl Debug for CrossSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrossSection")
+            .field("column_count", &self.column_count())
+            .field("row_count", &self.row_count())
+            .field("distance_spacing_meters", &self.distance_spacing_meters())
+            .field("height_spacing_meters", &self.height_spacing_meters())
+            .finish()
+    }
+}
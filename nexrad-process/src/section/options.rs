@@ -0,0 +1,53 @@
+use crate::result::{Error, Result};
+
+/// Options controlling extraction of vertical cross sections.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionOptions {
+    /// The distance in meters along the ground between adjacent columns of the cross section.
+    pub distance_spacing_meters: f32,
+
+    /// The vertical distance in meters between adjacent rows of the cross section.
+    pub height_spacing_meters: f32,
+
+    /// The maximum height in meters above the radar of the cross section.
+    pub max_height_meters: f32,
+
+    /// The maximum ground range in meters of cross sections along a single azimuth.
+    pub max_range_meters: f32,
+
+    /// The antenna's half-power beam width in degrees. Above the highest sweep, below the lowest
+    /// sweep and next to sweeps without data, values are only filled within half a beam width of
+    /// a sweep's beam center.
+    pub beam_width_degrees: f32,
+}
+
+impl SectionOptions {
+    /// Checks that these options are usable for a cross section.
+    pub fn validate(&self) -> Result<()> {
+        if self.distance_spacing_meters <= 0.0 || self.height_spacing_meters <= 0.0 {
+            return Err(Error::InvalidOptions("section spacing must be positive"));
+        }
+
+        if self.max_height_meters <= 0.0 || self.max_range_meters <= 0.0 {
+            return Err(Error::InvalidOptions("section extent must be positive"));
+        }
+
+        if self.beam_width_degrees <= 0.0 {
+            return Err(Error::InvalidOptions("beam width must be positive"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SectionOptions {
+    fn default() -> Self {
+        Self {
+            distance_spacing_meters: 500.0,
+            height_spacing_meters: 250.0,
+            max_height_meters: 15_000.0,
+            max_range_meters: 230_000.0,
+            beam_width_degrees: 0.95,
+        }
+    }
+}
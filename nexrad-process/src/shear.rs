@@ -0,0 +1,365 @@
+//!
+//! Azimuthal shear and radial divergence computed from radial velocity using the linear
+//! least-squares derivative (LLSD) method. For each gate, a plane is fit to the velocities in a
+//! small kernel around it. The plane's slope across the beam is the azimuthal shear, which is
+//! positive for cyclonic rotation, and its slope along the beam is the radial divergence, which is
+//! positive for divergent flow. Both are in units of inverse seconds.
+//!
+//! Maxima of azimuthal shear within low-level and mid-level height layers can be accumulated over
+//! successive scans to produce rotation tracks.
+//!
+
+mod options;
+pub use options::*;
+
+use crate::field::PolarField;
+use crate::math::{fold, solve_3x3};
+use crate::result::Result;
+use nexrad_model::data::{Radial, Scan, Sweep};
+use nexrad_model::geometry::{beam_height_meters, ground_range_meters};
+use std::f32::consts::PI;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The largest number of radials on either side of a kernel's center. This bounds the kernel's
+/// size near the radar where radials are closely spaced.
+const MAX_KERNEL_RADIALS: usize = 15;
+
+/// Azimuthal shear and radial divergence fields for a sweep.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShearFields {
+    /// Azimuthal shear in inverse seconds, positive for cyclonic rotation.
+    pub azimuthal_shear: PolarField,
+
+    /// Radial divergence in inverse seconds, positive for divergent flow.
+    pub radial_divergence: PolarField,
+}
+
+/// The maximum azimuthal shear found in the low-level and mid-level layers of a scan. These fields
+/// are composited across sweeps and indexed by ground range.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayerMaxima {
+    /// The maximum azimuthal shear in inverse seconds in the low-level layer.
+    pub low_level: PolarField,
+
+    /// The maximum azimuthal shear in inverse seconds in the mid-level layer.
+    pub mid_level: PolarField,
+}
+
+/// Computes azimuthal shear and radial divergence from a sweep's velocity data. Returns [None] if
+/// the sweep has no velocity data.
+pub fn sweep_shear(sweep: &Sweep, options: &ShearOptions) -> Result<Option<ShearFields>> {
+    options.validate()?;
+
+    let velocity = match PolarField::from_sweep(sweep, Radial::velocity) {
+        Some(velocity) => velocity,
+        None => return Ok(None),
+    };
+
+    let nyquist_velocity = if options.unfold_velocities {
+        sweep
+            .radials()
+            .iter()
+            .filter_map(|radial| radial.nyquist_velocity_meters_per_second())
+            .reduce(f32::min)
+    } else {
+        None
+    };
+
+    Ok(Some(llsd(&velocity, nyquist_velocity, options)))
+}
+
+/// Computes the maximum azimuthal shear in the low-level and mid-level layers across all of a
+/// scan's sweeps.
+pub fn layer_maxima(scan: &Scan, options: &ShearOptions) -> Result<LayerMaxima> {
+    options.validate()?;
+
+    let radial_count = (360.0 / options.layer_azimuth_spacing_degrees).round() as usize;
+    let azimuths = (0..radial_count)
+        .map(|index| (index as f32 + 0.5) * options.layer_azimuth_spacing_degrees)
+        .collect();
+    let gate_count =
+        (options.layer_max_range_meters / options.layer_gate_interval_meters).ceil() as usize;
+
+    let low_level = PolarField::empty(
+        None,
+        azimuths,
+        0.0,
+        options.layer_gate_interval_meters,
+        gate_count,
+    );
+    let mid_level = low_level.empty_like();
+    let mut maxima = LayerMaxima {
+        low_level,
+        mid_level,
+    };
+
+    for sweep in scan.sweeps() {
+        let shear = match sweep_shear(sweep, options)? {
+            Some(shear) => shear.azimuthal_shear,
+            None => continue,
+        };
+
+        let elevation_angle = shear.elevation_angle_degrees().unwrap_or_default();
+        for gate_index in 0..shear.gate_count() {
+            let slant_range = shear.gate_range_meters(gate_index);
+            let height = beam_height_meters(slant_range, elevation_angle);
+
+            let layer = if within(height, options.low_level_layer_meters) {
+                &mut maxima.low_level
+            } else if within(height, options.mid_level_layer_meters) {
+                &mut maxima.mid_level
+            } else {
+                continue;
+            };
+
+            let ground_range = ground_range_meters(slant_range, elevation_angle);
+            let layer_gate_index = match layer.gate_index(ground_range) {
+                Some(index) => index,
+                None => continue,
+            };
+
+            for (radial_index, azimuth) in shear.azimuths_degrees().iter().enumerate() {
+                let value = match shear.value(radial_index, gate_index) {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                let layer_radial_index =
+                    (azimuth.rem_euclid(360.0) / options.layer_azimuth_spacing_degrees) as usize
+                        % radial_count;
+
+                match layer.value(layer_radial_index, layer_gate_index) {
+                    Some(existing) if existing >= value => {}
+                    _ => layer.set(layer_radial_index, layer_gate_index, value),
+                }
+            }
+        }
+    }
+
+    Ok(maxima)
+}
+
+/// Fits a plane to the velocities in the kernel around each gate.
+fn llsd(
+    velocity: &PolarField,
+    nyquist_velocity: Option<f32>,
+    options: &ShearOptions,
+) -> ShearFields {
+    let mut fields = ShearFields {
+        azimuthal_shear: velocity.empty_like(),
+        radial_divergence: velocity.empty_like(),
+    };
+
+    let radial_count = velocity.radial_count();
+    if radial_count == 0 || velocity.gate_interval_meters() <= 0.0 {
+        return fields;
+    }
+
+    let azimuth_spacing = 2.0 * PI / radial_count as f32;
+    let kernel_gates =
+        (options.radial_kernel_meters / 2.0 / velocity.gate_interval_meters()).ceil() as isize;
+    let azimuths: Vec<f32> = velocity
+        .azimuths_degrees()
+        .iter()
+        .map(|azimuth| azimuth.to_radians())
+        .collect();
+
+    for radial_index in 0..radial_count {
+        for gate_index in 0..velocity.gate_count() {
+            let center = match velocity.value(radial_index, gate_index) {
+                Some(center) => center,
+                None => continue,
+            };
+
+            let range = velocity.gate_range_meters(gate_index).max(1.0);
+            let kernel_radials =
+                ((options.azimuthal_kernel_meters / 2.0 / (range * azimuth_spacing)).ceil()
+                    as usize)
+                    .min(MAX_KERNEL_RADIALS)
+                    .min((radial_count - 1) / 2) as isize;
+
+            let mut normal = [[0.0f64; 3]; 3];
+            let mut target = [0.0f64; 3];
+            let mut count = 0;
+
+            for radial_offset in -kernel_radials..=kernel_radials {
+                let kernel_radial = (radial_index as isize + radial_offset)
+                    .rem_euclid(radial_count as isize) as usize;
+
+                let angle = (azimuths[kernel_radial] - azimuths[radial_index] + PI)
+                    .rem_euclid(2.0 * PI)
+                    - PI;
+                let across = (range * angle) as f64;
+
+                for gate_offset in -kernel_gates..=kernel_gates {
+                    let kernel_gate = gate_index as isize + gate_offset;
+                    if kernel_gate < 0 {
+                        continue;
+                    }
+
+                    let mut value = match velocity.value(kernel_radial, kernel_gate as usize) {
+                        Some(value) => value,
+                        None => continue,
+                    };
+
+                    if let Some(nyquist_velocity) = nyquist_velocity {
+                        value = center + fold(value - center, 2.0 * nyquist_velocity);
+                    }
+
+                    let along = (gate_offset as f32 * velocity.gate_interval_meters()) as f64;
+                    let basis = [1.0, across, along];
+                    for row in 0..3 {
+                        for column in 0..3 {
+                            normal[row][column] += basis[row] * basis[column];
+                        }
+                        target[row] += basis[row] * value as f64;
+                    }
+                    count += 1;
+                }
+            }
+
+            if count < options.min_points {
+                continue;
+            }
+
+            if let Some([_, shear, divergence]) = solve_3x3(normal, target) {
+                fields
+                    .azimuthal_shear
+                    .set(radial_index, gate_index, shear as f32);
+                fields
+                    .radial_divergence
+                    .set(radial_index, gate_index, divergence as f32);
+            }
+        }
+    }
+
+    fields
+}
+
+fn within(height: f32, (bottom, top): (f32, f32)) -> bool {
+    height >= bottom && height <= top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::Product;
+
+    /// A sweep whose velocities near 90 degrees azimuth increase linearly across the beam,
+    /// producing uniform azimuthal shear.
+    fn shear_sweep(shear: f32) -> Sweep {
+        shear_sweep_at(1, 0.5, shear)
+    }
+
+    /// A sweep at the given elevation whose velocities near 90 degrees azimuth increase linearly
+    /// across the beam, producing uniform azimuthal shear.
+    fn shear_sweep_at(elevation_number: u8, elevation_angle_degrees: f32, shear: f32) -> Sweep {
+        let radials = (0..720)
+            .map(|azimuth_number| {
+                let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                let values = (0..240)
+                    .map(|gate| {
+                        let range = 2_000.0 + gate as f32 * 250.0;
+                        let across = range * (azimuth - 90.0).to_radians();
+                        let velocity = shear * across;
+                        (velocity * 2.0 + 129.0).round().clamp(2.0, 255.0) as u8
+                    })
+                    .collect();
+
+                radial(
+                    0,
+                    azimuth_number,
+                    0.5,
+                    elevation_number,
+                    elevation_angle_degrees,
+                )
+                .with_moment(Product::Velocity, moment(&Product::Velocity, values))
+            })
+            .collect();
+
+        Sweep::new(elevation_number, radials)
+    }
+
+    /// The layer maximum at 90 degrees azimuth and the given ground range in meters.
+    fn layer_value(layer: &PolarField, ground_range: f32) -> Option<f32> {
+        layer.value(layer.radial_index(90.0)?, layer.gate_index(ground_range)?)
+    }
+
+    #[test]
+    fn uniform_shear() {
+        let shear = sweep_shear(&shear_sweep(0.01), &ShearOptions::default())
+            .unwrap()
+            .unwrap();
+
+        let field = &shear.azimuthal_shear;
+        let radial_index = field.radial_index(90.0).unwrap();
+        let gate_index = field.gate_index(40_000.0).unwrap();
+
+        let value = field.value(radial_index, gate_index).unwrap();
+        assert!((value - 0.01).abs() < 0.001, "shear {value}");
+
+        let divergence = shear
+            .radial_divergence
+            .value(radial_index, gate_index)
+            .unwrap();
+        assert!(divergence.abs() < 0.001, "divergence {divergence}");
+    }
+
+    #[test]
+    fn composites_sweeps_into_layers_by_beam_height() {
+        let scan = Scan::new(
+            212,
+            vec![shear_sweep_at(1, 0.5, 0.01), shear_sweep_at(2, 10.0, 0.02)],
+        );
+        let maxima = layer_maxima(&scan, &ShearOptions::default()).unwrap();
+
+        // The low sweep's beam stays below 2 km, within the low-level layer.
+        let low_level = layer_value(&maxima.low_level, 40_000.0).unwrap();
+        assert!((low_level - 0.01).abs() < 0.001, "low-level {low_level}");
+
+        // The high sweep's beam is between 3 and 6 km at 25 km, within the mid-level layer.
+        let mid_level = layer_value(&maxima.mid_level, 25_000.0).unwrap();
+        assert!((mid_level - 0.02).abs() < 0.002, "mid-level {mid_level}");
+        assert_eq!(layer_value(&maxima.mid_level, 40_000.0), None);
+
+        // The high sweep's beam is above both layers at 50 km, so only the low sweep contributes.
+        let low_level = layer_value(&maxima.low_level, 50_000.0).unwrap();
+        assert!((low_level - 0.01).abs() < 0.001, "low-level {low_level}");
+        assert_eq!(layer_value(&maxima.mid_level, 50_000.0), None);
+    }
+
+    #[test]
+    fn takes_maximum_across_sweeps() {
+        for sweeps in [
+            vec![shear_sweep_at(1, 0.5, 0.01), shear_sweep_at(2, 0.9, 0.02)],
+            vec![shear_sweep_at(1, 0.5, 0.02), shear_sweep_at(2, 0.9, 0.01)],
+        ] {
+            let maxima = layer_maxima(&Scan::new(212, sweeps), &ShearOptions::default()).unwrap();
+
+            let low_level = layer_value(&maxima.low_level, 30_000.0).unwrap();
+            assert!((low_level - 0.02).abs() < 0.002, "low-level {low_level}");
+        }
+    }
+
+    #[test]
+    fn bounds_layers_by_height() {
+        let options = ShearOptions {
+            low_level_layer_meters: (0.0, 300.0),
+            ..ShearOptions::default()
+        };
+        let maxima = layer_maxima(&Scan::new(212, vec![shear_sweep(0.01)]), &options).unwrap();
+
+        // The beam is below 300 meters at 20 km but above it at 60 km.
+        assert!(layer_value(&maxima.low_level, 20_000.0).is_some());
+        assert_eq!(layer_value(&maxima.low_level, 60_000.0), None);
+        assert_eq!(layer_value(&maxima.mid_level, 20_000.0), None);
+
+        assert_eq!(maxima.low_level.radial_count(), 720);
+        assert_eq!(maxima.low_level.gate_count(), 920);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::result::{Error, Result};
+
+/// Options controlling the linear least-squares derivative (LLSD) shear computation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShearOptions {
+    /// The width in meters across the beam of the kernel of gates used to fit each derivative.
+    pub azimuthal_kernel_meters: f32,
+
+    /// The length in meters along the beam of the kernel of gates used to fit each derivative.
+    pub radial_kernel_meters: f32,
+
+    /// The minimum number of valid velocity gates in a kernel for a derivative to be computed.
+    pub min_points: usize,
+
+    /// Whether velocities in each kernel should be unfolded relative to the kernel's center gate
+    /// using the sweep's Nyquist velocity. This should be enabled for raw Level II velocities and
+    /// may be disabled for velocities which have already been dealiased.
+    pub unfold_velocities: bool,
+
+    /// The bottom and top heights in meters above the radar of the low-level layer.
+    pub low_level_layer_meters: (f32, f32),
+
+    /// The bottom and top heights in meters above the radar of the mid-level layer.
+    pub mid_level_layer_meters: (f32, f32),
+
+    /// The azimuthal spacing in degrees of layer maximum fields.
+    pub layer_azimuth_spacing_degrees: f32,
+
+    /// The ground distance in meters between gates of layer maximum fields.
+    pub layer_gate_interval_meters: f32,
+
+    /// The maximum ground range in meters of layer maximum fields.
+    pub layer_max_range_meters: f32,
+}
+
+impl ShearOptions {
+    /// Checks that these options are usable for a shear computation.
+    pub fn validate(&self) -> Result<()> {
+        if self.azimuthal_kernel_meters <= 0.0 || self.radial_kernel_meters <= 0.0 {
+            return Err(Error::InvalidOptions("kernel dimensions must be positive"));
+        }
+
+        if self.min_points < 3 {
+            return Err(Error::InvalidOptions("at least three points are required"));
+        }
+
+        if self.layer_azimuth_spacing_degrees <= 0.0
+            || self.layer_gate_interval_meters <= 0.0
+            || self.layer_max_range_meters <= 0.0
+        {
+            return Err(Error::InvalidOptions(
+                "layer field dimensions must be positive",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ShearOptions {
+    fn default() -> Self {
+        Self {
+            azimuthal_kernel_meters: 2_500.0,
+            radial_kernel_meters: 750.0,
+            min_points: 6,
+            unfold_velocities: true,
+            low_level_layer_meters: (0.0, 2_000.0),
+            mid_level_layer_meters: (3_000.0, 6_000.0),
+            layer_azimuth_spacing_degrees: 0.5,
+            layer_gate_interval_meters: 250.0,
+            layer_max_range_meters: 230_000.0,
+        }
+    }
+}
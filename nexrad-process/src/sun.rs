@@ -0,0 +1,254 @@
+//!
+//! Sun spike detection for validating antenna pointing. When the antenna points near the sun, its
+//! radiation fills a radial with noise whose received power is nearly independent of range. Such
+//! radials are found in low-elevation sweeps around sunrise and sunset and compared with the sun's
+//! computed position when they were collected, giving the antenna's azimuth and elevation pointing
+//! bias along with an estimate of the received solar power.
+//!
+
+mod options;
+pub use options::*;
+
+mod spike;
+pub use spike::*;
+
+use crate::math::standard_deviation;
+use crate::result::Result;
+use nexrad_model::data::{MomentValue, Scan, Sweep};
+use nexrad_model::meta::Site;
+
+/// The Unix time in days of the J2000 epoch, 2000-01-01 12:00 UTC.
+const J2000_UNIX_DAYS: f64 = 10_957.5;
+
+/// Computes the apparent position of the sun from a location at a time in milliseconds since the
+/// epoch, returning its azimuth in degrees clockwise from north and its elevation in degrees above
+/// the horizon including atmospheric refraction. The position is accurate to about a hundredth of
+/// a degree for recent decades.
+pub fn sun_position(latitude: f32, longitude: f32, timestamp: i64) -> (f32, f32) {
+    let days = timestamp as f64 / 86_400_000.0 - J2000_UNIX_DAYS;
+
+    let mean_longitude = 280.460 + 0.985_647_4 * days;
+    let mean_anomaly = (357.528 + 0.985_600_3 * days).to_radians();
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin())
+            .to_radians();
+    let obliquity = (23.439 - 0.000_000_4 * days).to_radians();
+
+    let right_ascension =
+        (obliquity.cos() * ecliptic_longitude.sin()).atan2(ecliptic_longitude.cos());
+    let declination = (obliquity.sin() * ecliptic_longitude.sin()).asin();
+
+    let sidereal_time = 280.460_618_37 + 360.985_647_366_29 * days + longitude as f64;
+    let hour_angle = sidereal_time.to_radians() - right_ascension;
+
+    let latitude = (latitude as f64).to_radians();
+    let elevation = (latitude.sin() * declination.sin()
+        + latitude.cos() * declination.cos() * hour_angle.cos())
+    .asin()
+    .to_degrees();
+    let azimuth = (-hour_angle.sin())
+        .atan2(declination.tan() * latitude.cos() - latitude.sin() * hour_angle.cos())
+        .to_degrees()
+        .rem_euclid(360.0);
+
+    (azimuth as f32, (elevation + refraction(elevation)) as f32)
+}
+
+/// Detects sun spikes in a single sweep, comparing them to the sun's position from the given radar
+/// site.
+pub fn detect_sun_spikes(
+    sweep: &Sweep,
+    site: &Site,
+    options: &SunOptions,
+) -> Result<Vec<SunSpike>> {
+    options.validate()?;
+
+    let mut spikes = Vec::new();
+    for radial in sweep.radials() {
+        let reflectivity = match radial.reflectivity() {
+            Some(reflectivity) => reflectivity,
+            None => continue,
+        };
+
+        let mut considered = 0;
+        let mut powers = Vec::new();
+        for (gate_index, value) in reflectivity.values().into_iter().enumerate() {
+            let range = reflectivity.gate_range_meters(gate_index);
+            if range < options.min_range_meters {
+                continue;
+            }
+
+            considered += 1;
+            if let MomentValue::Value(value) = value {
+                powers.push(value - 20.0 * (range / 1000.0).log10());
+            }
+        }
+
+        if powers.len() < options.min_gates
+            || (powers.len() as f32) < options.min_gate_fraction * considered as f32
+        {
+            continue;
+        }
+
+        if standard_deviation(&powers)
+            .is_none_or(|deviation| deviation > options.max_power_deviation_db)
+        {
+            continue;
+        }
+
+        let (sun_azimuth, sun_elevation) = sun_position(
+            site.latitude(),
+            site.longitude(),
+            radial.collection_timestamp(),
+        );
+
+        let spike = SunSpike::new(
+            sweep.elevation_number(),
+            radial.collection_timestamp(),
+            radial.azimuth_angle_degrees(),
+            radial.elevation_angle_degrees(),
+            sun_azimuth,
+            sun_elevation,
+            powers.iter().sum::<f32>() / powers.len() as f32,
+            powers.len(),
+        );
+
+        if spike
+            .azimuth_offset_degrees()
+            .hypot(spike.elevation_offset_degrees())
+            <= options.max_sun_offset_degrees
+        {
+            spikes.push(spike);
+        }
+    }
+
+    Ok(spikes)
+}
+
+/// Detects sun spikes across a scan's sweeps and estimates the antenna pointing bias and solar
+/// power from them. Returns [None] if the scan has no sun spikes.
+pub fn sun_check(scan: &Scan, site: &Site, options: &SunOptions) -> Result<Option<SunCheck>> {
+    let mut spikes = Vec::new();
+    for sweep in scan.sweeps() {
+        spikes.extend(detect_sun_spikes(sweep, site, options)?);
+    }
+
+    if spikes.is_empty() {
+        return Ok(None);
+    }
+
+    // Offsets are weighted by linear power so spikes nearest the center of the beam dominate.
+    let mut weight = 0.0;
+    let mut azimuth_bias = 0.0;
+    let mut elevation_bias = 0.0;
+    let mut peak_power = f32::MIN;
+    for spike in &spikes {
+        let spike_weight = 10.0f32.powf(spike.power_db() / 10.0);
+        weight += spike_weight;
+        azimuth_bias += spike_weight * spike.azimuth_offset_degrees();
+        elevation_bias += spike_weight * spike.elevation_offset_degrees();
+        peak_power = peak_power.max(spike.power_db());
+    }
+
+    Ok(Some(SunCheck::new(
+        azimuth_bias / weight,
+        elevation_bias / weight,
+        peak_power,
+        spikes,
+    )))
+}
+
+/// Atmospheric refraction in degrees for a body at the given true elevation in degrees, following
+/// Bennett's formula for standard conditions.
+fn refraction(elevation_degrees: f64) -> f64 {
+    if elevation_degrees < -1.0 {
+        return 0.0;
+    }
+
+    let apparent = elevation_degrees + 10.3 / (elevation_degrees + 5.11);
+    1.02 / apparent.to_radians().tan() / 60.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::Product;
+
+    #[test]
+    fn computes_sun_position() {
+        // Near the March 2024 equinox, the sun crosses the meridian at the equator and Greenwich
+        // close to overhead, rises in the east and sets in the west.
+        let noon = 1_710_936_420_000;
+        let (_, elevation) = sun_position(0.0, 0.0, noon);
+        assert!(elevation > 89.0, "elevation {elevation}");
+
+        let (azimuth, elevation) = sun_position(45.0, 0.0, noon);
+        assert!((azimuth - 180.0).abs() < 1.0, "azimuth {azimuth}");
+        assert!((elevation - 45.0).abs() < 0.5, "elevation {elevation}");
+
+        let (azimuth, _) = sun_position(0.0, 0.0, noon - 5 * 3_600_000);
+        assert!((azimuth - 90.0).abs() < 2.0, "azimuth {azimuth}");
+        let (azimuth, _) = sun_position(0.0, 0.0, noon + 5 * 3_600_000);
+        assert!((azimuth - 270.0).abs() < 2.0, "azimuth {azimuth}");
+    }
+
+    #[test]
+    fn estimates_pointing_bias() {
+        let site = Site::new(*b"KTLX", 35.33, -97.28, 370, 20);
+        let timestamp = 1_718_971_200_000;
+        let (sun_azimuth, sun_elevation) =
+            sun_position(site.latitude(), site.longitude(), timestamp);
+
+        // The beam has a one degree half-power width, and the antenna reports azimuths 0.3 degrees clockwise of and elevations 0.2 degrees above
+        // where it points. Rain to the south of the radar should not be mistaken for a spike.
+        let (azimuth_bias, elevation_bias) = (0.3, 0.2);
+        let radials = (0..720)
+            .map(|azimuth_number| {
+                let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                let offset =
+                    (azimuth - azimuth_bias - sun_azimuth) * sun_elevation.to_radians().cos();
+
+                let reflectivity = (0..240)
+                    .map(|gate_index| {
+                        let range = 2_000.0 + gate_index as f32 * 250.0;
+                        if offset.abs() < 1.5 {
+                            let power = -12.0 * offset.powi(2);
+                            let value = power + 20.0 * (range / 1000.0).log10();
+                            (value * 2.0 + 66.0).round() as u8
+                        } else if (170.0..190.0).contains(&azimuth) && gate_index < 120 {
+                            146
+                        } else {
+                            0
+                        }
+                    })
+                    .collect();
+
+                radial(
+                    timestamp,
+                    azimuth_number,
+                    0.5,
+                    1,
+                    sun_elevation + elevation_bias,
+                )
+                .with_moment(
+                    Product::Reflectivity,
+                    moment(&Product::Reflectivity, reflectivity),
+                )
+            })
+            .collect();
+
+        let scan = Scan::new(35, vec![Sweep::new(1, radials)]);
+        let check = sun_check(&scan, &site, &SunOptions::default())
+            .unwrap()
+            .unwrap();
+
+        assert!(check.spikes().len() >= 4);
+        assert!(
+            (check.azimuth_bias_degrees() - azimuth_bias * sun_elevation.to_radians().cos()).abs()
+                < 0.1
+        );
+        assert!((check.elevation_bias_degrees() - elevation_bias).abs() < 0.01);
+        assert!(check.peak_power_db().abs() < 1.0);
+    }
+}
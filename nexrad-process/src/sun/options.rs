@@ -0,0 +1,59 @@
+use crate::result::{Error, Result};
+
+/// Options controlling detection of sun spikes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SunOptions {
+    /// Only gates at or beyond this slant range in meters are considered. Nearby gates are more
+    /// often contaminated by clutter and weather.
+    pub min_range_meters: f32,
+
+    /// The fraction of a radial's gates, from 0 to 1, which must have valid reflectivity for it to
+    /// be considered a spike. Solar noise fills the whole radial.
+    pub min_gate_fraction: f32,
+
+    /// The minimum number of valid gates for a radial to be considered a spike.
+    pub min_gates: usize,
+
+    /// The maximum standard deviation in dB of a spike's range-normalized power. Solar noise has
+    /// nearly constant received power, so reflectivity increases with the square of range.
+    pub max_power_deviation_db: f32,
+
+    /// Spikes more than this angular distance in degrees from the computed sun position are
+    /// attributed to other interference and ignored.
+    pub max_sun_offset_degrees: f32,
+}
+
+impl SunOptions {
+    /// Checks that these options are usable for detection.
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.min_gate_fraction) {
+            return Err(Error::InvalidOptions(
+                "minimum gate fraction must be between 0 and 1",
+            ));
+        }
+
+        if self.min_gates < 2 {
+            return Err(Error::InvalidOptions(
+                "spikes must require at least two gates",
+            ));
+        }
+
+        if self.max_sun_offset_degrees <= 0.0 {
+            return Err(Error::InvalidOptions("maximum sun offset must be positive"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SunOptions {
+    fn default() -> Self {
+        Self {
+            min_range_meters: 20_000.0,
+            min_gate_fraction: 0.7,
+            min_gates: 50,
+            max_power_deviation_db: 3.0,
+            max_sun_offset_degrees: 3.0,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::math::fold;
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A radial filled with solar noise, along with the computed position of the sun when it was
+/// collected.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SunSpike {
+    elevation_number: u8,
+    collection_timestamp: i64,
+    azimuth_degrees: f32,
+    elevation_angle_degrees: f32,
+    sun_azimuth_degrees: f32,
+    sun_elevation_degrees: f32,
+    power_db: f32,
+    gate_count: usize,
+}
+
+impl SunSpike {
+    /// Create a new sun spike with the given properties.
+    pub fn new(
+        elevation_number: u8,
+        collection_timestamp: i64,
+        azimuth_degrees: f32,
+        elevation_angle_degrees: f32,
+        sun_azimuth_degrees: f32,
+        sun_elevation_degrees: f32,
+        power_db: f32,
+        gate_count: usize,
+    ) -> Self {
+        Self {
+            elevation_number,
+            collection_timestamp,
+            azimuth_degrees,
+            elevation_angle_degrees,
+            sun_azimuth_degrees,
+            sun_elevation_degrees,
+            power_db,
+            gate_count,
+        }
+    }
+
+    /// The elevation number of the sweep containing this spike.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// Collection time of the spike's radial in milliseconds since the epoch.
+    pub fn collection_timestamp(&self) -> i64 {
+        self.collection_timestamp
+    }
+
+    /// Azimuth angle in degrees reported for the spike's radial.
+    pub fn azimuth_degrees(&self) -> f32 {
+        self.azimuth_degrees
+    }
+
+    /// Elevation angle in degrees reported for the spike's radial.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// Computed azimuth of the sun in degrees when the radial was collected.
+    pub fn sun_azimuth_degrees(&self) -> f32 {
+        self.sun_azimuth_degrees
+    }
+
+    /// Computed apparent elevation of the sun in degrees, including atmospheric refraction, when
+    /// the radial was collected.
+    pub fn sun_elevation_degrees(&self) -> f32 {
+        self.sun_elevation_degrees
+    }
+
+    /// The angular distance in degrees along the horizon from the sun to the radial, scaled by the
+    /// cosine of the sun's elevation. Positive where the radial's azimuth is clockwise of the sun.
+    pub fn azimuth_offset_degrees(&self) -> f32 {
+        fold(self.azimuth_degrees - self.sun_azimuth_degrees, 360.0)
+            * self.sun_elevation_degrees.to_radians().cos()
+    }
+
+    /// The angular distance in degrees from the sun's elevation to the radial's. Positive where the
+    /// radial is above the sun.
+    pub fn elevation_offset_degrees(&self) -> f32 {
+        self.elevation_angle_degrees - self.sun_elevation_degrees
+    }
+
+    /// The spike's mean range-normalized power in dB: reflectivity less the range correction,
+    /// equivalent to the reflectivity solar noise would produce at 1 kilometer. This differs from
+    /// the received solar power by the radar constant.
+    pub fn power_db(&self) -> f32 {
+        self.power_db
+    }
+
+    /// The number of gates with solar noise in the spike's radial.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+}
+
+impl Debug for SunSpike {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SunSpike")
+            .field("elevation_number", &self.elevation_number())
+            .field("collection_timestamp", &self.collection_timestamp())
+            .field("azimuth_degrees", &self.azimuth_degrees())
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("sun_azimuth_degrees", &self.sun_azimuth_degrees())
+            .field("sun_elevation_degrees", &self.sun_elevation_degrees())
+            .field("power_db", &self.power_db())
+            .field("gate_count", &self.gate_count())
+            .finish()
+    }
+}
+
+/// The antenna pointing bias and solar power estimated from a scan's sun spikes.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SunCheck {
+    azimuth_bias_degrees: f32,
+    elevation_bias_degrees: f32,
+    peak_power_db: f32,
+    spikes: Vec<SunSpike>,
+}
+
+impl SunCheck {
+    /// Create a new sun check with the given properties.
+    pub fn new(
+        azimuth_bias_degrees: f32,
+        elevation_bias_degrees: f32,
+        peak_power_db: f32,
+        spikes: Vec<SunSpike>,
+    ) -> Self {
+        Self {
+            azimuth_bias_degrees,
+            elevation_bias_degrees,
+            peak_power_db,
+            spikes,
+        }
+    }
+
+    /// The power-weighted mean azimuth offset in degrees of the spikes from the sun. A positive
+    /// bias indicates reported azimuths are clockwise of where the antenna is actually pointing.
+    pub fn azimuth_bias_degrees(&self) -> f32 {
+        self.azimuth_bias_degrees
+    }
+
+    /// The power-weighted mean elevation offset in degrees of the spikes from the sun. A positive
+    /// bias indicates reported elevations are above where the antenna is actually pointing. This
+    /// is only well constrained when spikes are observed at several elevations.
+    pub fn elevation_bias_degrees(&self) -> f32 {
+        self.elevation_bias_degrees
+    }
+
+    /// The largest range-normalized power in dB among the spikes, approximating the solar power
+    /// received with the antenna pointed at the sun.
+    pub fn peak_power_db(&self) -> f32 {
+        self.peak_power_db
+    }
+
+    /// The sun spikes this check was derived from.
+    pub fn spikes(&self) -> &Vec<SunSpike> {
+        self.spikes.as_ref()
+    }
+}
+
+impl Debug for SunCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SunCheck")
+            .field("azimuth_bias_degrees", &self.azimuth_bias_degrees())
+            .field("elevation_bias_degrees", &self.elevation_bias_degrees())
+            .field("peak_power_db", &self.peak_power_db())
+            .field("spikes", &self.spikes())
+            .finish()
+    }
+}
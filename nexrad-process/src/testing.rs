@@ -0,0 +1,74 @@
+//! Builders for the synthetic radials and moments used throughout this crate's tests.
+
+use nexrad_model::data::{MomentData, Product, Radial, RadialStatus};
+
+/// Range from the radar to the center of the first gate of test moments in meters.
+pub(crate) const FIRST_GATE_RANGE: u16 = 2_000;
+
+/// Distance between the centers of adjacent gates of test moments in meters.
+pub(crate) const GATE_INTERVAL: u16 = 250;
+
+/// The azimuth in degrees at the center of the radial at the given index of a sweep whose first
+/// radial starts at north.
+pub(crate) fn azimuth(azimuth_index: u16, azimuth_spacing_degrees: f32) -> f32 {
+    (azimuth_index as f32 + 0.5) * azimuth_spacing_degrees
+}
+
+/// An intermediate radial at the given index of a sweep whose first radial starts at north, with
+/// no moment data.
+pub(crate) fn radial(
+    collection_timestamp: i64,
+    azimuth_index: u16,
+    azimuth_spacing_degrees: f32,
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+) -> Radial {
+    Radial::new(
+        collection_timestamp,
+        azimuth_index + 1,
+        azimuth(azimuth_index, azimuth_spacing_degrees),
+        azimuth_spacing_degrees,
+        RadialStatus::IntermediateRadialData,
+        elevation_number,
+        elevation_angle_degrees,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Moment data for a base product with the standard test gate geometry, encoded with the
+/// product's Level II scale and offset.
+pub(crate) fn moment(product: &Product, values: Vec<u8>) -> MomentData {
+    moment_at(product, FIRST_GATE_RANGE, GATE_INTERVAL, values)
+}
+
+/// Moment data for a base product with the given gate geometry in meters, encoded with the
+/// product's Level II scale and offset.
+pub(crate) fn moment_at(
+    product: &Product,
+    first_gate_range: u16,
+    gate_interval: u16,
+    values: Vec<u8>,
+) -> MomentData {
+    let (scale, offset) = encoding(product);
+    MomentData::from_fixed_point(scale, offset, values)
+        .with_gate_geometry(first_gate_range, gate_interval)
+}
+
+/// The Level II scale and offset of a base product.
+pub(crate) fn encoding(product: &Product) -> (f32, f32) {
+    match product {
+        Product::Reflectivity => (2.0, 66.0),
+        Product::Velocity => (2.0, 129.0),
+        Product::SpectrumWidth => (2.0, 129.0),
+        Product::DifferentialReflectivity => (16.0, 128.0),
+        Product::DifferentialPhase => (2.8361, 2.0),
+        Product::CorrelationCoefficient => (300.0, -60.5),
+        Product::SpecificDifferentialPhase | Product::Custom(_) => (1.0, 0.0),
+    }
+}
@@ -0,0 +1,240 @@
+//!
+//! Velocity Azimuth Display (VAD) wind retrieval. Within a sweep, the radial velocities observed
+//! around a ring at a fixed slant range trace out a sinusoid in azimuth whose amplitude and phase
+//! describe the horizontal wind at the height of that ring. Fitting rings across all of a scan's
+//! sweeps produces a vertical wind profile similar to the NWS VAD Wind Profile (VWP) product.
+//!
+
+mod fit;
+
+mod options;
+pub use options::*;
+
+mod profile;
+pub use profile::*;
+
+use crate::result::Result;
+use crate::vad::fit::{fit_aliased, fit_harmonic, Sample};
+use nexrad_model::data::{MomentValue, Scan, Sweep};
+use nexrad_model::geometry::beam_height_meters;
+
+/// Retrieves a vertical wind profile from the velocity data in a scan's sweeps.
+pub fn wind_profile(scan: &Scan, options: &VadOptions) -> Result<WindProfile> {
+    options.validate()?;
+
+    let mut levels = Vec::new();
+    for sweep in scan.sweeps() {
+        levels.extend(sweep_wind_levels(sweep, options)?);
+    }
+
+    Ok(WindProfile::new(levels))
+}
+
+/// Retrieves wind levels from a single sweep's velocity data, one for each of the configured slant
+/// ranges whose fit passes quality control.
+pub fn sweep_wind_levels(sweep: &Sweep, options: &VadOptions) -> Result<Vec<WindLevel>> {
+    options.validate()?;
+
+    let mut rings = vec![Vec::new(); options.slant_ranges_meters.len()];
+    let mut elevation_sum = 0.0;
+    let mut elevation_count = 0;
+    let mut nyquist_velocity: Option<f32> = None;
+
+    for radial in sweep.radials() {
+        let velocity = match radial.velocity() {
+            Some(velocity) => velocity,
+            None => continue,
+        };
+
+        elevation_sum += radial.elevation_angle_degrees();
+        elevation_count += 1;
+
+        if let Some(radial_nyquist) = radial.nyquist_velocity_meters_per_second() {
+            nyquist_velocity = Some(
+                nyquist_velocity.map_or(radial_nyquist, |nyquist| nyquist.min(radial_nyquist)),
+            );
+        }
+
+        let azimuth_radians = radial.azimuth_angle_degrees().to_radians();
+        let values = velocity.values();
+
+        for (ring, slant_range) in rings.iter_mut().zip(&options.slant_ranges_meters) {
+            let half_window = options.range_window_meters / 2.0;
+            let first = velocity.gate_index_meters(slant_range - half_window);
+            let last = velocity.gate_index_meters(slant_range + half_window);
+
+            if let (Some(first), Some(last)) = (first, last) {
+                for value in &values[first..=last] {
+                    if let MomentValue::Value(value) = value {
+                        ring.push(Sample {
+                            azimuth_radians,
+                            velocity: *value,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    if elevation_count == 0 {
+        return Ok(Vec::new());
+    }
+
+    let elevation_angle = elevation_sum / elevation_count as f32;
+    if elevation_angle < options.min_elevation_degrees
+        || elevation_angle > options.max_elevation_degrees
+    {
+        return Ok(Vec::new());
+    }
+
+    let elevation_cosine = elevation_angle.to_radians().cos();
+
+    let mut levels = Vec::new();
+    for (ring, slant_range) in rings.iter().zip(&options.slant_ranges_meters) {
+        if ring.len() < options.min_points
+            || max_azimuth_gap_degrees(ring) > options.max_azimuth_gap_degrees
+        {
+            continue;
+        }
+
+        let fit = match (options.velocity_mode, nyquist_velocity) {
+            (VelocityMode::Aliased, Some(nyquist_velocity)) => fit_aliased(
+                ring,
+                nyquist_velocity,
+                elevation_cosine,
+                options.max_wind_speed_meters_per_second,
+            ),
+            _ => fit_harmonic(ring),
+        };
+
+        let fit = match fit {
+            Some(fit) if fit.rms_error <= options.max_rms_error_meters_per_second => fit,
+            _ => continue,
+        };
+
+        let u = fit.sine / elevation_cosine;
+        let v = fit.cosine / elevation_cosine;
+        let direction = (-u).atan2(-v).to_degrees().rem_euclid(360.0);
+
+        levels.push(WindLevel::new(
+            beam_height_meters(*slant_range, elevation_angle),
+            direction,
+            u.hypot(v),
+            fit.rms_error,
+            sweep.elevation_number(),
+            elevation_angle,
+            *slant_range,
+            ring.len(),
+        ));
+    }
+
+    Ok(levels)
+}
+
+/// The largest azimuthal gap in degrees between consecutive samples around the ring.
+fn max_azimuth_gap_degrees(samples: &[Sample]) -> f32 {
+    let mut azimuths: Vec<f32> = samples
+        .iter()
+        .map(|sample| sample.azimuth_radians.to_degrees().rem_euclid(360.0))
+        .collect();
+
+    azimuths.sort_by(f32::total_cmp);
+    azimuths.dedup();
+
+    let (first, last) = match (azimuths.first(), azimuths.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return 360.0,
+    };
+
+    azimuths
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .fold(first + 360.0 - last, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment_at, radial};
+    use nexrad_model::data::{Product, Scan, Sweep};
+
+    const GATE_INTERVAL: u16 = 250;
+    const GATE_COUNT: usize = 200;
+
+    fn sweep(elevation_angle: f32, nyquist: Option<f32>, u: f32, v: f32) -> Sweep {
+        let radials = (0..360)
+            .map(|azimuth_number| {
+                let azimuth = azimuth_number as f32 + 0.5;
+                let mut velocity = (u * azimuth.to_radians().sin()
+                    + v * azimuth.to_radians().cos())
+                    * elevation_angle.to_radians().cos();
+
+                if let Some(nyquist) = nyquist {
+                    velocity -= 2.0 * nyquist * (velocity / (2.0 * nyquist)).round();
+                }
+
+                let raw = (velocity * 2.0 + 129.0).round() as u8;
+                radial(0, azimuth_number, 1.0, 1, elevation_angle)
+                    .with_nyquist_velocity_meters_per_second(nyquist)
+                    .with_moment(
+                        Product::Velocity,
+                        moment_at(
+                            &Product::Velocity,
+                            GATE_INTERVAL,
+                            GATE_INTERVAL,
+                            vec![raw; GATE_COUNT],
+                        ),
+                    )
+            })
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    fn options(velocity_mode: VelocityMode) -> VadOptions {
+        VadOptions {
+            slant_ranges_meters: vec![20_000.0],
+            velocity_mode,
+            ..VadOptions::default()
+        }
+    }
+
+    #[test]
+    fn dealiased_velocities() {
+        let scan = Scan::new(212, vec![sweep(2.4, None, 20.0, -10.0)]);
+        let profile = wind_profile(&scan, &options(VelocityMode::Dealiased)).unwrap();
+
+        let level = &profile.levels()[0];
+        assert!((level.speed_meters_per_second() - 22.36).abs() < 0.3);
+        assert!((level.direction_degrees() - 296.57).abs() < 1.0);
+        assert!((level.u_meters_per_second() - 20.0).abs() < 0.3);
+        assert!((level.v_meters_per_second() + 10.0).abs() < 0.3);
+        assert!((level.height_meters() - 861.0).abs() < 10.0);
+    }
+
+    #[test]
+    fn aliased_velocities() {
+        let scan = Scan::new(212, vec![sweep(0.5, Some(12.0), -25.0, 15.0)]);
+        let profile = wind_profile(&scan, &options(VelocityMode::Aliased)).unwrap();
+
+        let level = &profile.levels()[0];
+        assert!((level.u_meters_per_second() + 25.0).abs() < 0.3);
+        assert!((level.v_meters_per_second() - 15.0).abs() < 0.3);
+        assert!(level.rms_error_meters_per_second() < 0.5);
+    }
+
+    #[test]
+    fn azimuth_gap_rejects_fit() {
+        let sweep = sweep(2.4, None, 20.0, -10.0);
+        let radials = sweep
+            .radials()
+            .iter()
+            .filter(|radial| radial.azimuth_angle_degrees() < 270.0)
+            .cloned()
+            .collect();
+
+        let scan = Scan::new(212, vec![Sweep::new(1, radials)]);
+        let profile = wind_profile(&scan, &options(VelocityMode::Dealiased)).unwrap();
+        assert!(profile.levels().is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::math::{fold, solve_3x3};
+
+/// A single radial velocity observation at some azimuth angle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Sample {
+    pub azimuth_radians: f32,
+    pub velocity: f32,
+}
+
+/// A least-squares fit of radial velocity to a first-order harmonic of azimuth in the form
+/// `offset + cosine * cos(azimuth) + sine * sin(azimuth)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct HarmonicFit {
+    pub offset: f32,
+    pub cosine: f32,
+    pub sine: f32,
+    pub rms_error: f32,
+}
+
+impl HarmonicFit {
+    /// The fitted radial velocity at the given azimuth.
+    pub fn evaluate(&self, azimuth_radians: f32) -> f32 {
+        self.offset + self.cosine * azimuth_radians.cos() + self.sine * azimuth_radians.sin()
+    }
+}
+
+/// Fits a first-order harmonic to the given samples, returning [None] if the samples do not
+/// constrain the fit.
+pub(crate) fn fit_harmonic(samples: &[Sample]) -> Option<HarmonicFit> {
+    let mut normal = [[0.0f64; 3]; 3];
+    let mut target = [0.0f64; 3];
+
+    for sample in samples {
+        let azimuth = sample.azimuth_radians as f64;
+        let basis = [1.0, azimuth.cos(), azimuth.sin()];
+        for row in 0..3 {
+            for column in 0..3 {
+                normal[row][column] += basis[row] * basis[column];
+            }
+            target[row] += basis[row] * sample.velocity as f64;
+        }
+    }
+
+    let [offset, cosine, sine] = solve_3x3(normal, target)?;
+    let mut fit = HarmonicFit {
+        offset: offset as f32,
+        cosine: cosine as f32,
+        sine: sine as f32,
+        rms_error: 0.0,
+    };
+
+    let squared_error: f32 = samples
+        .iter()
+        .map(|sample| (sample.velocity - fit.evaluate(sample.azimuth_radians)).powi(2))
+        .sum();
+    fit.rms_error = (squared_error / samples.len() as f32).sqrt();
+
+    Some(fit)
+}
+
+/// Fits a first-order harmonic to samples which may be aliased into the interval +/- the Nyquist
+/// velocity. A coarse search over horizontal wind vectors minimizes the folded residual, then the
+/// samples are unfolded relative to the best candidate and refined with a least-squares fit.
+pub(crate) fn fit_aliased(
+    samples: &[Sample],
+    nyquist_velocity: f32,
+    elevation_cosine: f32,
+    max_wind_speed: f32,
+) -> Option<HarmonicFit> {
+    const SEARCH_STEP: f32 = 2.0;
+    const SEARCH_SAMPLES: usize = 180;
+    const REFINEMENT_PASSES: usize = 3;
+
+    let interval = 2.0 * nyquist_velocity;
+    let search_stride = samples.len().div_ceil(SEARCH_SAMPLES).max(1);
+    let search_samples: Vec<(f32, f32, f32)> = samples
+        .iter()
+        .step_by(search_stride)
+        .map(|sample| {
+            (
+                sample.azimuth_radians.sin() * elevation_cosine,
+                sample.azimuth_radians.cos() * elevation_cosine,
+                sample.velocity,
+            )
+        })
+        .collect();
+
+    let steps = (max_wind_speed / SEARCH_STEP).ceil() as i32;
+    let mut best = None;
+    let mut best_cost = f32::MAX;
+    for u_step in -steps..=steps {
+        for v_step in -steps..=steps {
+            let u = u_step as f32 * SEARCH_STEP;
+            let v = v_step as f32 * SEARCH_STEP;
+
+            let cost: f32 = search_samples
+                .iter()
+                .map(|(u_weight, v_weight, velocity)| {
+                    fold(velocity - (u * u_weight + v * v_weight), interval).powi(2)
+                })
+                .sum();
+
+            if cost < best_cost {
+                best_cost = cost;
+                best = Some(HarmonicFit {
+                    offset: 0.0,
+                    cosine: v * elevation_cosine,
+                    sine: u * elevation_cosine,
+                    rms_error: 0.0,
+                });
+            }
+        }
+    }
+
+    let mut fit = best?;
+    for _ in 0..REFINEMENT_PASSES {
+        let unfolded: Vec<Sample> = samples
+            .iter()
+            .map(|sample| {
+                let expected = fit.evaluate(sample.azimuth_radians);
+                Sample {
+                    azimuth_radians: sample.azimuth_radians,
+                    velocity: expected + fold(sample.velocity - expected, interval),
+                }
+            })
+            .collect();
+
+        fit = fit_harmonic(&unfolded)?;
+    }
+
+    Some(fit)
+}
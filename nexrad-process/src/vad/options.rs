@@ -0,0 +1,94 @@
+use crate::result::{Error, Result};
+
+/// How radial velocity data should be interpreted when fitting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VelocityMode {
+    /// Velocities are raw Level II values which may be aliased (folded) into the interval +/- the
+    /// radial's Nyquist velocity. The fit accounts for folding where the Nyquist velocity is known.
+    Aliased,
+    /// Velocities have already been dealiased and are fit directly.
+    Dealiased,
+}
+
+/// Options controlling the Velocity Azimuth Display wind retrieval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VadOptions {
+    /// Slant ranges in meters from the radar at which a ring of velocities is fit in each sweep.
+    pub slant_ranges_meters: Vec<f32>,
+
+    /// Width in meters of the window of gates around each slant range whose velocities are
+    /// included in the fit.
+    pub range_window_meters: f32,
+
+    /// Sweeps below this elevation angle in degrees are not used.
+    pub min_elevation_degrees: f32,
+
+    /// Sweeps above this elevation angle in degrees are not used.
+    pub max_elevation_degrees: f32,
+
+    /// How velocity data should be interpreted.
+    pub velocity_mode: VelocityMode,
+
+    /// The minimum number of valid velocity gates required for a ring to be fit.
+    pub min_points: usize,
+
+    /// The largest azimuthal gap in degrees without valid velocity data allowed for a ring to be
+    /// fit. Large gaps leave the sinusoid poorly constrained.
+    pub max_azimuth_gap_degrees: f32,
+
+    /// Fits with a root-mean-square error in meters per second above this are rejected.
+    pub max_rms_error_meters_per_second: f32,
+
+    /// The largest horizontal wind speed in meters per second considered when fitting aliased
+    /// velocities.
+    pub max_wind_speed_meters_per_second: f32,
+}
+
+impl VadOptions {
+    /// Checks that these options are usable for a retrieval.
+    pub fn validate(&self) -> Result<()> {
+        if self.slant_ranges_meters.is_empty() {
+            return Err(Error::InvalidOptions(
+                "at least one slant range is required",
+            ));
+        }
+
+        if self.range_window_meters <= 0.0 {
+            return Err(Error::InvalidOptions("range window must be positive"));
+        }
+
+        if self.min_elevation_degrees > self.max_elevation_degrees
+            || self.max_elevation_degrees >= 90.0
+        {
+            return Err(Error::InvalidOptions(
+                "elevation bounds must be ordered and below 90 degrees",
+            ));
+        }
+
+        if self.min_points < 3 {
+            return Err(Error::InvalidOptions("at least three points are required"));
+        }
+
+        if self.max_wind_speed_meters_per_second <= 0.0 {
+            return Err(Error::InvalidOptions("max wind speed must be positive"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for VadOptions {
+    fn default() -> Self {
+        Self {
+            slant_ranges_meters: vec![10_000.0, 20_000.0, 30_000.0, 40_000.0],
+            range_window_meters: 1_000.0,
+            min_elevation_degrees: 0.0,
+            max_elevation_degrees: 45.0,
+            velocity_mode: VelocityMode::Aliased,
+            min_points: 30,
+            max_azimuth_gap_degrees: 30.0,
+            max_rms_error_meters_per_second: 5.0,
+            max_wind_speed_meters_per_second: 60.0,
+        }
+    }
+}
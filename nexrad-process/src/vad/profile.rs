@@ -0,0 +1,163 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A vertical profile of horizontal wind retrieved from a volume scan, ordered by ascending height.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindProfile {
+    levels: Vec<WindLevel>,
+}
+
+impl WindProfile {
+    /// Create a new wind profile from the given levels, ordering them by ascending height.
+    pub fn new(mut levels: Vec<WindLevel>) -> Self {
+        levels.sort_by(|a, b| a.height_meters.total_cmp(&b.height_meters));
+        Self { levels }
+    }
+
+    /// The retrieved wind levels ordered by ascending height.
+    pub fn levels(&self) -> &Vec<WindLevel> {
+        self.levels.as_ref()
+    }
+
+    /// Reduce this profile to at most one level per height bin of the given size in meters, keeping
+    /// the level with the lowest RMS error in each bin. This mirrors how the VWP product selects a
+    /// single wind for each reporting altitude.
+    pub fn binned(&self, bin_size_meters: f32) -> Self {
+        let mut levels: Vec<WindLevel> = Vec::new();
+
+        for level in &self.levels {
+            let bin = (level.height_meters / bin_size_meters).floor();
+            match levels.last_mut() {
+                Some(last) if (last.height_meters / bin_size_meters).floor() == bin => {
+                    if level.rms_error_meters_per_second < last.rms_error_meters_per_second {
+                        *last = level.clone();
+                    }
+                }
+                _ => levels.push(level.clone()),
+            }
+        }
+
+        Self { levels }
+    }
+}
+
+impl Debug for WindProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WindProfile")
+            .field("levels", &self.levels())
+            .finish()
+    }
+}
+
+/// A horizontal wind estimate at a single height, retrieved by fitting a sinusoid to radial
+/// velocities around a ring at some slant range in a sweep.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindLevel {
+    height_meters: f32,
+    direction_degrees: f32,
+    speed_meters_per_second: f32,
+    rms_error_meters_per_second: f32,
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+    slant_range_meters: f32,
+    point_count: usize,
+}
+
+impl WindLevel {
+    /// Create a new wind level with the given properties.
+    pub fn new(
+        height_meters: f32,
+        direction_degrees: f32,
+        speed_meters_per_second: f32,
+        rms_error_meters_per_second: f32,
+        elevation_number: u8,
+        elevation_angle_degrees: f32,
+        slant_range_meters: f32,
+        point_count: usize,
+    ) -> Self {
+        Self {
+            height_meters,
+            direction_degrees,
+            speed_meters_per_second,
+            rms_error_meters_per_second,
+            elevation_number,
+            elevation_angle_degrees,
+            slant_range_meters,
+            point_count,
+        }
+    }
+
+    /// Height of this level above the radar antenna in meters. Add the site's height and feedhorn
+    /// height for the height above sea level.
+    pub fn height_meters(&self) -> f32 {
+        self.height_meters
+    }
+
+    /// The direction the wind is blowing from in degrees clockwise from north.
+    pub fn direction_degrees(&self) -> f32 {
+        self.direction_degrees
+    }
+
+    /// The horizontal wind speed in meters per second.
+    pub fn speed_meters_per_second(&self) -> f32 {
+        self.speed_meters_per_second
+    }
+
+    /// The eastward component of the wind in meters per second.
+    pub fn u_meters_per_second(&self) -> f32 {
+        -self.speed_meters_per_second * self.direction_degrees.to_radians().sin()
+    }
+
+    /// The northward component of the wind in meters per second.
+    pub fn v_meters_per_second(&self) -> f32 {
+        -self.speed_meters_per_second * self.direction_degrees.to_radians().cos()
+    }
+
+    /// The root-mean-square difference in meters per second between the observed radial velocities
+    /// and the fitted sinusoid.
+    pub fn rms_error_meters_per_second(&self) -> f32 {
+        self.rms_error_meters_per_second
+    }
+
+    /// The elevation number of the sweep this level was retrieved from.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// The mean elevation angle in degrees of the sweep this level was retrieved from.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// The slant range in meters of the ring this level was retrieved from.
+    pub fn slant_range_meters(&self) -> f32 {
+        self.slant_range_meters
+    }
+
+    /// The number of velocity gates used in the fit.
+    pub fn point_count(&self) -> usize {
+        self.point_count
+    }
+}
+
+impl Debug for WindLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WindLevel")
+            .field("height_meters", &self.height_meters())
+            .field("direction_degrees", &self.direction_degrees())
+            .field("speed_meters_per_second", &self.speed_meters_per_second())
+            .field(
+                "rms_error_meters_per_second",
+                &self.rms_error_meters_per_second(),
+            )
+            .field("elevation_number", &self.elevation_number())
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("slant_range_meters", &self.slant_range_meters())
+            .field("point_count", &self.point_count())
+            .finish()
+    }
+}
@@ -0,0 +1,381 @@
+//!
+//! Mesocyclone and tornado vortex signature (TVS) detection from radial velocity, in the spirit of
+//! the NSSL Mesocyclone Detection Algorithm and Tornado Detection Algorithm. Cyclonic rotation
+//! appears as velocities increasing with azimuth across a circulation. Within each sweep, runs of
+//! increasing velocity at each gate ("shear segments") are grouped with overlapping segments at
+//! adjacent gates into features. Features from successive sweeps which are vertically aligned are
+//! then associated into detections with a base, top and location.
+//!
+//! Mesocyclones are found from runs spanning one to several kilometers, while tornado vortex
+//! signatures are found from intense velocity differences between adjacent radials.
+//!
+
+mod options;
+pub use options::*;
+
+mod detection;
+pub use detection::*;
+
+use crate::association::{east_north, elevation_levels, stack_levels};
+use crate::field::PolarField;
+use crate::math::fold;
+use crate::result::Result;
+use nexrad_model::data::{Radial, Scan, Sweep};
+use nexrad_model::geometry::{
+    azimuth_difference_degrees, beam_height_meters, destination, ground_range_meters,
+};
+use nexrad_model::meta::Site;
+
+/// Detects mesocyclones and tornado vortex signatures in a scan, locating them relative to the
+/// given radar site.
+pub fn detect_vortices(
+    scan: &Scan,
+    site: &Site,
+    options: &VortexOptions,
+) -> Result<Vec<VortexDetection>> {
+    options.validate()?;
+
+    let mut detections = Vec::new();
+    for kind in [VortexKind::Mesocyclone, VortexKind::TornadoVortexSignature] {
+        let mut features = Vec::new();
+        for sweep in scan.sweeps() {
+            features.extend(sweep_vortex_features(sweep, kind, options)?);
+        }
+
+        detections.extend(associate(kind, features, site, options));
+    }
+
+    Ok(detections)
+}
+
+/// Finds features of the given kind in a single sweep's velocity data.
+pub fn sweep_vortex_features(
+    sweep: &Sweep,
+    kind: VortexKind,
+    options: &VortexOptions,
+) -> Result<Vec<VortexFeature>> {
+    options.validate()?;
+
+    let velocity = match PolarField::from_sweep(sweep, Radial::velocity) {
+        Some(velocity) => velocity,
+        None => return Ok(Vec::new()),
+    };
+
+    let nyquist_velocity = if options.unfold_velocities {
+        sweep
+            .radials()
+            .iter()
+            .filter_map(|radial| radial.nyquist_velocity_meters_per_second())
+            .reduce(f32::min)
+    } else {
+        None
+    };
+
+    let collection_timestamp = sweep
+        .radials()
+        .iter()
+        .map(|radial| radial.collection_timestamp())
+        .min()
+        .unwrap_or_default();
+
+    let segments = find_segments(&velocity, nyquist_velocity, kind, options);
+    let elevation_angle = velocity.elevation_angle_degrees().unwrap_or_default();
+
+    Ok(group_segments(&velocity, &segments, options.min_segments)
+        .into_iter()
+        .map(|group| {
+            let mut east = 0.0;
+            let mut north = 0.0;
+            let mut range = 0.0;
+            let mut weight = 0.0;
+            let mut strongest = &segments[group[0]];
+
+            for segment in group.iter().map(|index| &segments[*index]) {
+                let azimuth = segment.center_azimuth_degrees.to_radians();
+                east += segment.delta_velocity * azimuth.sin();
+                north += segment.delta_velocity * azimuth.cos();
+                range += segment.delta_velocity * velocity.gate_range_meters(segment.gate_index);
+                weight += segment.delta_velocity;
+
+                if segment.delta_velocity > strongest.delta_velocity {
+                    strongest = segment;
+                }
+            }
+
+            let slant_range = range / weight;
+            VortexFeature::new(
+                sweep.elevation_number(),
+                elevation_angle,
+                collection_timestamp,
+                east.atan2(north).to_degrees().rem_euclid(360.0),
+                slant_range,
+                ground_range_meters(slant_range, elevation_angle),
+                beam_height_meters(slant_range, elevation_angle),
+                strongest.delta_velocity,
+                strongest.diameter,
+            )
+        })
+        .collect())
+}
+
+/// A run of increasing velocity with azimuth at a single gate.
+struct Segment {
+    gate_index: usize,
+    start_radial: usize,
+    length: usize,
+    center_azimuth_degrees: f32,
+    delta_velocity: f32,
+    diameter: f32,
+}
+
+fn find_segments(
+    velocity: &PolarField,
+    nyquist_velocity: Option<f32>,
+    kind: VortexKind,
+    options: &VortexOptions,
+) -> Vec<Segment> {
+    let radial_count = velocity.radial_count();
+    if radial_count < 2 {
+        return Vec::new();
+    }
+
+    let azimuths = velocity.azimuths_degrees();
+    let nominal_spacing = 360.0 / radial_count as f32;
+
+    let mut segments = Vec::new();
+    let mut differences = vec![None; radial_count];
+
+    for gate_index in 0..velocity.gate_count() {
+        let range = velocity.gate_range_meters(gate_index);
+        if kind == VortexKind::TornadoVortexSignature && range > options.tvs_max_range_meters {
+            break;
+        }
+
+        for (radial_index, difference) in differences.iter_mut().enumerate() {
+            let next_index = (radial_index + 1) % radial_count;
+            *difference = match (
+                velocity.value(radial_index, gate_index),
+                velocity.value(next_index, gate_index),
+            ) {
+                (Some(value), Some(next_value))
+                    if azimuth_difference_degrees(azimuths[radial_index], azimuths[next_index])
+                        <= 2.0 * nominal_spacing =>
+                {
+                    let difference = next_value - value;
+                    Some(match nyquist_velocity {
+                        Some(nyquist) => fold(difference, 2.0 * nyquist),
+                        None => difference,
+                    })
+                }
+                _ => None,
+            };
+        }
+
+        let mut add_segment = |start_radial: usize, length: usize, delta_velocity: f32| {
+            let end_radial = (start_radial + length) % radial_count;
+            let span = (azimuths[end_radial] - azimuths[start_radial]).rem_euclid(360.0);
+            segments.push(Segment {
+                gate_index,
+                start_radial,
+                length,
+                center_azimuth_degrees: (azimuths[start_radial] + span / 2.0).rem_euclid(360.0),
+                delta_velocity,
+                diameter: range * span.to_radians(),
+            });
+        };
+
+        match kind {
+            VortexKind::TornadoVortexSignature => {
+                for (radial_index, difference) in differences.iter().enumerate() {
+                    if let Some(difference) = difference {
+                        if *difference >= options.tvs_min_delta_velocity_meters_per_second {
+                            add_segment(radial_index, 1, *difference);
+                        }
+                    }
+                }
+            }
+            VortexKind::Mesocyclone => {
+                for start_radial in 0..radial_count {
+                    let previous = differences[(start_radial + radial_count - 1) % radial_count];
+                    let starts_run = matches!(differences[start_radial], Some(d) if d > 0.0)
+                        && !matches!(previous, Some(d) if d > 0.0);
+                    if !starts_run {
+                        continue;
+                    }
+
+                    let mut length = 0;
+                    let mut delta_velocity = 0.0;
+                    while length < radial_count {
+                        match differences[(start_radial + length) % radial_count] {
+                            Some(difference) if difference > 0.0 => {
+                                delta_velocity += difference;
+                                length += 1;
+                            }
+                            _ => break,
+                        }
+                    }
+
+                    let end_radial = (start_radial + length) % radial_count;
+                    let span = (azimuths[end_radial] - azimuths[start_radial]).rem_euclid(360.0);
+                    let diameter = range * span.to_radians();
+
+                    if delta_velocity >= options.mesocyclone_min_delta_velocity_meters_per_second
+                        && diameter >= options.mesocyclone_min_diameter_meters
+                        && diameter <= options.mesocyclone_max_diameter_meters
+                        && delta_velocity / diameter >= options.mesocyclone_min_shear_per_second
+                    {
+                        add_segment(start_radial, length, delta_velocity);
+                    }
+                }
+            }
+        }
+    }
+
+    segments
+}
+
+/// Groups segments at adjacent gates whose radials overlap, returning groups of at least the given
+/// size as lists of segment indices.
+fn group_segments(
+    velocity: &PolarField,
+    segments: &[Segment],
+    min_segments: usize,
+) -> Vec<Vec<usize>> {
+    let radial_count = velocity.radial_count();
+    let mut by_gate = vec![Vec::new(); velocity.gate_count()];
+    for (index, segment) in segments.iter().enumerate() {
+        by_gate[segment.gate_index].push(index);
+    }
+
+    let mut parents: Vec<usize> = (0..segments.len()).collect();
+    fn root(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
+
+    for (index, segment) in segments.iter().enumerate() {
+        let next_gate = segment.gate_index + 1;
+        for other_index in by_gate.get(next_gate).into_iter().flatten() {
+            let other = &segments[*other_index];
+            let offset = (other.start_radial + radial_count - segment.start_radial) % radial_count;
+            if offset <= segment.length || radial_count - offset <= other.length {
+                let (a, b) = (root(&mut parents, index), root(&mut parents, *other_index));
+                parents[a] = b;
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = vec![Vec::new(); segments.len()];
+    for index in 0..segments.len() {
+        let group = root(&mut parents, index);
+        groups[group].push(index);
+    }
+
+    groups
+        .into_iter()
+        .filter(|group| !group.is_empty() && group.len() >= min_segments)
+        .collect()
+}
+
+/// Associates features from successive sweeps which are vertically aligned into detections.
+fn associate(
+    kind: VortexKind,
+    features: Vec<VortexFeature>,
+    site: &Site,
+    options: &VortexOptions,
+) -> Vec<VortexDetection> {
+    let levels = elevation_levels(features, VortexFeature::elevation_angle_degrees);
+    stack_levels(
+        levels,
+        &[options.association_distance_meters],
+        false,
+        |feature: &VortexFeature| {
+            east_north(feature.azimuth_degrees(), feature.ground_range_meters())
+        },
+    )
+    .into_iter()
+    .filter(|stack| stack.len() >= options.min_sweeps)
+    .filter_map(|stack| {
+        let base = stack.first()?;
+        let (latitude, longitude) = destination(
+            site.latitude(),
+            site.longitude(),
+            base.azimuth_degrees(),
+            base.ground_range_meters(),
+        );
+
+        Some(VortexDetection::new(kind, latitude, longitude, stack))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{moment, radial};
+    use nexrad_model::data::Product;
+
+    /// A sweep containing a Rankine vortex with the given core radius and maximum rotational
+    /// velocity centered at 30 km range and 180 degrees azimuth.
+    fn vortex_sweep(elevation_number: u8, elevation: f32, radius: f32, rotation: f32) -> Sweep {
+        let center = (0.0f32, -30_000.0f32);
+
+        let radials = (0..720)
+            .map(|azimuth_number| {
+                let azimuth = (azimuth_number as f32 + 0.5) * 0.5;
+                let values = (0..400)
+                    .map(|gate| {
+                        let range = 2_000.0 + gate as f32 * 250.0;
+                        let x = range * azimuth.to_radians().sin() - center.0;
+                        let y = range * azimuth.to_radians().cos() - center.1;
+                        let distance = x.hypot(y).max(1.0);
+                        let speed = if distance < radius {
+                            rotation * distance / radius
+                        } else {
+                            rotation * radius / distance
+                        };
+
+                        // Counterclockwise tangential wind projected onto the beam.
+                        let (u, v) = (-speed * y / distance, speed * x / distance);
+                        let radial_velocity =
+                            u * azimuth.to_radians().sin() + v * azimuth.to_radians().cos();
+                        (radial_velocity * 2.0 + 129.0).round().clamp(2.0, 255.0) as u8
+                    })
+                    .collect();
+
+                radial(0, azimuth_number, 0.5, elevation_number, elevation)
+                    .with_moment(Product::Velocity, moment(&Product::Velocity, values))
+            })
+            .collect();
+
+        Sweep::new(elevation_number, radials)
+    }
+
+    #[test]
+    fn detects_mesocyclone() {
+        let scan = Scan::new(
+            212,
+            vec![
+                vortex_sweep(1, 0.5, 2_000.0, 20.0),
+                vortex_sweep(2, 1.5, 2_000.0, 20.0),
+                vortex_sweep(3, 2.4, 2_000.0, 20.0),
+            ],
+        );
+        let site = Site::new(*b"KTLX", 35.33, -97.28, 370, 20);
+
+        let detections = detect_vortices(&scan, &site, &VortexOptions::default()).unwrap();
+        let mesocyclone = detections
+            .iter()
+            .find(|detection| detection.kind() == VortexKind::Mesocyclone)
+            .unwrap();
+
+        assert_eq!(mesocyclone.features().len(), 3);
+        assert!((mesocyclone.max_delta_velocity_meters_per_second().unwrap() - 40.0).abs() < 4.0);
+        assert!((mesocyclone.latitude_degrees() - 35.06).abs() < 0.02);
+        assert!((mesocyclone.longitude_degrees() + 97.28).abs() < 0.02);
+        assert!(mesocyclone.base_height_meters() < mesocyclone.top_height_meters());
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The kind of rotational feature detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VortexKind {
+    /// A storm-scale circulation, typically several kilometers across.
+    Mesocyclone,
+    /// A tornado vortex signature: an intense gate-to-gate velocity couplet.
+    TornadoVortexSignature,
+}
+
+/// A rotational feature observed within a single sweep.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VortexFeature {
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+    collection_timestamp: i64,
+    azimuth_degrees: f32,
+    slant_range_meters: f32,
+    ground_range_meters: f32,
+    height_meters: f32,
+    delta_velocity_meters_per_second: f32,
+    diameter_meters: f32,
+}
+
+impl VortexFeature {
+    /// Create a new vortex feature with the given properties.
+    pub fn new(
+        elevation_number: u8,
+        elevation_angle_degrees: f32,
+        collection_timestamp: i64,
+        azimuth_degrees: f32,
+        slant_range_meters: f32,
+        ground_range_meters: f32,
+        height_meters: f32,
+        delta_velocity_meters_per_second: f32,
+        diameter_meters: f32,
+    ) -> Self {
+        Self {
+            elevation_number,
+            elevation_angle_degrees,
+            collection_timestamp,
+            azimuth_degrees,
+            slant_range_meters,
+            ground_range_meters,
+            height_meters,
+            delta_velocity_meters_per_second,
+            diameter_meters,
+        }
+    }
+
+    /// The elevation number of the sweep this feature was found in.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// The mean elevation angle in degrees of the sweep this feature was found in.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// The collection timestamp of the sweep this feature was found in, in milliseconds since the
+    /// UNIX epoch.
+    pub fn collection_timestamp(&self) -> i64 {
+        self.collection_timestamp
+    }
+
+    /// The azimuth in degrees of this feature's center.
+    pub fn azimuth_degrees(&self) -> f32 {
+        self.azimuth_degrees
+    }
+
+    /// The slant range in meters from the radar to this feature's center.
+    pub fn slant_range_meters(&self) -> f32 {
+        self.slant_range_meters
+    }
+
+    /// The distance along the ground in meters from the radar to this feature's center.
+    pub fn ground_range_meters(&self) -> f32 {
+        self.ground_range_meters
+    }
+
+    /// The height in meters above the radar of this feature's center.
+    pub fn height_meters(&self) -> f32 {
+        self.height_meters
+    }
+
+    /// The largest velocity difference in meters per second across this feature.
+    pub fn delta_velocity_meters_per_second(&self) -> f32 {
+        self.delta_velocity_meters_per_second
+    }
+
+    /// The distance in meters between the velocity extrema across this feature.
+    pub fn diameter_meters(&self) -> f32 {
+        self.diameter_meters
+    }
+}
+
+impl Debug for VortexFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VortexFeature")
+            .field("elevation_number", &self.elevation_number())
+            .field("elevation_angle_degrees", &self.elevation_angle_degrees())
+            .field("collection_timestamp", &self.collection_timestamp())
+            .field("azimuth_degrees", &self.azimuth_degrees())
+            .field("slant_range_meters", &self.slant_range_meters())
+            .field("ground_range_meters", &self.ground_range_meters())
+            .field("height_meters", &self.height_meters())
+            .field(
+                "delta_velocity_meters_per_second",
+                &self.delta_velocity_meters_per_second(),
+            )
+            .field("diameter_meters", &self.diameter_meters())
+            .finish()
+    }
+}
+
+/// A rotational feature extending vertically through one or more sweeps of a scan.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VortexDetection {
+    kind: VortexKind,
+    latitude_degrees: f32,
+    longitude_degrees: f32,
+    features: Vec<VortexFeature>,
+}
+
+impl VortexDetection {
+    /// Create a new detection from its features, which should be ordered by ascending elevation.
+    pub fn new(
+        kind: VortexKind,
+        latitude_degrees: f32,
+        longitude_degrees: f32,
+        features: Vec<VortexFeature>,
+    ) -> Self {
+        Self {
+            kind,
+            latitude_degrees,
+            longitude_degrees,
+            features,
+        }
+    }
+
+    /// The kind of rotational feature detected.
+    pub fn kind(&self) -> VortexKind {
+        self.kind
+    }
+
+    /// The latitude in degrees of this detection's base.
+    pub fn latitude_degrees(&self) -> f32 {
+        self.latitude_degrees
+    }
+
+    /// The longitude in degrees of this detection's base.
+    pub fn longitude_degrees(&self) -> f32 {
+        self.longitude_degrees
+    }
+
+    /// The per-sweep features comprising this detection ordered by ascending elevation.
+    pub fn features(&self) -> &Vec<VortexFeature> {
+        self.features.as_ref()
+    }
+
+    /// The collection timestamp of this detection's base in milliseconds since the UNIX epoch.
+    pub fn collection_timestamp(&self) -> Option<i64> {
+        self.features
+            .first()
+            .map(|feature| feature.collection_timestamp())
+    }
+
+    /// The height in meters above the radar of this detection's lowest feature.
+    pub fn base_height_meters(&self) -> Option<f32> {
+        self.features
+            .iter()
+            .map(|feature| feature.height_meters())
+            .reduce(f32::min)
+    }
+
+    /// The height in meters above the radar of this detection's highest feature.
+    pub fn top_height_meters(&self) -> Option<f32> {
+        self.features
+            .iter()
+            .map(|feature| feature.height_meters())
+            .reduce(f32::max)
+    }
+
+    /// The largest velocity difference in meters per second across any of this detection's
+    /// features.
+    pub fn max_delta_velocity_meters_per_second(&self) -> Option<f32> {
+        self.features
+            .iter()
+            .map(|feature| feature.delta_velocity_meters_per_second())
+            .reduce(f32::max)
+    }
+
+    /// A strength rank from 1 to 25 derived from the maximum rotational velocity (half the velocity
+    /// difference), increasing by one for every 2.5 meters per second above 5 meters per second.
+    pub fn strength_rank(&self) -> u8 {
+        let rotational_velocity = self.max_delta_velocity_meters_per_second().unwrap_or(0.0) / 2.0;
+        (((rotational_velocity - 5.0) / 2.5).floor() + 1.0).clamp(1.0, 25.0) as u8
+    }
+}
+
+impl Debug for VortexDetection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VortexDetection")
+            .field("kind", &self.kind())
+            .field("latitude_degrees", &self.latitude_degrees())
+            .field("longitude_degrees", &self.longitude_degrees())
+            .field("base_height_meters", &self.base_height_meters())
+            .field("top_height_meters", &self.top_height_meters())
+            .field(
+                "max_delta_velocity_meters_per_second",
+                &self.max_delta_velocity_meters_per_second(),
+            )
+            .field("strength_rank", &self.strength_rank())
+            .field("features", &self.features())
+            .finish()
+    }
+}
@@ -0,0 +1,84 @@
+use crate::result::{Error, Result};
+
+/// Options controlling mesocyclone and tornado vortex signature detection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VortexOptions {
+    /// The minimum velocity difference in meters per second across a run of increasing velocities
+    /// for it to be considered part of a mesocyclone.
+    pub mesocyclone_min_delta_velocity_meters_per_second: f32,
+
+    /// The minimum azimuthal shear in inverse seconds across a run of increasing velocities for it
+    /// to be considered part of a mesocyclone.
+    pub mesocyclone_min_shear_per_second: f32,
+
+    /// The smallest diameter in meters of a mesocyclone's circulation.
+    pub mesocyclone_min_diameter_meters: f32,
+
+    /// The largest diameter in meters of a mesocyclone's circulation.
+    pub mesocyclone_max_diameter_meters: f32,
+
+    /// The minimum gate-to-gate velocity difference in meters per second for a couplet to be
+    /// considered part of a tornado vortex signature.
+    pub tvs_min_delta_velocity_meters_per_second: f32,
+
+    /// Tornado vortex signatures are only detected within this slant range in meters, beyond which
+    /// the beam is too wide to resolve them.
+    pub tvs_max_range_meters: f32,
+
+    /// The minimum number of shear segments along adjacent gates needed to form a feature in a
+    /// sweep.
+    pub min_segments: usize,
+
+    /// The largest distance in meters between features in successive sweeps for them to be
+    /// associated into the same vertical detection.
+    pub association_distance_meters: f32,
+
+    /// The minimum number of sweeps a detection must span.
+    pub min_sweeps: usize,
+
+    /// Whether velocity differences should be unfolded using the sweep's Nyquist velocity. This
+    /// should be enabled for raw Level II velocities.
+    pub unfold_velocities: bool,
+}
+
+impl VortexOptions {
+    /// Checks that these options are usable for detection.
+    pub fn validate(&self) -> Result<()> {
+        if self.mesocyclone_min_diameter_meters > self.mesocyclone_max_diameter_meters {
+            return Err(Error::InvalidOptions(
+                "mesocyclone diameter bounds must be ordered",
+            ));
+        }
+
+        if self.min_segments == 0 || self.min_sweeps == 0 {
+            return Err(Error::InvalidOptions(
+                "at least one segment and sweep are required",
+            ));
+        }
+
+        if self.association_distance_meters <= 0.0 {
+            return Err(Error::InvalidOptions(
+                "association distance must be positive",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for VortexOptions {
+    fn default() -> Self {
+        Self {
+            mesocyclone_min_delta_velocity_meters_per_second: 15.0,
+            mesocyclone_min_shear_per_second: 0.004,
+            mesocyclone_min_diameter_meters: 1_000.0,
+            mesocyclone_max_diameter_meters: 10_000.0,
+            tvs_min_delta_velocity_meters_per_second: 20.0,
+            tvs_max_range_meters: 100_000.0,
+            min_segments: 2,
+            association_distance_meters: 5_000.0,
+            min_sweeps: 2,
+            unfold_velocities: true,
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//!
+//! Estimation of systematic differential reflectivity (ZDR) bias. Light rain and dry aggregate
+//! snow both have small, well-known intrinsic differential reflectivity, so the difference between
+//! the observed and expected values where they are sampled reveals a radar's calibration bias.
+//! Light rain is identified by modest reflectivity and high correlation coefficient below the
+//! melting layer, and dry snow similarly well above it. Estimates can be compared with the
+//! calibration reported in the volume data and tracked over time to monitor drift.
+//!
+
+mod estimate;
+pub use estimate::*;
+
+mod options;
+pub use options::*;
+
+use crate::field::PolarField;
+use crate::result::Result;
+use nexrad_model::data::{Radial, Scan};
+use nexrad_model::geometry::beam_height_meters;
+use nexrad_model::meta::ZdrCalibration;
+
+/// Estimates a scan's differential reflectivity bias from light rain and dry snow, reporting it
+/// alongside the scan's volume calibration if provided.
+pub fn estimate_zdr_bias(
+    scan: &Scan,
+    calibration: Option<&ZdrCalibration>,
+    options: &ZdrOptions,
+) -> Result<ZdrBiasEstimate> {
+    options.validate()?;
+
+    let rain_max_height = options.freezing_level_height_meters - options.melting_layer_depth_meters;
+    let snow_min_height =
+        options.freezing_level_height_meters + options.snow_min_height_above_freezing_meters;
+
+    let mut rain = Vec::new();
+    let mut snow = Vec::new();
+    for sweep in scan.sweeps() {
+        let (reflectivity, differential_reflectivity, correlation) = match (
+            PolarField::from_sweep(sweep, Radial::reflectivity),
+            PolarField::from_sweep(sweep, Radial::differential_reflectivity),
+            PolarField::from_sweep(sweep, Radial::correlation_coefficient),
+        ) {
+            (Some(reflectivity), Some(differential_reflectivity), Some(correlation)) => {
+                (reflectivity, differential_reflectivity, correlation)
+            }
+            _ => continue,
+        };
+
+        let elevation_angle = reflectivity.elevation_angle_degrees().unwrap_or_default();
+        for (radial_index, azimuth) in reflectivity.azimuths_degrees().iter().enumerate() {
+            for gate_index in 0..reflectivity.gate_count() {
+                let range = reflectivity.gate_range_meters(gate_index);
+                if range < options.min_range_meters {
+                    continue;
+                }
+
+                let (reflectivity_value, differential_reflectivity_value, correlation_value) =
+                    match (
+                        reflectivity.value(radial_index, gate_index),
+                        differential_reflectivity.value_at(*azimuth, range),
+                        correlation.value_at(*azimuth, range),
+                    ) {
+                        (
+                            Some(reflectivity),
+                            Some(differential_reflectivity),
+                            Some(correlation),
+                        ) => (reflectivity, differential_reflectivity, correlation),
+                        _ => continue,
+                    };
+
+                if correlation_value < options.min_correlation {
+                    continue;
+                }
+
+                let height = beam_height_meters(range, elevation_angle);
+                let within =
+                    |(lower, upper): (f32, f32)| (lower..=upper).contains(&reflectivity_value);
+
+                if height <= rain_max_height && within(options.rain_reflectivity_dbz) {
+                    rain.push(differential_reflectivity_value);
+                } else if height >= snow_min_height && within(options.snow_reflectivity_dbz) {
+                    snow.push(differential_reflectivity_value);
+                }
+            }
+        }
+    }
+
+    let bias = |values: &mut Vec<f32>, expected: f32| {
+        if values.len() < options.min_gates {
+            return None;
+        }
+
+        median(values).map(|median| median - expected)
+    };
+
+    let collection_timestamp = scan
+        .sweeps()
+        .iter()
+        .flat_map(|sweep| sweep.radials())
+        .map(|radial| radial.collection_timestamp())
+        .min()
+        .unwrap_or_default();
+
+    Ok(ZdrBiasEstimate::new(
+        collection_timestamp,
+        bias(
+            &mut rain,
+            options.rain_expected_differential_reflectivity_db,
+        ),
+        rain.len(),
+        bias(
+            &mut snow,
+            options.snow_expected_differential_reflectivity_db,
+        ),
+        snow.len(),
+        calibration.map(|calibration| calibration.system_differential_reflectivity_db()),
+        calibration.map(|calibration| calibration.bias_estimate_db()),
+    ))
+}
+
+/// The median of the given values, which are sorted in place.
+fn median(values: &mut [f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_by(f32::total_cmp);
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[middle - 1] + values[middle]) / 2.0)
+    } else {
+        Some(values[middle])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{encoding, moment, radial};
+    use nexrad_model::data::{Product, Sweep};
+
+    /// Builds a sweep at the given elevation with uniform reflectivity, differential reflectivity
+    /// and correlation coefficient.
+    fn sweep(elevation_number: u8, elevation_angle: f32, z: f32, zdr: f32, cc: f32) -> Sweep {
+        let radials = (0..360)
+            .map(|azimuth_number| {
+                let uniform = |product: Product, value: f32| {
+                    let (scale, offset) = encoding(&product);
+                    let values = vec![(value * scale + offset).round() as u8; 240];
+                    (moment(&product, values), product)
+                };
+
+                [
+                    uniform(Product::Reflectivity, z),
+                    uniform(Product::DifferentialReflectivity, zdr),
+                    uniform(Product::CorrelationCoefficient, cc),
+                ]
+                .into_iter()
+                .fold(
+                    radial(
+                        1_700_000_000_000,
+                        azimuth_number,
+                        1.0,
+                        elevation_number,
+                        elevation_angle,
+                    ),
+                    |radial, (data, product)| radial.with_moment(product, data),
+                )
+            })
+            .collect();
+
+        Sweep::new(elevation_number, radials)
+    }
+
+    #[test]
+    fn estimates_bias() {
+        // Both light rain at low levels and snow aloft are observed 0.3 dB higher than expected.
+        let scan = Scan::new(
+            212,
+            vec![
+                sweep(1, 0.5, 24.0, 0.5, 0.99),
+                sweep(2, 10.0, 20.0, 0.4375, 0.99),
+            ],
+        );
+
+        let calibration = ZdrCalibration::new(-0.2, 0.25);
+        let estimate =
+            estimate_zdr_bias(&scan, Some(&calibration), &ZdrOptions::new(3_000.0)).unwrap();
+
+        assert!(estimate.rain_gate_count() > 0);
+        assert!(estimate.snow_gate_count() > 0);
+        assert!((estimate.rain_bias_db().unwrap() - 0.3).abs() < 0.01);
+        assert!((estimate.snow_bias_db().unwrap() - 0.2875).abs() < 0.01);
+        assert!((estimate.bias_db().unwrap() - 0.3).abs() < 0.02);
+        assert_eq!(estimate.system_differential_reflectivity_db(), Some(-0.2));
+        assert!((estimate.reported_bias_difference_db().unwrap() - 0.05).abs() < 0.02);
+
+        // Rain with a low correlation coefficient is not sampled.
+        let scan = Scan::new(212, vec![sweep(1, 0.5, 24.0, 0.5, 0.9)]);
+        let estimate = estimate_zdr_bias(&scan, None, &ZdrOptions::new(3_000.0)).unwrap();
+        assert_eq!(estimate.rain_gate_count(), 0);
+        assert_eq!(estimate.bias_db(), None);
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An estimate of a scan's systematic differential reflectivity bias, optionally alongside the
+/// calibration reported with the scan's volume data.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZdrBiasEstimate {
+    collection_timestamp: i64,
+    rain_bias_db: Option<f32>,
+    rain_gate_count: usize,
+    snow_bias_db: Option<f32>,
+    snow_gate_count: usize,
+    system_differential_reflectivity_db: Option<f32>,
+    reported_bias_db: Option<f32>,
+}
+
+impl ZdrBiasEstimate {
+    /// Create a new ZDR bias estimate with the given properties.
+    pub fn new(
+        collection_timestamp: i64,
+        rain_bias_db: Option<f32>,
+        rain_gate_count: usize,
+        snow_bias_db: Option<f32>,
+        snow_gate_count: usize,
+        system_differential_reflectivity_db: Option<f32>,
+        reported_bias_db: Option<f32>,
+    ) -> Self {
+        Self {
+            collection_timestamp,
+            rain_bias_db,
+            rain_gate_count,
+            snow_bias_db,
+            snow_gate_count,
+            system_differential_reflectivity_db,
+            reported_bias_db,
+        }
+    }
+
+    /// Collection time of the scan's first radial in milliseconds since the epoch.
+    pub fn collection_timestamp(&self) -> i64 {
+        self.collection_timestamp
+    }
+
+    /// The bias in dB estimated from light rain, if enough was sampled.
+    pub fn rain_bias_db(&self) -> Option<f32> {
+        self.rain_bias_db
+    }
+
+    /// The number of light rain gates sampled.
+    pub fn rain_gate_count(&self) -> usize {
+        self.rain_gate_count
+    }
+
+    /// The bias in dB estimated from dry aggregate snow, if enough was sampled.
+    pub fn snow_bias_db(&self) -> Option<f32> {
+        self.snow_bias_db
+    }
+
+    /// The number of dry snow gates sampled.
+    pub fn snow_gate_count(&self) -> usize {
+        self.snow_gate_count
+    }
+
+    /// The bias in dB combining the light rain and dry snow estimates, weighted by the number of
+    /// gates sampled in each.
+    pub fn bias_db(&self) -> Option<f32> {
+        match (self.rain_bias_db, self.snow_bias_db) {
+            (Some(rain), Some(snow)) => {
+                let rain_weight = self.rain_gate_count as f32;
+                let snow_weight = self.snow_gate_count as f32;
+                Some((rain * rain_weight + snow * snow_weight) / (rain_weight + snow_weight))
+            }
+            (rain, snow) => rain.or(snow),
+        }
+    }
+
+    /// The system differential reflectivity calibration in dB reported with the scan, if known.
+    pub fn system_differential_reflectivity_db(&self) -> Option<f32> {
+        self.system_differential_reflectivity_db
+    }
+
+    /// The RPG's weighted mean bias estimate in dB reported with the scan, if known.
+    pub fn reported_bias_db(&self) -> Option<f32> {
+        self.reported_bias_db
+    }
+
+    /// The difference in dB between this estimate and the RPG's reported bias estimate.
+    pub fn reported_bias_difference_db(&self) -> Option<f32> {
+        Some(self.bias_db()? - self.reported_bias_db?)
+    }
+}
+
+impl Debug for ZdrBiasEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZdrBiasEstimate")
+            .field("collection_timestamp", &self.collection_timestamp())
+            .field("rain_bias_db", &self.rain_bias_db())
+            .field("rain_gate_count", &self.rain_gate_count())
+            .field("snow_bias_db", &self.snow_bias_db())
+            .field("snow_gate_count", &self.snow_gate_count())
+            .field("bias_db", &self.bias_db())
+            .field(
+                "system_differential_reflectivity_db",
+                &self.system_differential_reflectivity_db(),
+            )
+            .field("reported_bias_db", &self.reported_bias_db())
+            .finish()
+    }
+}
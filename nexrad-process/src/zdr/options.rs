@@ -0,0 +1,83 @@
+use crate::result::{Error, Result};
+
+/// Options controlling estimation of differential reflectivity bias.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZdrOptions {
+    /// The height of the freezing level in meters above the radar.
+    pub freezing_level_height_meters: f32,
+
+    /// The depth in meters of the melting layer below the freezing level. Light rain is only
+    /// sampled below the melting layer.
+    pub melting_layer_depth_meters: f32,
+
+    /// Dry snow is only sampled at least this height in meters above the freezing level, where
+    /// aggregates are unlikely to be wet.
+    pub snow_min_height_above_freezing_meters: f32,
+
+    /// Gates nearer than this slant range in meters are ignored, avoiding clutter and the near
+    /// field of the antenna.
+    pub min_range_meters: f32,
+
+    /// The range of reflectivity in dBZ of light rain, in which drops are small and nearly
+    /// spherical.
+    pub rain_reflectivity_dbz: (f32, f32),
+
+    /// The intrinsic differential reflectivity in dB expected of light rain.
+    pub rain_expected_differential_reflectivity_db: f32,
+
+    /// The range of reflectivity in dBZ of dry aggregate snow.
+    pub snow_reflectivity_dbz: (f32, f32),
+
+    /// The intrinsic differential reflectivity in dB expected of dry aggregate snow.
+    pub snow_expected_differential_reflectivity_db: f32,
+
+    /// The minimum correlation coefficient of sampled gates, excluding mixed phase and
+    /// non-meteorological echo.
+    pub min_correlation: f32,
+
+    /// The minimum number of gates of light rain or dry snow for a bias to be estimated from them.
+    pub min_gates: usize,
+}
+
+impl ZdrOptions {
+    /// Create new options for the given freezing level height in meters above the radar, with
+    /// defaults for other options.
+    pub fn new(freezing_level_height_meters: f32) -> Self {
+        Self {
+            freezing_level_height_meters,
+            melting_layer_depth_meters: 1_000.0,
+            snow_min_height_above_freezing_meters: 1_000.0,
+            min_range_meters: 10_000.0,
+            rain_reflectivity_dbz: (20.0, 28.0),
+            rain_expected_differential_reflectivity_db: 0.2,
+            snow_reflectivity_dbz: (15.0, 25.0),
+            snow_expected_differential_reflectivity_db: 0.15,
+            min_correlation: 0.98,
+            min_gates: 500,
+        }
+    }
+
+    /// Checks that these options are usable for estimation.
+    pub fn validate(&self) -> Result<()> {
+        if self.melting_layer_depth_meters < 0.0 || self.snow_min_height_above_freezing_meters < 0.0
+        {
+            return Err(Error::InvalidOptions(
+                "melting layer margins must not be negative",
+            ));
+        }
+
+        if self.rain_reflectivity_dbz.0 > self.rain_reflectivity_dbz.1
+            || self.snow_reflectivity_dbz.0 > self.snow_reflectivity_dbz.1
+        {
+            return Err(Error::InvalidOptions(
+                "reflectivity ranges must be in ascending order",
+            ));
+        }
+
+        if self.min_gates == 0 {
+            return Err(Error::InvalidOptions("at least one gate must be required"));
+        }
+
+        Ok(())
+    }
+}